@@ -0,0 +1,108 @@
+//! Ethgen derive macros
+//!
+//! Procedural macros for the [ethgen](https://docs.rs/ethgen) crate, should
+//! be used through the `derive` feature of the main crate rather than
+//! directly.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod rlp;
+
+/// Derive RLP `Encodable` and `Decodable` for a struct
+///
+/// The struct is encoded as an RLP list of its fields in declaration order,
+/// which produces exactly the same bytes as the tuple of the fields would:
+///
+/// ```rust
+/// use ethgen::rlp::{self, Encodable, Rlp};
+///
+///
+///
+/// #[derive(Debug, PartialEq, Rlp)]
+/// struct Foo<'a> {
+///     nonce: u64,
+///     data: &'a [u8],
+/// }
+///
+///
+///
+/// let foo = Foo { nonce: 9, data: b"dog" };
+///
+/// let mut buf = [0u8; 16];
+/// let n = foo.encode(&mut buf);
+/// assert_eq!(n, foo.length());
+/// assert_eq!(b"\xc5\x09\x83dog", &buf[..n]);
+///
+/// assert_eq!((9u64, &b"dog"[..]).length(), n);
+/// assert_eq!(Ok(foo), rlp::decode(&buf[..n]));
+/// ```
+///
+/// <br>
+///
+/// ## Attributes
+///
+/// - `#[rlp(skip)]` on a field excludes it from the encoding, the field is
+///   set to `Default::default()` when decoded;
+/// - `#[rlp(transparent)]` on a struct with a single (not skipped) field
+///   encodes the struct as the field itself, rather than a list;
+///
+/// Fields of `Option<T>` type are considered trailing optional fields and
+/// must follow all the required ones. `None` fields are omitted from the
+/// encoding, a missing tail of the list is decoded as `None`. This is the way
+/// the block header encodes fields introduced by later forks:
+///
+/// ```rust
+/// use ethgen::rlp::{self, Encodable, Rlp};
+///
+///
+///
+/// #[derive(Debug, Default, PartialEq, Rlp)]
+/// struct Header {
+///     number: u64,
+///     #[rlp(skip)]
+///     cached: u64,
+///     base_fee: Option<u64>,
+///     blob_gas_used: Option<u64>,
+/// }
+///
+/// #[derive(Debug, PartialEq, Rlp)]
+/// #[rlp(transparent)]
+/// struct Nonce(u64);
+///
+///
+///
+/// let header = Header { number: 1, cached: 42, base_fee: Some(7), ..Default::default() };
+///
+/// let mut buf = [0u8; 16];
+/// let n = header.encode(&mut buf);
+/// assert_eq!(b"\xc2\x01\x07", &buf[..n]);
+///
+/// let decoded: Header = rlp::decode(&buf[..n]).unwrap();
+/// assert_eq!(Header { cached: 0, ..header }, decoded);
+///
+/// let n = Nonce(1024).encode(&mut buf);
+/// assert_eq!(b"\x82\x04\x00", &buf[..n]);
+/// ```
+///
+/// <br>
+///
+/// ## Panics
+///
+/// Encoding panics if an optional field is `None` while any of the following
+/// optional fields is set, as such value can not be represented.
+///
+/// <br>
+///
+/// ## Lifetimes
+///
+/// A struct with a lifetime parameter is decoded borrowing the data from the
+/// input with that lifetime, at most one lifetime parameter is supported.
+#[proc_macro_derive(Rlp, attributes(rlp))]
+pub fn derive_rlp(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    rlp::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,363 @@
+//! `#[derive(Rlp)]` implementation
+//!
+//! The expansion only refers to fully qualified paths, so it compiles next
+//! to items shadowing the prelude:
+//!
+//! ```rust
+//! # #![allow(dead_code, non_camel_case_types)]
+//! use ethgen::rlp::{self, Encodable, Rlp};
+//!
+//!
+//! enum Shadow { Some, None, Ok, Err }
+//! use Shadow::*;
+//!
+//! struct Default;
+//! type Result = ();
+//! type usize = ();
+//! type u8 = ();
+//! macro_rules! assert { ($($t:tt)*) => { compile_error!("shadowed") } }
+//!
+//! #[derive(Rlp)]
+//! struct Foo {
+//!     nonce: u64,
+//!     #[rlp(skip)]
+//!     cached: u64,
+//!     tip: ::core::option::Option<u64>,
+//! }
+//!
+//! #[derive(Rlp)]
+//! #[rlp(transparent)]
+//! struct Bar(u64, #[rlp(skip)] u64);
+//! ```
+//!
+//! Invalid input is reported as a compile error:
+//!
+//! ```compile_fail
+//! #[derive(ethgen::rlp::Rlp)]
+//! enum Foo { A, B }
+//! ```
+//!
+//! ```compile_fail
+//! #[derive(ethgen::rlp::Rlp)]
+//! struct Foo {
+//!     #[rlp(unknown)]
+//!     nonce: u64,
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! #[derive(ethgen::rlp::Rlp)]
+//! struct Foo {
+//!     tip: Option<u64>,
+//!     nonce: u64,
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! #[derive(ethgen::rlp::Rlp)]
+//! struct Foo(#[rlp(transparent)] u64);
+//! ```
+//!
+//! ```compile_fail
+//! #[derive(ethgen::rlp::Rlp)]
+//! #[rlp(transparent)]
+//! struct Foo(u64, u64);
+//! ```
+//!
+//! ```compile_fail
+//! #[derive(ethgen::rlp::Rlp)]
+//! struct Foo<'a, 'b>(&'a [u8], &'b [u8]);
+//! ```
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Fields, GenericArgument, GenericParam, Lifetime,
+    LifetimeParam, Member, PathArguments, Type,
+};
+
+struct Field {
+    member: Member,
+    ty: Type,
+    skip: bool,
+    /// Inner type of the trailing `Option<T>` field
+    optional: Option<Type>,
+}
+
+/// Parse `#[rlp(...)]` attributes, returning whether `skip` and
+/// `transparent` flags are present
+fn parse_attrs(attrs: &[syn::Attribute]) -> syn::Result<(bool, bool)> {
+    let (mut skip, mut transparent) = (false, false);
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("rlp")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else if meta.path.is_ident("transparent") {
+                transparent = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown rlp attribute"))
+            }
+        })?;
+    }
+
+    Ok((skip, transparent))
+}
+
+/// Extract `T` from `Option<T>`
+fn option_inner(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    match args.args.first() {
+        Some(GenericArgument::Type(inner)) if args.args.len() == 1 => Some(inner.clone()),
+        _ => None,
+    }
+}
+
+fn parse_fields(input: &DeriveInput) -> syn::Result<Vec<Field>> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "Rlp can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut result = Vec::new();
+    let mut optional_seen = false;
+
+    let members: Vec<Member> = match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|f| Member::Named(f.ident.clone().unwrap()))
+            .collect(),
+        Fields::Unnamed(unnamed) => (0..unnamed.unnamed.len())
+            .map(|i| Member::Unnamed(i.into()))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    for (field, member) in fields.iter().zip(members) {
+        let (skip, transparent) = parse_attrs(&field.attrs)?;
+
+        if transparent {
+            return Err(Error::new_spanned(
+                field,
+                "`transparent` is a struct attribute",
+            ));
+        }
+
+        let optional = option_inner(&field.ty);
+
+        if !skip {
+            match (optional.is_some(), optional_seen) {
+                (true, _) => optional_seen = true,
+                (false, true) => {
+                    return Err(Error::new_spanned(
+                        field,
+                        "required field can not follow an optional one",
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        result.push(Field {
+            member,
+            ty: field.ty.clone(),
+            skip,
+            optional,
+        });
+    }
+
+    Ok(result)
+}
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let (_, transparent) = parse_attrs(&input.attrs)?;
+    let fields = parse_fields(&input)?;
+
+    let name = &input.ident;
+    // Paths are fully qualified, so that the expansion is not affected by
+    // the items in scope of the derive
+    let rlp = quote!(::ethgen::rlp);
+    let option = quote!(::core::option::Option);
+    let result = quote!(::core::result::Result);
+    let default = quote!(::core::default::Default::default());
+    let usize = quote!(::core::primitive::usize);
+    let u8 = quote!(::core::primitive::u8);
+
+    // Lifetime the decoded data is borrowed for
+    let mut lifetimes = input.generics.lifetimes();
+    let (lt, own_lt) = match (lifetimes.next(), lifetimes.next()) {
+        (Some(_), Some(second)) => {
+            return Err(Error::new_spanned(
+                second,
+                "Rlp supports at most one lifetime parameter",
+            ))
+        }
+        (Some(param), None) => (param.lifetime.clone(), false),
+        (None, _) => (Lifetime::new("'__rlp", Span::call_site()), true),
+    };
+
+    let encoded: Vec<&Field> = fields.iter().filter(|f| !f.skip).collect();
+    let bound_types: Vec<&Type> = encoded
+        .iter()
+        .map(|f| f.optional.as_ref().unwrap_or(&f.ty))
+        .collect();
+    let has_types = input.generics.type_params().next().is_some();
+
+    let mut enc_generics = input.generics.clone();
+    let mut dec_generics = input.generics.clone();
+
+    if has_types {
+        let enc_where = enc_generics.make_where_clause();
+        for ty in &bound_types {
+            enc_where
+                .predicates
+                .push(parse_quote!(#ty: #rlp::Encodable));
+        }
+
+        let dec_where = dec_generics.make_where_clause();
+        for ty in &bound_types {
+            dec_where
+                .predicates
+                .push(parse_quote!(#ty: #rlp::Decodable<#lt>));
+        }
+    }
+
+    if own_lt {
+        dec_generics
+            .params
+            .insert(0, GenericParam::Lifetime(LifetimeParam::new(lt.clone())));
+    }
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let (enc_impl, _, enc_where) = enc_generics.split_for_impl();
+    let (dec_impl, _, dec_where) = dec_generics.split_for_impl();
+
+    let skipped = fields.iter().filter(|f| f.skip).map(|f| &f.member);
+
+    if transparent {
+        let [field] = encoded.as_slice() else {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "transparent struct must have exactly one encoded field",
+            ));
+        };
+
+        let member = &field.member;
+
+        return Ok(quote! {
+            impl #enc_impl #rlp::Encodable for #name #ty_generics #enc_where {
+                #[inline(always)]
+                fn length(&self) -> #usize {
+                    #rlp::Encodable::length(&self.#member)
+                }
+
+                #[inline(always)]
+                fn encode(&self, buf: &mut [#u8]) -> #usize {
+                    #rlp::Encodable::encode(&self.#member, buf)
+                }
+            }
+
+            impl #dec_impl #rlp::Decodable<#lt> for #name #ty_generics #dec_where {
+                #[inline(always)]
+                fn decode(buf: &mut &#lt [#u8]) -> #result<Self, #rlp::Error> {
+                    #result::Ok(Self {
+                        #member: #rlp::Decodable::decode(buf)?,
+                        #(#skipped: #default,)*
+                    })
+                }
+            }
+        });
+    }
+
+    let required: Vec<&Member> = encoded
+        .iter()
+        .filter(|f| f.optional.is_none())
+        .map(|f| &f.member)
+        .collect();
+    let optional: Vec<&Member> = encoded
+        .iter()
+        .filter(|f| f.optional.is_some())
+        .map(|f| &f.member)
+        .collect();
+
+    let payload_length = quote! {{
+        let mut payload_length: #usize = 0;
+        #(payload_length += #rlp::Encodable::length(&self.#required);)*
+
+        let mut gap = false;
+        #(match &self.#optional {
+            #option::Some(value) => {
+                ::core::assert!(!gap, "Optional RLP field is set after a missing one");
+                payload_length += #rlp::Encodable::length(value);
+            }
+            #option::None => gap = true,
+        })*
+
+        payload_length
+    }};
+
+    let decode = encoded.iter().map(|f| {
+        let member = &f.member;
+        match f.optional {
+            None => quote!(#member: #rlp::Decodable::decode(&mut payload)?),
+            Some(_) => quote! {
+                #member: match payload.is_empty() {
+                    true => #option::None,
+                    false => #option::Some(#rlp::Decodable::decode(&mut payload)?),
+                }
+            },
+        }
+    });
+
+    Ok(quote! {
+        impl #enc_impl #rlp::Encodable for #name #ty_generics #enc_where {
+            fn length(&self) -> #usize {
+                #rlp::Header::list(#payload_length).length_with_payload()
+            }
+
+            fn encode(&self, buf: &mut [#u8]) -> #usize {
+                let mut n = #rlp::Header::list(#payload_length).encode(buf);
+                #(n += #rlp::Encodable::encode(&self.#required, &mut buf[n..]);)*
+                #(if let #option::Some(value) = &self.#optional {
+                    n += #rlp::Encodable::encode(value, &mut buf[n..]);
+                })*
+                n
+            }
+        }
+
+        impl #dec_impl #rlp::Decodable<#lt> for #name #ty_generics #dec_where {
+            fn decode(buf: &mut &#lt [#u8]) -> #result<Self, #rlp::Error> {
+                let mut payload = #rlp::decode_list(buf)?;
+                let value = Self {
+                    #(#decode,)*
+                    #(#skipped: #default,)*
+                };
+
+                match payload.is_empty() {
+                    true => #result::Ok(value),
+                    false => #result::Err(#rlp::Error::LengthMismatch),
+                }
+            }
+        }
+    })
+}
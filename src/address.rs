@@ -0,0 +1,478 @@
+use crate::{as_hex, encoding::Data, hex, keccak, typenum::U20, Bytes};
+use core::{fmt, str::FromStr};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The error type returned when parsing an address from a string fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseAddressError {
+    /// The string is not 40 hex characters (optionally `0x` prefixed)
+    InvalidLength,
+    /// Non hex character presented in the string
+    InvalidCharacter,
+    /// Mixed-case string doesn't match the checksum
+    InvalidChecksum,
+}
+
+impl ParseAddressError {
+    /// Panic with the error description in const context
+    const fn panic(self) -> ! {
+        match self {
+            Self::InvalidLength => panic!("Invalid address length"),
+            Self::InvalidCharacter => panic!("Invalid hex character in address"),
+            Self::InvalidChecksum => panic!("Invalid address checksum"),
+        }
+    }
+}
+
+impl fmt::Display for ParseAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "invalid address length"),
+            Self::InvalidCharacter => write!(f, "invalid hex character in address"),
+            Self::InvalidChecksum => write!(f, "invalid address checksum"),
+        }
+    }
+}
+
+/// Address
+///
+/// A 20 byte account identifier. Serialized as lower case `0x` prefixed hex
+/// string the same way as `as_hex` does, while `Display` outputs the mixed
+/// case checksum encoding from [EIP-55](https://eips.ethereum.org/EIPS/eip-55).
+/// Deserialized strictly from `0x` followed by exactly 40 hex digits, the
+/// checksum is not validated.
+///
+/// ```rust
+/// use ethgen::{address, Address};
+///
+///
+/// const VITALIK: Address = address!("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+///
+/// let parsed: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".parse().unwrap();
+/// assert_eq!(VITALIK, parsed);
+///
+/// assert_eq!(
+///     "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+///     format!("{}", VITALIK),
+/// );
+///
+///
+/// let mut buf = [0u8; 64];
+/// let n = serde_json_core::to_slice(&VITALIK, &mut buf).unwrap();
+/// let json = core::str::from_utf8(&buf[..n]).unwrap();
+///
+/// assert_eq!(r#""0xd8da6bf26964af9d7eed9e03e53415d37aa96045""#, json);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Address(Bytes<U20>);
+
+impl Address {
+    pub const ZERO: Self = Self::new([0u8; 20]);
+
+    #[inline(always)]
+    pub const fn new(bytes: [u8; 20]) -> Self {
+        Self(Bytes::from_array(bytes))
+    }
+
+    #[inline(always)]
+    pub const fn into_array(self) -> [u8; 20] {
+        self.0.into_array()
+    }
+
+    #[inline(always)]
+    pub const fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    /// Mixed case checksum encoding with `0x` prefix
+    ///
+    /// Encodes [EIP-55](https://eips.ethereum.org/EIPS/eip-55) checksum if
+    /// `chain_id` is `None` and the chain-id-aware checksum described in
+    /// [EIP-1191](https://eips.ethereum.org/EIPS/eip-1191) otherwise (used
+    /// by RSK and derived networks).
+    ///
+    /// ```rust
+    /// use ethgen::address;
+    ///
+    ///
+    /// let addr = address!("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+    ///
+    /// assert_eq!(b"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", &addr.to_checksum(None));
+    /// assert_eq!(b"0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD", &addr.to_checksum(Some(30)));
+    /// ```
+    pub const fn to_checksum(&self, chain_id: Option<u64>) -> [u8; 42] {
+        let lower: [u8; 40] = hex::const_encode(self.0.as_slice());
+
+        // The hash input is prefixed with the decimal chain id and `0x` for
+        // EIP-1191, the longest possible input is `u64::MAX` (20 digits).
+        let mut input = [0u8; 62];
+        let mut n = 0usize;
+
+        if let Some(mut id) = chain_id {
+            let mut digits = [0u8; 20];
+            let mut len = 0usize;
+            loop {
+                digits[len] = b'0' + (id % 10) as u8;
+                id /= 10;
+                len += 1;
+                if id == 0 {
+                    break;
+                }
+            }
+
+            while len > 0 {
+                len -= 1;
+                input[n] = digits[len];
+                n += 1;
+            }
+
+            input[n] = b'0';
+            input[n + 1] = b'x';
+            n += 2;
+        }
+
+        let mut i = 0;
+        while i < 40 {
+            input[n + i] = lower[i];
+            i += 1;
+        }
+
+        let (input, _) = input.split_at(n + 40);
+        let hash = keccak::const_keccak256(input);
+
+        let mut out = [0u8; 42];
+        out[0] = b'0';
+        out[1] = b'x';
+
+        i = 0;
+        while i < 40 {
+            let nybl = (hash[i >> 1] >> ((1 - (i & 1)) << 2)) & 0x0f;
+            out[i + 2] = match lower[i] >= b'a' && nybl >= 8 {
+                true => lower[i] - 32,
+                false => lower[i],
+            };
+            i += 1;
+        }
+
+        out
+    }
+
+    /// Parse from 40 hex characters, optionally `0x` prefixed
+    ///
+    /// With `lenient` set, all lower and all upper case strings are accepted
+    /// without checksum validation, while mixed case strings are required to
+    /// have a valid checksum regardless.
+    const fn parse(
+        src: &str,
+        chain_id: Option<u64>,
+        lenient: bool,
+    ) -> Result<Self, ParseAddressError> {
+        let src = match src.as_bytes() {
+            [b'0', b'x', rest @ ..] => rest,
+            rest => rest,
+        };
+
+        if src.len() != 40 {
+            return Err(ParseAddressError::InvalidLength);
+        }
+
+        let (mut lower, mut upper, mut i) = (false, false, 0usize);
+        while i < 40 {
+            match src[i] {
+                b'0'..=b'9' => {}
+                b'a'..=b'f' => lower = true,
+                b'A'..=b'F' => upper = true,
+                _ => return Err(ParseAddressError::InvalidCharacter),
+            }
+            i += 1;
+        }
+
+        let address = Self::new(hex::const_decode(src));
+        if lenient && !(lower && upper) {
+            return Ok(address);
+        }
+
+        let checksum = address.to_checksum(chain_id);
+        i = 0;
+        while i < 40 {
+            if checksum[i + 2] != src[i] {
+                return Err(ParseAddressError::InvalidChecksum);
+            }
+            i += 1;
+        }
+
+        Ok(address)
+    }
+
+    /// Parse a checksummed address
+    ///
+    /// Requires the string to match [EIP-55](https://eips.ethereum.org/EIPS/eip-55)
+    /// checksum if `chain_id` is `None` or [EIP-1191](https://eips.ethereum.org/EIPS/eip-1191)
+    /// checksum for the given chain id otherwise.
+    ///
+    /// ```rust
+    /// use ethgen::{Address, ParseAddressError};
+    ///
+    ///
+    /// let addr = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    /// assert!(Address::parse_checksummed(addr, None).is_ok());
+    ///
+    /// let addr = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+    /// assert_eq!(Address::parse_checksummed(addr, None), Err(ParseAddressError::InvalidChecksum));
+    ///
+    /// let addr = "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD";
+    /// assert!(Address::parse_checksummed(addr, Some(30)).is_ok());
+    /// assert!(Address::parse_checksummed(addr, None).is_err());
+    /// ```
+    pub const fn parse_checksummed(
+        src: &str,
+        chain_id: Option<u64>,
+    ) -> Result<Self, ParseAddressError> {
+        Self::parse(src, chain_id, false)
+    }
+
+    /// Parse an address accepting all lower and all upper case strings
+    ///
+    /// Mixed case strings are still validated against the checksum.
+    ///
+    /// ```rust
+    /// use ethgen::{Address, ParseAddressError};
+    ///
+    ///
+    /// let addr = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+    /// assert!(Address::parse_lenient(addr, None).is_ok());
+    ///
+    /// let addr = "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED";
+    /// assert!(Address::parse_lenient(addr, None).is_ok());
+    ///
+    /// let addr = "0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    /// assert_eq!(Address::parse_lenient(addr, None), Err(ParseAddressError::InvalidChecksum));
+    /// ```
+    pub const fn parse_lenient(
+        src: &str,
+        chain_id: Option<u64>,
+    ) -> Result<Self, ParseAddressError> {
+        Self::parse(src, chain_id, true)
+    }
+
+    /// Parse an address in const context, see `address!`
+    ///
+    /// ## Panics
+    ///
+    /// - if the string is not 40 hex characters (optionally `0x` prefixed);
+    /// - if mixed case string doesn't match EIP-55 checksum;
+    pub const fn const_from_str(src: &str) -> Self {
+        match Self::parse_lenient(src, None) {
+            Ok(address) => address,
+            Err(err) => err.panic(),
+        }
+    }
+}
+
+impl From<Bytes<U20>> for Address {
+    fn from(value: Bytes<U20>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Address> for Bytes<U20> {
+    fn from(value: Address) -> Self {
+        value.0
+    }
+}
+
+impl From<&Address> for Bytes<U20> {
+    fn from(value: &Address) -> Self {
+        value.0
+    }
+}
+
+impl From<[u8; 20]> for Address {
+    fn from(value: [u8; 20]) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Address> for [u8; 20] {
+    fn from(value: Address) -> Self {
+        value.into_array()
+    }
+}
+
+impl AsRef<[u8]> for Address {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+/// Parse an address with strict EIP-55 checksum validation
+impl FromStr for Address {
+    type Err = ParseAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_checksummed(s, None)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let checksum = self.to_checksum(None);
+
+        // Safe since `to_checksum` can return only valid ascii characters
+        f.pad(unsafe { core::str::from_utf8_unchecked(&checksum) })
+    }
+}
+
+impl fmt::Debug for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Address {}", self)
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        as_hex::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Data::<U20>::deserialize(deserializer).map(|data| Self(data.0))
+    }
+}
+
+/// Parse an address at compilation time
+///
+/// Accepts 40 hex characters, optionally `0x` prefixed. All lower and all
+/// upper case literals are accepted as is, while mixed case literals are
+/// validated against EIP-55 checksum.
+///
+/// ## Panics
+///
+/// - if the literal is not 40 hex characters;
+/// - if mixed case literal doesn't match EIP-55 checksum;
+///
+/// <br>
+///
+/// ## Example
+///
+/// ```
+/// use ethgen::{address, Address};
+///
+///
+/// const WETH: Address = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+/// assert_eq!(WETH, address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"));
+/// ```
+///
+/// ```compile_fail
+/// use ethgen::{address, Address};
+///
+///
+/// const WETH: Address = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756CC2");
+/// ```
+#[macro_export]
+macro_rules! address {
+    ($s:literal) => {{
+        const ADDRESS: $crate::Address = $crate::Address::const_from_str($s);
+        ADDRESS
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_eip55() {
+        let cases = [
+            "0x52908400098527886E0F7030069857D2E4169EE7",
+            "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+            "0xde709f2102306220921060314715629080e2fb77",
+            "0x27b1fdb04752bbc536007a920d24acb045561c26",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+
+        for case in cases {
+            let address: Address = case.parse().unwrap();
+            assert_eq!(address.to_checksum(None), case.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_eip1191() {
+        let cases = [
+            (30, "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD"),
+            (30, "0xFb6916095cA1Df60bb79ce92cE3EA74c37c5d359"),
+            (30, "0xDBF03B407c01E7CD3cBea99509D93F8Dddc8C6FB"),
+            (30, "0xD1220A0Cf47c7B9BE7a2e6ba89F429762E7B9adB"),
+            (31, "0x5aAeb6053F3e94c9b9A09F33669435E7EF1BEaEd"),
+            (31, "0xFb6916095CA1dF60bb79CE92ce3Ea74C37c5D359"),
+            (31, "0xdbF03B407C01E7cd3cbEa99509D93f8dDDc8C6fB"),
+            (31, "0xd1220a0CF47c7B9Be7A2E6Ba89f429762E7b9adB"),
+        ];
+
+        for (chain_id, case) in cases {
+            let address = Address::parse_checksummed(case, Some(chain_id)).unwrap();
+            assert_eq!(address.to_checksum(Some(chain_id)), case.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA".parse::<Address>(),
+            Err(ParseAddressError::InvalidLength)
+        );
+        assert_eq!(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg".parse::<Address>(),
+            Err(ParseAddressError::InvalidCharacter)
+        );
+        assert_eq!(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD".parse::<Address>(),
+            Err(ParseAddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let (address, _): (Address, usize) =
+            serde_json_core::from_str(r#""0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed""#).unwrap();
+        assert_eq!(
+            address,
+            address!("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+        );
+
+        let cases = [
+            r#""""#,
+            r#""0x""#,
+            r#""0x1""#,
+            r#""0x12""#,
+            r#""0xzz""#,
+            // Missing prefix
+            r#""5aaeb6053f3e94c9b9a09f33669435e7ef1beaed""#,
+            // Short, long and odd length
+            r#""0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea""#,
+            r#""0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed00""#,
+            r#""0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed0""#,
+            // Non-hex digit
+            r#""0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaeg""#,
+        ];
+
+        for case in cases {
+            assert!(
+                serde_json_core::from_str::<Address>(case).is_err(),
+                "{}",
+                case
+            );
+        }
+    }
+}
@@ -0,0 +1,924 @@
+use crate::biguint::{uint256, BigUint, ParseIntError, TryFromIntError, UINT256_DIGITS};
+use crate::{typenum::U32, Bytes};
+use core::{cmp::Ordering, fmt, ops::*, str::FromStr};
+use crypto_bigint::Uint;
+
+/// BigInt
+///
+/// Two's complement signed integer over `crypto_bigint::Uint`, the signed
+/// counterpart of `BigUint` with the same checked-by-default policy: any
+/// overflow, division by zero or shift by more than the bit width panics.
+///
+/// The bit representation matches the Solidity ABI encoding of `intN`, so
+/// the conversion to and from `BigUint` of the same size is a reinterpretation
+/// of bits with `from_bits` / `to_bits`, while `TryFrom` preserves the value.
+///
+/// ```rust
+/// use ethgen::{int256, uint256};
+///
+///
+/// let price = int256::from(1850i64);
+/// let delta = int256::from(-2000i64);
+///
+/// assert!(price + delta < int256::ZERO);
+/// assert_eq!(price + delta, int256::from(-150i64));
+/// assert_eq!((price + delta).unsigned_abs(), uint256::from(150u64));
+/// assert_eq!(int256::from(-7i64) / int256::from(2i64), int256::from(-3i64));
+/// assert_eq!(int256::from(-7i64) % int256::from(2i64), int256::from(-1i64));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BigInt<const N: usize>(Uint<N>);
+
+impl<const N: usize> BigInt<N> {
+    pub const ZERO: Self = Self(Uint::<N>::ZERO);
+    pub const ONE: Self = Self(Uint::<N>::ONE);
+    pub const MINUS_ONE: Self = Self(Uint::<N>::MAX);
+    pub const MIN: Self = Self(Uint::<N>::ONE.shl_vartime(Uint::<N>::BITS - 1));
+    pub const MAX: Self = Self(Uint::<N>::MAX.shr_vartime(1));
+
+    /// Total amount of bits in the representation
+    pub const BITS: u32 = Uint::<N>::BITS as u32;
+
+    /// Reinterpret two's complement bits as a signed integer
+    #[inline(always)]
+    pub const fn from_bits(value: BigUint<N>) -> Self {
+        Self(*value.as_uint())
+    }
+
+    /// Two's complement bits of the signed integer
+    #[inline(always)]
+    pub const fn to_bits(&self) -> BigUint<N> {
+        BigUint::from_uint(self.0)
+    }
+
+    /// Construct from a primitive in const context
+    #[inline(always)]
+    pub const fn from_i64(value: i64) -> Self {
+        Self::from_sign_abs(value < 0, Uint::<N>::from_u64(value.unsigned_abs()))
+    }
+
+    /// Construct from a primitive in const context
+    #[inline(always)]
+    pub const fn from_i128(value: i128) -> Self {
+        Self::from_sign_abs(value < 0, Uint::<N>::from_u128(value.unsigned_abs()))
+    }
+
+    #[inline(always)]
+    const fn from_sign_abs(negative: bool, abs: Uint<N>) -> Self {
+        match negative {
+            true => Self(abs.wrapping_neg()),
+            false => Self(abs),
+        }
+    }
+
+    /// Parse from an optionally signed decimal or `0x` prefixed hex string
+    /// in const context
+    ///
+    /// The sign is applied to the magnitude, so hex strings are not treated
+    /// as two's complement bits: `-0x01` is parsed as `-1`, while `0xff..ff`
+    /// overflows.
+    ///
+    /// ```rust
+    /// use ethgen::{int256, ParseIntError};
+    ///
+    ///
+    /// assert_eq!(int256::try_from_str("-42"), Ok(int256::from(-42i64)));
+    /// assert_eq!(int256::try_from_str("+0x2a"), Ok(int256::from(42i64)));
+    /// assert_eq!(int256::try_from_str("-"), Err(ParseIntError::Empty));
+    /// ```
+    pub const fn try_from_str(src: &str) -> Result<Self, ParseIntError> {
+        let (negative, abs) = match src.as_bytes() {
+            [b'-', rest @ ..] => (true, rest),
+            [b'+', rest @ ..] => (false, rest),
+            rest => (false, rest),
+        };
+
+        // Safe since only a leading ascii character has been stripped
+        let abs = unsafe { core::str::from_utf8_unchecked(abs) };
+        let abs = match BigUint::<N>::try_from_str(abs) {
+            Ok(abs) => abs,
+            Err(err) => return Err(err),
+        };
+
+        let limit = BigUint::from_uint(Self::MIN.0);
+        match (negative, abs.const_cmp(&limit)) {
+            (_, Ordering::Less) | (true, Ordering::Equal) => {
+                Ok(Self::from_sign_abs(negative, *abs.as_uint()))
+            }
+            _ => Err(ParseIntError::Overflow),
+        }
+    }
+
+    /// Parse from an optionally signed decimal or `0x` prefixed hex string
+    /// in const context
+    ///
+    /// ## Panics
+    ///
+    /// - if the string is empty;
+    /// - if invalid digit presented in the string;
+    /// - if the value doesn't fit into the type;
+    pub const fn const_from_str(src: &str) -> Self {
+        match Self::try_from_str(src) {
+            Ok(value) => value,
+            Err(err) => err.panic(),
+        }
+    }
+
+    /// Take the least significant 64 bits, acting as `as` casting
+    #[inline(always)]
+    pub const fn as_i64(&self) -> i64 {
+        self.to_bits().as_u64() as i64
+    }
+
+    /// Take the least significant 128 bits, acting as `as` casting
+    #[inline(always)]
+    pub const fn as_i128(&self) -> i128 {
+        self.to_bits().as_u128() as i128
+    }
+
+    #[inline(always)]
+    pub const fn is_zero(&self) -> bool {
+        self.0.bits_vartime() == 0
+    }
+
+    #[inline(always)]
+    pub const fn is_negative(&self) -> bool {
+        self.0.bit_vartime(Uint::<N>::BITS - 1)
+    }
+
+    #[inline(always)]
+    pub const fn is_positive(&self) -> bool {
+        !self.is_negative() && !self.is_zero()
+    }
+
+    /// Returns `-1`, `0` or `1` depending on the sign
+    #[inline(always)]
+    pub const fn signum(&self) -> Self {
+        match (self.is_negative(), self.is_zero()) {
+            (true, _) => Self::MINUS_ONE,
+            (false, true) => Self::ZERO,
+            (false, false) => Self::ONE,
+        }
+    }
+
+    /// Absolute value as unsigned integer, which can never overflow
+    #[inline(always)]
+    pub const fn unsigned_abs(&self) -> BigUint<N> {
+        match self.is_negative() {
+            true => BigUint::from_uint(self.0.wrapping_neg()),
+            false => BigUint::from_uint(self.0),
+        }
+    }
+
+    /// Const context alternative to `Ord::cmp`
+    #[inline(always)]
+    pub const fn const_cmp(&self, rhs: &Self) -> Ordering {
+        // Flipping the sign bit maps the signed range onto the unsigned one
+        // preserving the order.
+        let lhs = Uint::bitxor(&self.0, &Self::MIN.0);
+        let rhs = Uint::bitxor(&rhs.0, &Self::MIN.0);
+        lhs.cmp_vartime(&rhs)
+    }
+
+    #[inline(always)]
+    const fn is_min(&self) -> bool {
+        matches!(self.0.cmp_vartime(&Self::MIN.0), Ordering::Equal)
+    }
+
+    #[inline(always)]
+    pub const fn overflowing_neg(&self) -> (Self, bool) {
+        (Self(self.0.wrapping_neg()), self.is_min())
+    }
+
+    #[inline(always)]
+    pub const fn overflowing_abs(&self) -> (Self, bool) {
+        match self.is_negative() {
+            true => self.overflowing_neg(),
+            false => (*self, false),
+        }
+    }
+
+    #[inline(always)]
+    pub const fn overflowing_add(&self, rhs: &Self) -> (Self, bool) {
+        let res = Self(self.0.wrapping_add(&rhs.0));
+        let (a, b, r) = (self.is_negative(), rhs.is_negative(), res.is_negative());
+        (res, a == b && r != a)
+    }
+
+    #[inline(always)]
+    pub const fn overflowing_sub(&self, rhs: &Self) -> (Self, bool) {
+        let res = Self(self.0.wrapping_sub(&rhs.0));
+        let (a, b, r) = (self.is_negative(), rhs.is_negative(), res.is_negative());
+        (res, a != b && r != a)
+    }
+
+    #[inline(always)]
+    pub const fn overflowing_mul(&self, rhs: &Self) -> (Self, bool) {
+        let res = Self(self.0.wrapping_mul(&rhs.0));
+        let (abs, overflow) = self.unsigned_abs().overflowing_mul(&rhs.unsigned_abs());
+
+        // Magnitude limit is `2^(BITS-1)` for negative and one less for
+        // positive results, which is exactly the bits of `MIN`.
+        let limit = BigUint::from_uint(Self::MIN.0);
+        let overflow = overflow
+            || match self.is_negative() != rhs.is_negative() {
+                true => matches!(abs.const_cmp(&limit), Ordering::Greater),
+                false => !matches!(abs.const_cmp(&limit), Ordering::Less),
+            };
+
+        (res, overflow)
+    }
+
+    /// Truncating division, returning `true` for `MIN / -1`
+    ///
+    /// ## Panics
+    ///
+    /// - if `rhs` is zero;
+    #[inline(always)]
+    pub const fn overflowing_div(&self, rhs: &Self) -> (Self, bool) {
+        let abs = self.unsigned_abs().wrapping_div(&rhs.unsigned_abs());
+        let res = Self::from_sign_abs(self.is_negative() != rhs.is_negative(), *abs.as_uint());
+        (res, self.is_min() && rhs.is_minus_one())
+    }
+
+    /// Remainder with the sign of `self`, returning `true` for `MIN % -1`
+    ///
+    /// ## Panics
+    ///
+    /// - if `rhs` is zero;
+    #[inline(always)]
+    pub const fn overflowing_rem(&self, rhs: &Self) -> (Self, bool) {
+        let abs = self.unsigned_abs().wrapping_rem(&rhs.unsigned_abs());
+        let res = Self::from_sign_abs(self.is_negative(), *abs.as_uint());
+        (res, self.is_min() && rhs.is_minus_one())
+    }
+
+    #[inline(always)]
+    const fn is_minus_one(&self) -> bool {
+        matches!(self.0.cmp_vartime(&Uint::<N>::MAX), Ordering::Equal)
+    }
+
+    /// Shift left by `rhs % BITS` bits, returning `true` if `rhs >= BITS`
+    #[inline(always)]
+    pub const fn overflowing_shl(&self, rhs: u32) -> (Self, bool) {
+        let res = self.0.shl_vartime((rhs % Self::BITS) as usize);
+        (Self(res), rhs >= Self::BITS)
+    }
+
+    /// Arithmetic (sign extending) shift right by `rhs % BITS` bits,
+    /// returning `true` if `rhs >= BITS`
+    #[inline(always)]
+    pub const fn overflowing_shr(&self, rhs: u32) -> (Self, bool) {
+        let shift = (rhs % Self::BITS) as usize;
+        let res = match self.is_negative() {
+            true => Uint::not(&Uint::not(&self.0).shr_vartime(shift)),
+            false => self.0.shr_vartime(shift),
+        };
+        (Self(res), rhs >= Self::BITS)
+    }
+
+    #[inline(always)]
+    pub const fn checked_neg(&self) -> Option<Self> {
+        match self.overflowing_neg() {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn checked_abs(&self) -> Option<Self> {
+        match self.overflowing_abs() {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        match self.overflowing_add(rhs) {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        match self.overflowing_sub(rhs) {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        match self.overflowing_mul(rhs) {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    /// Checked division, returns `None` if `rhs` is zero or for `MIN / -1`
+    #[inline(always)]
+    pub const fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        match rhs.is_zero() {
+            true => None,
+            false => match self.overflowing_div(rhs) {
+                (res, false) => Some(res),
+                _ => None,
+            },
+        }
+    }
+
+    /// Checked remainder, returns `None` if `rhs` is zero or for `MIN % -1`
+    #[inline(always)]
+    pub const fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+        match rhs.is_zero() {
+            true => None,
+            false => match self.overflowing_rem(rhs) {
+                (res, false) => Some(res),
+                _ => None,
+            },
+        }
+    }
+
+    #[inline(always)]
+    pub const fn checked_shl(&self, rhs: u32) -> Option<Self> {
+        match self.overflowing_shl(rhs) {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn checked_shr(&self, rhs: u32) -> Option<Self> {
+        match self.overflowing_shr(rhs) {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_neg(&self) -> Self {
+        self.overflowing_neg().0
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_abs(&self) -> Self {
+        self.overflowing_abs().0
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_add(&self, rhs: &Self) -> Self {
+        Self(self.0.wrapping_add(&rhs.0))
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_sub(&self, rhs: &Self) -> Self {
+        Self(self.0.wrapping_sub(&rhs.0))
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_mul(&self, rhs: &Self) -> Self {
+        Self(self.0.wrapping_mul(&rhs.0))
+    }
+
+    /// Wrapping division, `MIN / -1` results in `MIN`
+    ///
+    /// ## Panics
+    ///
+    /// - if `rhs` is zero;
+    #[inline(always)]
+    pub const fn wrapping_div(&self, rhs: &Self) -> Self {
+        assert!(!rhs.is_zero(), "Attempt to divide by zero");
+        self.overflowing_div(rhs).0
+    }
+
+    /// Wrapping remainder, `MIN % -1` results in zero
+    ///
+    /// ## Panics
+    ///
+    /// - if `rhs` is zero;
+    #[inline(always)]
+    pub const fn wrapping_rem(&self, rhs: &Self) -> Self {
+        assert!(
+            !rhs.is_zero(),
+            "Attempt to calculate the remainder with a divisor of zero"
+        );
+        self.overflowing_rem(rhs).0
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_shl(&self, rhs: u32) -> Self {
+        self.overflowing_shl(rhs).0
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_shr(&self, rhs: u32) -> Self {
+        self.overflowing_shr(rhs).0
+    }
+
+    #[inline(always)]
+    pub const fn saturating_neg(&self) -> Self {
+        match self.checked_neg() {
+            Some(res) => res,
+            None => Self::MAX,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn saturating_abs(&self) -> Self {
+        match self.checked_abs() {
+            Some(res) => res,
+            None => Self::MAX,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn saturating_add(&self, rhs: &Self) -> Self {
+        match (self.checked_add(rhs), rhs.is_negative()) {
+            (Some(res), _) => res,
+            (None, true) => Self::MIN,
+            (None, false) => Self::MAX,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn saturating_sub(&self, rhs: &Self) -> Self {
+        match (self.checked_sub(rhs), rhs.is_negative()) {
+            (Some(res), _) => res,
+            (None, true) => Self::MAX,
+            (None, false) => Self::MIN,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn saturating_mul(&self, rhs: &Self) -> Self {
+        match (
+            self.checked_mul(rhs),
+            self.is_negative() != rhs.is_negative(),
+        ) {
+            (Some(res), _) => res,
+            (None, true) => Self::MIN,
+            (None, false) => Self::MAX,
+        }
+    }
+
+    /// Raise to the power of `exp`, returns `None` on overflow
+    pub const fn checked_pow(&self, mut exp: u32) -> Option<Self> {
+        let (mut base, mut acc) = (*self, Self::ONE);
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = match acc.checked_mul(&base) {
+                    Some(acc) => acc,
+                    None => return None,
+                };
+            }
+
+            exp >>= 1;
+            if exp > 0 {
+                base = match base.checked_mul(&base) {
+                    Some(base) => base,
+                    None => return None,
+                };
+            }
+        }
+
+        Some(acc)
+    }
+
+    /// Raise to the power of `exp`
+    ///
+    /// ## Panics
+    ///
+    /// - if the result overflows;
+    pub const fn pow(&self, exp: u32) -> Self {
+        match self.checked_pow(exp) {
+            Some(res) => res,
+            None => panic!("Attempt to multiply with overflow"),
+        }
+    }
+}
+
+impl<const N: usize> From<i64> for BigInt<N> {
+    fn from(value: i64) -> Self {
+        Self::from_i64(value)
+    }
+}
+
+impl<const N: usize> From<i128> for BigInt<N> {
+    fn from(value: i128) -> Self {
+        Self::from_i128(value)
+    }
+}
+
+impl<const N: usize> TryFrom<BigInt<N>> for i64 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: BigInt<N>) -> Result<Self, Self::Error> {
+        match BigInt::from_i64(i64::MIN) <= value && value <= BigInt::from_i64(i64::MAX) {
+            true => Ok(value.as_i64()),
+            false => Err(TryFromIntError(())),
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<BigInt<N>> for i128 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: BigInt<N>) -> Result<Self, Self::Error> {
+        match BigInt::from_i128(i128::MIN) <= value && value <= BigInt::from_i128(i128::MAX) {
+            true => Ok(value.as_i128()),
+            false => Err(TryFromIntError(())),
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<BigUint<N>> for BigInt<N> {
+    type Error = TryFromIntError;
+
+    fn try_from(value: BigUint<N>) -> Result<Self, Self::Error> {
+        match Self::from_bits(value) {
+            res if res.is_negative() => Err(TryFromIntError(())),
+            res => Ok(res),
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<BigInt<N>> for BigUint<N> {
+    type Error = TryFromIntError;
+
+    fn try_from(value: BigInt<N>) -> Result<Self, Self::Error> {
+        match value.is_negative() {
+            true => Err(TryFromIntError(())),
+            false => Ok(value.to_bits()),
+        }
+    }
+}
+
+impl<const N: usize> FromStr for BigInt<N> {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_str(s)
+    }
+}
+
+impl<const N: usize> PartialOrd for BigInt<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for BigInt<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.const_cmp(other)
+    }
+}
+
+macro_rules! impl_checked_op {
+    ($op:ident, $fn:ident, $assign:ident, $assign_fn:ident, $overflowing:ident, $msg:literal) => {
+        impl<const N: usize> $op for BigInt<N> {
+            type Output = Self;
+
+            fn $fn(self, rhs: Self) -> Self {
+                match self.$overflowing(&rhs) {
+                    (res, false) => res,
+                    _ => panic!($msg),
+                }
+            }
+        }
+
+        impl<const N: usize> $assign for BigInt<N> {
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $op::$fn(*self, rhs);
+            }
+        }
+    };
+}
+
+impl_checked_op!(
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    overflowing_add,
+    "Attempt to add with overflow"
+);
+impl_checked_op!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    overflowing_sub,
+    "Attempt to subtract with overflow"
+);
+impl_checked_op!(
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    overflowing_mul,
+    "Attempt to multiply with overflow"
+);
+impl_checked_op!(
+    Div,
+    div,
+    DivAssign,
+    div_assign,
+    overflowing_div,
+    "Attempt to divide with overflow"
+);
+impl_checked_op!(
+    Rem,
+    rem,
+    RemAssign,
+    rem_assign,
+    overflowing_rem,
+    "Attempt to calculate the remainder with overflow"
+);
+
+macro_rules! impl_shift_op {
+    ($op:ident, $fn:ident, $assign:ident, $assign_fn:ident, $checked:ident, $msg:literal) => {
+        impl<const N: usize> $op<u32> for BigInt<N> {
+            type Output = Self;
+
+            fn $fn(self, rhs: u32) -> Self {
+                self.$checked(rhs).expect($msg)
+            }
+        }
+
+        impl<const N: usize> $assign<u32> for BigInt<N> {
+            fn $assign_fn(&mut self, rhs: u32) {
+                *self = $op::$fn(*self, rhs);
+            }
+        }
+    };
+}
+
+impl_shift_op!(
+    Shl,
+    shl,
+    ShlAssign,
+    shl_assign,
+    checked_shl,
+    "Attempt to shift left with overflow"
+);
+impl_shift_op!(
+    Shr,
+    shr,
+    ShrAssign,
+    shr_assign,
+    checked_shr,
+    "Attempt to shift right with overflow"
+);
+
+macro_rules! impl_bit_op {
+    ($op:ident, $fn:ident, $assign:ident, $assign_fn:ident) => {
+        impl<const N: usize> $op for BigInt<N> {
+            type Output = Self;
+
+            fn $fn(self, rhs: Self) -> Self {
+                Self(self.0.$fn(&rhs.0))
+            }
+        }
+
+        impl<const N: usize> $assign for BigInt<N> {
+            fn $assign_fn(&mut self, rhs: Self) {
+                self.0 = self.0.$fn(&rhs.0);
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl<const N: usize> Not for BigInt<N> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(self.0.not())
+    }
+}
+
+impl<const N: usize> Neg for BigInt<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg().expect("Attempt to negate with overflow")
+    }
+}
+
+/// int256
+///
+/// ```should_panic
+/// use ethgen::int256;
+///
+/// let a = int256::MIN;
+/// let b = int256::ONE;
+/// let _ = a - b;
+/// ```
+#[cfg(target_pointer_width = "32")]
+#[allow(non_camel_case_types)]
+pub type int256 = BigInt<8>;
+
+/// int256
+///
+/// ```should_panic
+/// use ethgen::int256;
+///
+/// let a = int256::MIN;
+/// let b = int256::ONE;
+/// let _ = a - b;
+/// ```
+#[cfg(target_pointer_width = "64")]
+#[allow(non_camel_case_types)]
+pub type int256 = BigInt<4>;
+
+/// Alias for `int256`
+#[allow(non_camel_case_types)]
+pub type int = int256;
+
+impl int256 {
+    /// Construct from the Big Endian two's complement bytes in const context
+    #[inline(always)]
+    pub const fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        Self::from_bits(uint256::from_be_bytes(bytes))
+    }
+
+    /// Big Endian two's complement bytes in const context
+    ///
+    /// This is also the ABI encoding of `int256`.
+    ///
+    /// ```rust
+    /// use ethgen::int256;
+    ///
+    ///
+    /// const BYTES: [u8; 32] = int256::from_i64(-2).to_be_bytes();
+    /// assert_eq!(&[0xff; 31], &BYTES[..31]);
+    /// assert_eq!(0xfe, BYTES[31]);
+    /// ```
+    #[inline(always)]
+    pub const fn to_be_bytes(&self) -> [u8; 32] {
+        self.to_bits().to_be_bytes()
+    }
+}
+
+impl From<Bytes<U32>> for int256 {
+    fn from(value: Bytes<U32>) -> Self {
+        Self::from_be_bytes(value.into_array())
+    }
+}
+
+impl From<int256> for Bytes<U32> {
+    fn from(value: int256) -> Self {
+        Self::from_array(value.to_be_bytes())
+    }
+}
+
+impl From<&int256> for Bytes<U32> {
+    fn from(value: &int256) -> Self {
+        Self::from_array(value.to_be_bytes())
+    }
+}
+
+impl fmt::Debug for int256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.is_negative() {
+            true => write!(f, "int256 -{:x}", self.unsigned_abs().as_uint()),
+            false => write!(f, "int256 {:x}", self.0),
+        }
+    }
+}
+
+/// Decimal representation of `int256`
+///
+/// ```rust
+/// use ethgen::int256;
+///
+///
+/// assert_eq!("-42", format!("{}", int256::from(-42i64)));
+/// assert_eq!("+42", format!("{:+}", int256::from(42i64)));
+/// assert_eq!(
+///     "-57896044618658097711785492504343953926634992332820282019728792003956564819968",
+///     format!("{}", int256::MIN),
+/// );
+/// ```
+impl fmt::Display for int256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0u8; UINT256_DIGITS];
+        let pos = self.unsigned_abs().write_decimal(&mut buf);
+
+        // Safe since only ascii digits are written into the buffer
+        let s = unsafe { core::str::from_utf8_unchecked(&buf[pos..]) };
+        f.pad_integral(!self.is_negative(), "", s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_primitives() {
+        for v in [
+            0i128,
+            1,
+            -1,
+            42,
+            -42,
+            i64::MIN as i128,
+            i128::MIN,
+            i128::MAX,
+        ] {
+            let x = int256::from(v);
+            assert_eq!(i128::try_from(x), Ok(v));
+            assert_eq!(x.is_negative(), v < 0);
+        }
+
+        assert!(i64::try_from(int256::from(i64::MAX as i128 + 1)).is_err());
+        assert!(i64::try_from(int256::from(i64::MIN as i128 - 1)).is_err());
+        assert_eq!(i64::try_from(int256::from(-5i64)), Ok(-5));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(int256::MAX.checked_add(&int256::ONE), None);
+        assert_eq!(int256::MIN.checked_sub(&int256::ONE), None);
+        assert_eq!(int256::MIN.checked_neg(), None);
+        assert_eq!(int256::MIN.checked_div(&int256::MINUS_ONE), None);
+        assert_eq!(int256::MIN.checked_rem(&int256::MINUS_ONE), None);
+        assert_eq!(int256::MIN.wrapping_div(&int256::MINUS_ONE), int256::MIN);
+
+        let half = int256::ONE << 254;
+        assert_eq!(half.checked_mul(&int256::from(2i64)), None);
+        assert_eq!(half.checked_mul(&int256::from(-2i64)), Some(int256::MIN));
+        assert_eq!(half.saturating_mul(&int256::from(3i64)), int256::MAX);
+        assert_eq!(half.saturating_mul(&int256::from(-3i64)), int256::MIN);
+        assert_eq!(int256::MIN.saturating_sub(&int256::ONE), int256::MIN);
+        assert_eq!(int256::MAX.saturating_sub(&int256::MINUS_ONE), int256::MAX);
+    }
+
+    #[test]
+    fn test_cmp() {
+        let mut values = [
+            int256::MAX,
+            int256::from(1i64),
+            int256::MIN,
+            int256::from(-1i64),
+            int256::ZERO,
+        ];
+        values.sort();
+
+        assert_eq!(
+            values,
+            [
+                int256::MIN,
+                int256::from(-1i64),
+                int256::ZERO,
+                int256::from(1i64),
+                int256::MAX,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shift() {
+        assert_eq!(int256::from(-16i64) >> 2, int256::from(-4i64));
+        assert_eq!(int256::from(-1i64) >> 255, int256::MINUS_ONE);
+        assert_eq!(int256::from(16i64) >> 2, int256::from(4i64));
+        assert_eq!(int256::from(-3i64) << 1, int256::from(-6i64));
+    }
+
+    #[test]
+    fn test_unsigned() {
+        assert_eq!(uint256::try_from(int256::from(-1i64)).ok(), None);
+        assert_eq!(int256::try_from(uint256::MAX).ok(), None);
+        assert_eq!(int256::from_bits(uint256::MAX), int256::MINUS_ONE);
+        assert_eq!(int256::MIN.unsigned_abs(), uint256::ONE << 255);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let min = "-57896044618658097711785492504343953926634992332820282019728792003956564819968";
+        let max = "57896044618658097711785492504343953926634992332820282019728792003956564819967";
+        let over = "57896044618658097711785492504343953926634992332820282019728792003956564819968";
+
+        assert_eq!(min.parse(), Ok(int256::MIN));
+        assert_eq!(max.parse(), Ok(int256::MAX));
+        assert_eq!(over.parse::<int256>(), Err(ParseIntError::Overflow));
+        assert_eq!("-0".parse(), Ok(int256::ZERO));
+        assert_eq!("--1".parse::<int256>(), Err(ParseIntError::InvalidDigit));
+    }
+
+    #[test]
+    fn test_bytes() {
+        let x = int256::from(-262988610i64);
+        let bytes: Bytes<U32> = x.into();
+
+        assert_eq!(&bytes[28..], &(-262988610i32).to_be_bytes());
+        assert_eq!(int256::from(bytes), x);
+    }
+}
@@ -1,16 +1,480 @@
-use core::{fmt, ops::Add};
-use crypto_bigint::{CheckedAdd, Uint};
+use crate::{typenum::U32, Bytes};
+use core::{cmp::Ordering, fmt, ops::*, str::FromStr};
+use crypto_bigint::{Uint, Word};
+
+/// The error type returned when a checked integral type conversion fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromIntError(pub(crate) ());
+
+impl fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "out of range integral type conversion attempted")
+    }
+}
+
+/// The error type returned when parsing an integer from a string fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseIntError {
+    /// Empty string or a bare `0x` prefix
+    Empty,
+    /// Character which is not a digit in the detected radix
+    InvalidDigit,
+    /// The value doesn't fit into the target type
+    Overflow,
+}
+
+impl ParseIntError {
+    /// Panic with the error description in const context
+    pub(crate) const fn panic(self) -> ! {
+        match self {
+            Self::Empty => panic!("Cannot parse integer from empty string"),
+            Self::InvalidDigit => panic!("Invalid digit found in string"),
+            Self::Overflow => panic!("Number too large to fit in target type"),
+        }
+    }
+}
+
+impl fmt::Display for ParseIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "cannot parse integer from empty string"),
+            Self::InvalidDigit => write!(f, "invalid digit found in string"),
+            Self::Overflow => write!(f, "number too large to fit in target type"),
+        }
+    }
+}
 
 /// BigUint
 ///
 /// A thin wrapper for `crypto_bigint::Uint` with checked math operaions
 /// enabled by default with additional implementations for required features.
+///
+/// Operators follow the same policy as Rust primitives in debug builds: any
+/// overflow, division by zero or shift by more than the bit width panics.
+/// For the explicit behavior use `checked_*`, `wrapping_*`, `saturating_*`
+/// or `overflowing_*` methods.
+///
+/// ```rust
+/// use ethgen::uint256;
+///
+///
+/// let fee = uint256::from(21000u64) * uint256::from(30_000_000_000u64);
+/// let balance = uint256::from(1_000_000_000_000_000u64);
+///
+/// assert!(fee < balance);
+/// assert_eq!(balance - fee, uint256::from(370_000_000_000_000u64));
+/// assert_eq!(fee.checked_sub(&balance), None);
+/// assert_eq!(fee.saturating_sub(&balance), uint256::ZERO);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct BigUint<const N: usize>(Uint<N>);
 
 impl<const N: usize> BigUint<N> {
     pub const ZERO: Self = Self(Uint::<N>::ZERO);
     pub const ONE: Self = Self(Uint::<N>::ONE);
     pub const MAX: Self = Self(Uint::<N>::MAX);
+
+    /// Total amount of bits in the representation
+    pub const BITS: u32 = Uint::<N>::BITS as u32;
+
+    /// Construct from the inner `crypto_bigint::Uint` in const context
+    #[inline(always)]
+    pub const fn from_uint(value: Uint<N>) -> Self {
+        Self(value)
+    }
+
+    /// Construct from a primitive in const context
+    #[inline(always)]
+    pub const fn from_u64(value: u64) -> Self {
+        Self(Uint::<N>::from_u64(value))
+    }
+
+    /// Construct from a primitive in const context
+    #[inline(always)]
+    pub const fn from_u128(value: u128) -> Self {
+        Self(Uint::<N>::from_u128(value))
+    }
+
+    /// Parse from a decimal or `0x` prefixed hex string in const context
+    ///
+    /// Hex digits are accepted in lower, upper and mixed case, leading zeroes
+    /// are allowed in both radixes.
+    ///
+    /// ```rust
+    /// use ethgen::{uint256, ParseIntError};
+    ///
+    ///
+    /// const GWEI: uint256 = match uint256::try_from_str("1000000000") {
+    ///     Ok(gwei) => gwei,
+    ///     Err(_) => panic!(),
+    /// };
+    ///
+    /// assert_eq!(uint256::try_from_str("0x3B9ACA00"), Ok(GWEI));
+    /// assert_eq!(uint256::try_from_str("0x"), Err(ParseIntError::Empty));
+    /// assert_eq!(uint256::try_from_str("1e9"), Err(ParseIntError::InvalidDigit));
+    /// ```
+    pub const fn try_from_str(src: &str) -> Result<Self, ParseIntError> {
+        let src = src.as_bytes();
+
+        let hex = src.len() >= 2 && src[0] == b'0' && (src[1] == b'x' || src[1] == b'X');
+        let (radix, mut i) = match hex {
+            true => (16u64, 2usize),
+            false => (10u64, 0usize),
+        };
+
+        if i == src.len() {
+            return Err(ParseIntError::Empty);
+        }
+
+        let mut acc = Self::ZERO;
+        while i < src.len() {
+            let digit = match src[i] {
+                b'0'..=b'9' => src[i] - b'0',
+                b'a'..=b'f' if hex => src[i] - b'a' + 10,
+                b'A'..=b'F' if hex => src[i] - b'A' + 10,
+                _ => return Err(ParseIntError::InvalidDigit),
+            };
+
+            acc = match acc.checked_mul(&Self::from_u64(radix)) {
+                Some(acc) => acc,
+                None => return Err(ParseIntError::Overflow),
+            };
+
+            acc = match acc.checked_add(&Self::from_u64(digit as u64)) {
+                Some(acc) => acc,
+                None => return Err(ParseIntError::Overflow),
+            };
+
+            i += 1;
+        }
+
+        Ok(acc)
+    }
+
+    /// Parse from a decimal or `0x` prefixed hex string in const context
+    ///
+    /// ## Panics
+    ///
+    /// - if the string is empty;
+    /// - if invalid digit presented in the string;
+    /// - if the value doesn't fit into the type;
+    pub const fn const_from_str(src: &str) -> Self {
+        match Self::try_from_str(src) {
+            Ok(value) => value,
+            Err(err) => err.panic(),
+        }
+    }
+
+    /// Write decimal digits into the buffer from the higher end
+    ///
+    /// Returns the index of the most significant digit.
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain all the digits;
+    pub(crate) fn write_decimal(&self, buf: &mut [u8]) -> usize {
+        // The largest power of ten fitting into `u64`
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        const CHUNK_DIGITS: usize = 19;
+
+        let (mut value, mut pos) = (*self, buf.len());
+        loop {
+            let (q, r) = match value.checked_div_rem(&Self::from_u64(CHUNK)) {
+                Some(res) => res,
+                None => unreachable!(),
+            };
+
+            let (mut r, mut n) = (r.as_u64(), 0usize);
+            while n < CHUNK_DIGITS && (r > 0 || n == 0 || !q.is_zero()) {
+                pos -= 1;
+                buf[pos] = b'0' + (r % 10) as u8;
+                r /= 10;
+                n += 1;
+            }
+
+            if q.is_zero() {
+                return pos;
+            }
+
+            value = q;
+        }
+    }
+
+    /// Take the least significant 64 bits, acting as `as` casting
+    #[inline(always)]
+    pub const fn as_u64(&self) -> u64 {
+        self.as_u128() as u64
+    }
+
+    /// Take the least significant 128 bits, acting as `as` casting
+    #[inline(always)]
+    pub const fn as_u128(&self) -> u128 {
+        let words = self.0.as_words();
+        let (mut acc, mut i) = (0u128, 0usize);
+
+        while i < N && i * (Word::BITS as usize) < 128 {
+            acc |= (words[i] as u128) << (i as u32 * Word::BITS);
+            i += 1;
+        }
+
+        acc
+    }
+
+    /// Borrow the inner `crypto_bigint::Uint`
+    #[inline(always)]
+    pub const fn as_uint(&self) -> &Uint<N> {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub const fn is_zero(&self) -> bool {
+        self.0.bits_vartime() == 0
+    }
+
+    /// Amount of significant bits (position of the highest set bit + 1)
+    #[inline(always)]
+    pub const fn bits(&self) -> u32 {
+        self.0.bits_vartime() as u32
+    }
+
+    #[inline(always)]
+    pub const fn leading_zeros(&self) -> u32 {
+        self.0.leading_zeros() as u32
+    }
+
+    #[inline(always)]
+    pub const fn trailing_zeros(&self) -> u32 {
+        self.0.trailing_zeros() as u32
+    }
+
+    /// Test the bit at the given position (zero is the least significant)
+    #[inline(always)]
+    pub const fn bit(&self, index: u32) -> bool {
+        self.0.bit_vartime(index as usize)
+    }
+
+    /// Const context alternative to `Ord::cmp`
+    #[inline(always)]
+    pub const fn const_cmp(&self, rhs: &Self) -> Ordering {
+        self.0.cmp_vartime(&rhs.0)
+    }
+
+    #[inline(always)]
+    pub const fn overflowing_add(&self, rhs: &Self) -> (Self, bool) {
+        let (res, carry) = self.0.adc(&rhs.0, crypto_bigint::Limb::ZERO);
+        (Self(res), carry.0 != 0)
+    }
+
+    #[inline(always)]
+    pub const fn overflowing_sub(&self, rhs: &Self) -> (Self, bool) {
+        let (res, borrow) = self.0.sbb(&rhs.0, crypto_bigint::Limb::ZERO);
+        (Self(res), borrow.0 != 0)
+    }
+
+    #[inline(always)]
+    pub const fn overflowing_mul(&self, rhs: &Self) -> (Self, bool) {
+        let (lo, hi) = self.0.mul_wide(&rhs.0);
+        (Self(lo), hi.bits_vartime() != 0)
+    }
+
+    /// Shift left by `rhs % BITS` bits, returning `true` if `rhs >= BITS`
+    ///
+    /// Follows the semantics of primitives: the overflow flag reports the
+    /// shift amount overflow, the bits shifted out are discarded.
+    #[inline(always)]
+    pub const fn overflowing_shl(&self, rhs: u32) -> (Self, bool) {
+        let res = self.0.shl_vartime((rhs % Self::BITS) as usize);
+        (Self(res), rhs >= Self::BITS)
+    }
+
+    /// Shift right by `rhs % BITS` bits, returning `true` if `rhs >= BITS`
+    #[inline(always)]
+    pub const fn overflowing_shr(&self, rhs: u32) -> (Self, bool) {
+        let res = self.0.shr_vartime((rhs % Self::BITS) as usize);
+        (Self(res), rhs >= Self::BITS)
+    }
+
+    #[inline(always)]
+    pub const fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        match self.overflowing_add(rhs) {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        match self.overflowing_sub(rhs) {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        match self.overflowing_mul(rhs) {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    /// Checked division, returns `None` if `rhs` is zero
+    #[inline(always)]
+    pub const fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        match rhs.is_zero() {
+            true => None,
+            false => Some(Self(self.0.wrapping_div(&rhs.0))),
+        }
+    }
+
+    /// Checked remainder, returns `None` if `rhs` is zero
+    #[inline(always)]
+    pub const fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+        match rhs.is_zero() {
+            true => None,
+            false => Some(Self(self.0.wrapping_rem(&rhs.0))),
+        }
+    }
+
+    /// Checked division with remainder, returns `None` if `rhs` is zero
+    #[inline(always)]
+    pub fn checked_div_rem(&self, rhs: &Self) -> Option<(Self, Self)> {
+        let rhs = Option::<_>::from(crypto_bigint::NonZero::new(rhs.0))?;
+        let (q, r) = self.0.div_rem(&rhs);
+        Some((Self(q), Self(r)))
+    }
+
+    #[inline(always)]
+    pub const fn checked_shl(&self, rhs: u32) -> Option<Self> {
+        match self.overflowing_shl(rhs) {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn checked_shr(&self, rhs: u32) -> Option<Self> {
+        match self.overflowing_shr(rhs) {
+            (res, false) => Some(res),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_add(&self, rhs: &Self) -> Self {
+        Self(self.0.wrapping_add(&rhs.0))
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_sub(&self, rhs: &Self) -> Self {
+        Self(self.0.wrapping_sub(&rhs.0))
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_mul(&self, rhs: &Self) -> Self {
+        Self(self.0.wrapping_mul(&rhs.0))
+    }
+
+    /// Wrapping division, which can never wrap for unsigned integers
+    ///
+    /// ## Panics
+    ///
+    /// - if `rhs` is zero;
+    #[inline(always)]
+    pub const fn wrapping_div(&self, rhs: &Self) -> Self {
+        match self.checked_div(rhs) {
+            Some(res) => res,
+            None => panic!("Attempt to divide by zero"),
+        }
+    }
+
+    /// Wrapping remainder, which can never wrap for unsigned integers
+    ///
+    /// ## Panics
+    ///
+    /// - if `rhs` is zero;
+    #[inline(always)]
+    pub const fn wrapping_rem(&self, rhs: &Self) -> Self {
+        match self.checked_rem(rhs) {
+            Some(res) => res,
+            None => panic!("Attempt to calculate the remainder with a divisor of zero"),
+        }
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_neg(&self) -> Self {
+        Self(self.0.wrapping_neg())
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_shl(&self, rhs: u32) -> Self {
+        self.overflowing_shl(rhs).0
+    }
+
+    #[inline(always)]
+    pub const fn wrapping_shr(&self, rhs: u32) -> Self {
+        self.overflowing_shr(rhs).0
+    }
+
+    #[inline(always)]
+    pub const fn saturating_add(&self, rhs: &Self) -> Self {
+        match self.checked_add(rhs) {
+            Some(res) => res,
+            None => Self::MAX,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn saturating_sub(&self, rhs: &Self) -> Self {
+        match self.checked_sub(rhs) {
+            Some(res) => res,
+            None => Self::ZERO,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn saturating_mul(&self, rhs: &Self) -> Self {
+        match self.checked_mul(rhs) {
+            Some(res) => res,
+            None => Self::MAX,
+        }
+    }
+
+    /// Raise to the power of `exp`, returns `None` on overflow
+    pub const fn checked_pow(&self, mut exp: u32) -> Option<Self> {
+        let (mut base, mut acc) = (*self, Self::ONE);
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = match acc.checked_mul(&base) {
+                    Some(acc) => acc,
+                    None => return None,
+                };
+            }
+
+            exp >>= 1;
+            if exp > 0 {
+                base = match base.checked_mul(&base) {
+                    Some(base) => base,
+                    None => return None,
+                };
+            }
+        }
+
+        Some(acc)
+    }
+
+    /// Raise to the power of `exp`
+    ///
+    /// ## Panics
+    ///
+    /// - if the result overflows;
+    pub const fn pow(&self, exp: u32) -> Self {
+        match self.checked_pow(exp) {
+            Some(res) => res,
+            None => panic!("Attempt to multiply with overflow"),
+        }
+    }
 }
 
 impl<const N: usize> From<Uint<N>> for BigUint<N> {
@@ -19,9 +483,9 @@ impl<const N: usize> From<Uint<N>> for BigUint<N> {
     }
 }
 
-impl<const N: usize> Into<Uint<N>> for BigUint<N> {
-    fn into(self) -> Uint<N> {
-        self.0
+impl<const N: usize> From<BigUint<N>> for Uint<N> {
+    fn from(value: BigUint<N>) -> Self {
+        value.0
     }
 }
 
@@ -31,14 +495,175 @@ impl<const N: usize> From<u64> for BigUint<N> {
     }
 }
 
-impl<const N: usize> Add for BigUint<N> {
+impl<const N: usize> From<u128> for BigUint<N> {
+    fn from(value: u128) -> Self {
+        Self(Uint::<N>::from_u128(value))
+    }
+}
+
+impl<const N: usize> TryFrom<BigUint<N>> for u64 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: BigUint<N>) -> Result<Self, Self::Error> {
+        match value.bits() <= u64::BITS {
+            true => Ok(value.as_u64()),
+            false => Err(TryFromIntError(())),
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<BigUint<N>> for u128 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: BigUint<N>) -> Result<Self, Self::Error> {
+        match value.bits() <= u128::BITS {
+            true => Ok(value.as_u128()),
+            false => Err(TryFromIntError(())),
+        }
+    }
+}
+
+impl<const N: usize> FromStr for BigUint<N> {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_str(s)
+    }
+}
+
+impl<const N: usize> PartialOrd for BigUint<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for BigUint<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+macro_rules! impl_checked_op {
+    ($op:ident, $fn:ident, $assign:ident, $assign_fn:ident, $checked:ident, $msg:literal) => {
+        impl<const N: usize> $op for BigUint<N> {
+            type Output = Self;
+
+            fn $fn(self, rhs: Self) -> Self {
+                self.$checked(&rhs).expect($msg)
+            }
+        }
+
+        impl<const N: usize> $assign for BigUint<N> {
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $op::$fn(*self, rhs);
+            }
+        }
+    };
+}
+
+impl_checked_op!(
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    checked_add,
+    "Attempt to add with overflow"
+);
+impl_checked_op!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    checked_sub,
+    "Attempt to subtract with overflow"
+);
+impl_checked_op!(
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    checked_mul,
+    "Attempt to multiply with overflow"
+);
+impl_checked_op!(
+    Div,
+    div,
+    DivAssign,
+    div_assign,
+    checked_div,
+    "Attempt to divide by zero"
+);
+impl_checked_op!(
+    Rem,
+    rem,
+    RemAssign,
+    rem_assign,
+    checked_rem,
+    "Attempt to calculate the remainder with a divisor of zero"
+);
+
+macro_rules! impl_shift_op {
+    ($op:ident, $fn:ident, $assign:ident, $assign_fn:ident, $checked:ident, $msg:literal) => {
+        impl<const N: usize> $op<u32> for BigUint<N> {
+            type Output = Self;
+
+            fn $fn(self, rhs: u32) -> Self {
+                self.$checked(rhs).expect($msg)
+            }
+        }
+
+        impl<const N: usize> $assign<u32> for BigUint<N> {
+            fn $assign_fn(&mut self, rhs: u32) {
+                *self = $op::$fn(*self, rhs);
+            }
+        }
+    };
+}
+
+impl_shift_op!(
+    Shl,
+    shl,
+    ShlAssign,
+    shl_assign,
+    checked_shl,
+    "Attempt to shift left with overflow"
+);
+impl_shift_op!(
+    Shr,
+    shr,
+    ShrAssign,
+    shr_assign,
+    checked_shr,
+    "Attempt to shift right with overflow"
+);
+
+macro_rules! impl_bit_op {
+    ($op:ident, $fn:ident, $assign:ident, $assign_fn:ident) => {
+        impl<const N: usize> $op for BigUint<N> {
+            type Output = Self;
+
+            fn $fn(self, rhs: Self) -> Self {
+                Self(self.0.$fn(&rhs.0))
+            }
+        }
+
+        impl<const N: usize> $assign for BigUint<N> {
+            fn $assign_fn(&mut self, rhs: Self) {
+                self.0 = self.0.$fn(&rhs.0);
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl<const N: usize> Not for BigUint<N> {
     type Output = Self;
 
-    fn add(self, rhs: Self) -> Self {
-        self.0
-            .checked_add(&rhs.0)
-            .expect("Attempt to add with overflow")
-            .into()
+    fn not(self) -> Self {
+        Self(self.0.not())
     }
 }
 
@@ -72,8 +697,228 @@ pub type uint256 = BigUint<4>;
 #[allow(non_camel_case_types)]
 pub type uint = uint256;
 
+impl uint256 {
+    /// Construct from the Big Endian byte representation in const context
+    #[inline(always)]
+    pub const fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        Self(Uint::from_be_slice(&bytes))
+    }
+
+    /// Big Endian byte representation in const context
+    ///
+    /// This is also the ABI encoding of `uint256`.
+    ///
+    /// ```rust
+    /// use ethgen::uint256;
+    ///
+    ///
+    /// const BYTES: [u8; 32] = uint256::from_u64(0x0face342).to_be_bytes();
+    /// assert_eq!(&[0x0f, 0xac, 0xe3, 0x42], &BYTES[28..]);
+    /// assert_eq!(uint256::from_be_bytes(BYTES), uint256::from(0x0face342u64));
+    /// ```
+    #[inline(always)]
+    pub const fn to_be_bytes(&self) -> [u8; 32] {
+        const WORD: usize = (Word::BITS >> 3) as usize;
+
+        let words = self.0.as_words();
+        let (mut bytes, mut i) = ([0u8; 32], 0usize);
+
+        while i < 32 {
+            let word = words[words.len() - 1 - i / WORD];
+            bytes[i] = (word >> ((WORD - 1 - i % WORD) << 3)) as u8;
+            i += 1;
+        }
+
+        bytes
+    }
+}
+
+impl From<Bytes<U32>> for uint256 {
+    fn from(value: Bytes<U32>) -> Self {
+        Self::from_be_bytes(value.into_array())
+    }
+}
+
+impl From<uint256> for Bytes<U32> {
+    fn from(value: uint256) -> Self {
+        Self::from_array(value.to_be_bytes())
+    }
+}
+
+impl From<&uint256> for Bytes<U32> {
+    fn from(value: &uint256) -> Self {
+        Self::from_array(value.to_be_bytes())
+    }
+}
+
 impl fmt::Debug for uint256 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "uint256 {:x}", self.0)
     }
 }
+
+/// Maximum amount of decimal digits in `uint256`
+pub(crate) const UINT256_DIGITS: usize = 78;
+
+/// Decimal representation of `uint256`
+///
+/// Respects width, fill and alignment flags of the formatter.
+///
+/// ```rust
+/// use ethgen::uint256;
+///
+///
+/// let wei = uint256::from(10u64).pow(18);
+///
+/// assert_eq!("1000000000000000000", format!("{}", wei));
+/// assert_eq!("   42", format!("{:>5}", uint256::from(42u64)));
+/// assert_eq!(
+///     "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+///     format!("{}", uint256::MAX),
+/// );
+/// ```
+impl fmt::Display for uint256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0u8; UINT256_DIGITS];
+        let pos = self.write_decimal(&mut buf);
+
+        // Safe since only ascii digits are written into the buffer
+        let s = unsafe { core::str::from_utf8_unchecked(&buf[pos..]) };
+        f.pad_integral(true, "", s)
+    }
+}
+
+/// Parse `uint256` from a decimal or hex string literal at compilation time
+///
+/// ## Panics
+///
+/// - if the string is empty;
+/// - if invalid digit presented in the string;
+/// - if the value doesn't fit into `uint256`;
+///
+/// <br>
+///
+/// ## Example
+///
+/// ```
+/// use ethgen::uint256;
+///
+///
+/// const MAX_SUPPLY: uint256 = uint256!("21000000000000000000000000");
+/// assert_eq!(MAX_SUPPLY, uint256::from(21_000_000u64) * uint256::from(10u64).pow(18));
+///
+/// let mask = uint256!("0xffffffffffffffffffffffffffffffffffffffff");
+/// assert_eq!(mask, (uint256::ONE << 160) - uint256::ONE);
+/// ```
+#[macro_export]
+macro_rules! uint256 {
+    ($s:literal) => {{
+        const VALUE: $crate::uint256 = $crate::uint256::const_from_str($s);
+        VALUE
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_overflowing() {
+        let (res, o) = uint256::MAX.overflowing_add(&uint256::ONE);
+        assert_eq!((res, o), (uint256::ZERO, true));
+
+        let (res, o) = uint256::ZERO.overflowing_sub(&uint256::ONE);
+        assert_eq!((res, o), (uint256::MAX, true));
+
+        let (res, o) = uint256::MAX.overflowing_mul(&uint256::from(2u64));
+        assert_eq!((res, o), (uint256::MAX - uint256::ONE, true));
+
+        let (res, o) = uint256::ONE.overflowing_shl(257);
+        assert_eq!((res, o), (uint256::from(2u64), true));
+
+        let (res, o) = uint256::from(6u64).overflowing_mul(&uint256::from(7u64));
+        assert_eq!((res, o), (uint256::from(42u64), false));
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let a = uint256::from(u128::MAX);
+        let b = uint256::from(1_000_000_007u64);
+
+        assert_eq!(a / b, uint256::from(u128::MAX / 1_000_000_007));
+        assert_eq!(a % b, uint256::from(u128::MAX % 1_000_000_007));
+        assert_eq!(a.checked_div(&uint256::ZERO), None);
+        assert_eq!(a.checked_rem(&uint256::ZERO), None);
+        assert_eq!(a.checked_div_rem(&b), Some((a / b, a % b)));
+    }
+
+    #[test]
+    fn test_bits() {
+        let a = uint256::from(0b1100u64);
+        let b = uint256::from(0b1010u64);
+
+        assert_eq!(a & b, uint256::from(0b1000u64));
+        assert_eq!(a | b, uint256::from(0b1110u64));
+        assert_eq!(a ^ b, uint256::from(0b0110u64));
+        assert_eq!(!uint256::ZERO, uint256::MAX);
+        assert_eq!(uint256::ONE << 255 >> 255, uint256::ONE);
+        assert_eq!(uint256::MAX.leading_zeros(), 0);
+        assert_eq!((uint256::ONE << 200).bits(), 201);
+    }
+
+    #[test]
+    fn test_pow() {
+        let ten = uint256::from(10u64);
+
+        assert_eq!(ten.pow(18), uint256::from(1_000_000_000_000_000_000u64));
+        assert!(ten.checked_pow(77).is_some());
+        assert_eq!(ten.checked_pow(78), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("0".parse(), Ok(uint256::ZERO));
+        assert_eq!("0x0".parse(), Ok(uint256::ZERO));
+        assert_eq!("0x00ff".parse(), Ok(uint256::from(255u64)));
+        assert_eq!("".parse::<uint256>(), Err(ParseIntError::Empty));
+        assert_eq!("-1".parse::<uint256>(), Err(ParseIntError::InvalidDigit));
+        assert_eq!("12a".parse::<uint256>(), Err(ParseIntError::InvalidDigit));
+
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(max.parse(), Ok(uint256::MAX));
+
+        let over = "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+        assert_eq!(over.parse::<uint256>(), Err(ParseIntError::Overflow));
+    }
+
+    #[test]
+    fn test_write_decimal() {
+        let cases: [(uint256, &str); 4] = [
+            (uint256::ZERO, "0"),
+            (uint256::from(7u64), "7"),
+            (uint256::from(10u64).pow(19), "10000000000000000000"),
+            (
+                uint256::from(10u64).pow(38) + uint256::from(5u64),
+                "100000000000000000000000000000000000005",
+            ),
+        ];
+
+        for (value, expected) in cases {
+            let mut buf = [0u8; UINT256_DIGITS];
+            let pos = value.write_decimal(&mut buf);
+            assert_eq!(&buf[pos..], expected.as_bytes());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sub_overflow() {
+        let _ = uint256::ZERO - uint256::ONE;
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_shl_overflow() {
+        let _ = uint256::ONE << 256;
+    }
+}
@@ -0,0 +1,34 @@
+/// JSON object fixture of the tests, the common `header` or `receipt` fields
+/// followed by the given ones
+macro_rules! json {
+    (header $(, $fields:literal)*) => {
+        concat!(
+            r#"{"parentHash":"0x6a1a5e56d2e8b4e0a1c8a1f2f5e1a2d8b3e0d4a1c2b3a4f5e6d7c8b9a0f1e2d3","#,
+            r#""sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347","#,
+            r#""miner":"0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5","#,
+            r#""stateRoot":"0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544","#,
+            r#""transactionsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","#,
+            r#""receiptsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","#,
+            r#""logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","#,
+            r#""difficulty":"0x0","number":"0x13a8c40","gasLimit":"0x1c9c380","gasUsed":"0x5208","#,
+            r#""timestamp":"0x65f1b057","extraData":"0x6265617665726275696c642e6f7267","#,
+            r#""mixHash":"0x0000000000000000000000000000000000000000000000000000000000000001","#,
+            r#""nonce":"0x0000000000000000""#,
+            $(",", $fields,)*
+            "}"
+        )
+    };
+    (receipt $(, $fields:literal)*) => {
+        concat!(
+            r#"{"transactionHash":"0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060","#,
+            r#""transactionIndex":"0x0","#,
+            r#""blockHash":"0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b","#,
+            r#""blockNumber":"0x13a8c40","#,
+            r#""from":"0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f","#,
+            r#""cumulativeGasUsed":"0x5208","gasUsed":"0x5208","effectiveGasPrice":"0x3b9aca00","#,
+            r#""logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000""#,
+            $(",", $fields,)*
+            "}"
+        )
+    };
+}
@@ -0,0 +1,543 @@
+use super::required;
+use crate::{
+    encoding::{decode_data, Data},
+    rlp::{self, Decodable, Encodable},
+    rpc::QuantityValue,
+    typenum::{U256, U32, U8},
+    uint256, Address, Bytes, Keccak256, ListBuf,
+};
+use core::{fmt, ops::Deref};
+use serde::{
+    de::{self, IgnoredAny, MapAccess, Unexpected, Visitor},
+    Deserialize, Deserializer,
+};
+
+/// Extra data of a block header of up to `N` bytes
+///
+/// The consensus limits it to 32 bytes on Ethereum, which is the default
+/// capacity. Proof of authority chains (Clique, BSC, Polygon) append the
+/// signer seal and, on the checkpoint blocks, the validators to it, starting
+/// from 97 bytes for the vanity and the seal.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExtraData<const N: usize = 32>(ListBuf<u8, N>);
+
+impl<const N: usize> ExtraData<N> {
+    pub const MAX_LENGTH: usize = N;
+
+    /// Copy the extra data, `None` if it exceeds the maximum length
+    #[inline(always)]
+    pub fn new(src: &[u8]) -> Option<Self> {
+        ListBuf::from_slice(src).map(Self)
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl<const N: usize> Deref for ExtraData<N> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> fmt::Debug for ExtraData<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ExtraData").field(&self.as_slice()).finish()
+    }
+}
+
+impl<const N: usize> Encodable for ExtraData<N> {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        self.as_slice().length()
+    }
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        self.as_slice().encode(buf)
+    }
+}
+
+impl<const N: usize> Decodable<'_> for ExtraData<N> {
+    fn decode(buf: &mut &[u8]) -> Result<Self, rlp::Error> {
+        Self::new(rlp::decode_string(buf)?).ok_or(rlp::Error::Overflow)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for ExtraData<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ExtraDataVisitor<const N: usize>;
+
+        impl<const N: usize> Visitor<'_> for ExtraDataVisitor<N> {
+            type Value = ExtraData<N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "0x prefixed hex string of at most {} bytes", N)
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                let mut buf = [0u8; N];
+                decode_data(v, &mut buf)
+                    .and_then(|len| ExtraData::new(&buf[..len]))
+                    .ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(ExtraDataVisitor)
+    }
+}
+
+/// Block header
+///
+/// Encoded as the RLP list of the fields in the declaration order, its Keccak
+/// hash is the block hash. The optional fields were introduced by the forks:
+/// the base fee by London, the withdrawals root by Shanghai, the blob gas and
+/// the parent beacon block root by Cancun and the requests hash by Prague,
+/// they are expected to be set consecutively, as a fork implies the previous
+/// ones.
+///
+/// Deserialized from the JSON block object of `eth_getBlockByNumber` and
+/// `eth_getBlockByHash`, ignoring the fields which are not part of the header.
+/// The extra data capacity `X` is 32 bytes by default, see [`ExtraData`].
+///
+/// ```rust
+/// use ethgen::{block::{ExtraData, Header}, from_hex, hex, rlp, uint256, Bytes};
+///
+///
+/// // Mainnet genesis block
+/// let header = Header {
+///     ommers_hash: from_hex("0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"),
+///     state_root: from_hex("0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544"),
+///     transactions_root: from_hex("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+///     receipts_root: from_hex("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+///     difficulty: uint256::from(0x400000000u64),
+///     gas_limit: 5000,
+///     extra_data: ExtraData::new(&hex::const_decode::<32>(
+///         b"11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+///     )).unwrap(),
+///     nonce: from_hex("0x0000000000000042"),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     header.hash(),
+///     from_hex::<Bytes<_>, _>("0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"),
+/// );
+///
+/// let mut buf = [0u8; <Header>::MAX_LENGTH];
+/// let n = rlp::Encodable::encode(&header, &mut buf);
+/// assert_eq!(rlp::decode::<Header>(&buf[..n]), Ok(header));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Header<const X: usize = 32> {
+    pub parent_hash: Bytes<U32>,
+    /// Hash of the uncles list, `sha3Uncles` in JSON
+    pub ommers_hash: Bytes<U32>,
+    /// Fee recipient, `miner` in JSON
+    pub beneficiary: Address,
+    pub state_root: Bytes<U32>,
+    pub transactions_root: Bytes<U32>,
+    pub receipts_root: Bytes<U32>,
+    pub logs_bloom: Bytes<U256>,
+    /// Zero since the merge
+    pub difficulty: uint256,
+    pub number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: ExtraData<X>,
+    /// RANDAO mix since the merge
+    pub mix_hash: Bytes<U32>,
+    /// Zero since the merge
+    pub nonce: Bytes<U8>,
+    pub base_fee_per_gas: Option<u128>,
+    pub withdrawals_root: Option<Bytes<U32>>,
+    pub blob_gas_used: Option<u64>,
+    pub excess_blob_gas: Option<u64>,
+    pub parent_beacon_block_root: Option<Bytes<U32>>,
+    pub requests_hash: Option<Bytes<U32>>,
+}
+
+/// Maximum length of the header fields encoding, except the extra data
+const FIELDS_LENGTH: usize = 690;
+
+impl<const X: usize> Header<X> {
+    /// Maximum length of the header encoding with all fields present
+    pub const MAX_LENGTH: usize =
+        rlp::Header::list(FIELDS_LENGTH + rlp::Header::string(X).length_with_payload())
+            .length_with_payload();
+
+    fn fields_length(&self) -> usize {
+        self.parent_hash.length()
+            + self.ommers_hash.length()
+            + self.beneficiary.length()
+            + self.state_root.length()
+            + self.transactions_root.length()
+            + self.receipts_root.length()
+            + self.logs_bloom.length()
+            + self.difficulty.length()
+            + self.number.length()
+            + self.gas_limit.length()
+            + self.gas_used.length()
+            + self.timestamp.length()
+            + self.extra_data.length()
+            + self.mix_hash.length()
+            + self.nonce.length()
+            + self.base_fee_per_gas.as_ref().map_or(0, Encodable::length)
+            + self.withdrawals_root.as_ref().map_or(0, Encodable::length)
+            + self.blob_gas_used.as_ref().map_or(0, Encodable::length)
+            + self.excess_blob_gas.as_ref().map_or(0, Encodable::length)
+            + self
+                .parent_beacon_block_root
+                .as_ref()
+                .map_or(0, Encodable::length)
+            + self.requests_hash.as_ref().map_or(0, Encodable::length)
+    }
+
+    /// Keccak hash of the header encoding, which is the block hash
+    pub fn hash(&self) -> Bytes<U32> {
+        // The extra data is hashed in place, so that the buffer doesn't
+        // depend on its capacity; the list and the string headers are at
+        // most 9 bytes each
+        let mut buf = [0u8; FIELDS_LENGTH + 18];
+        let extra_data = self.extra_data.as_slice();
+
+        let mut n = rlp::Header::list(self.fields_length()).encode(&mut buf);
+        n += self.encode_leading(&mut buf[n..]);
+
+        // The single byte below `0x80` is its own encoding
+        if rlp::string_length(extra_data) > extra_data.len() {
+            n += rlp::Header::string(extra_data.len()).encode(&mut buf[n..]);
+        }
+
+        let mut hasher = Keccak256::new();
+        hasher.update(&buf[..n]);
+        hasher.update(extra_data);
+
+        let n = self.encode_trailing(&mut buf);
+        hasher.update(&buf[..n]);
+        hasher.finalize()
+    }
+
+    /// Encode the fields preceding the extra data
+    fn encode_leading(&self, buf: &mut [u8]) -> usize {
+        let mut n = self.parent_hash.encode(buf);
+        n += self.ommers_hash.encode(&mut buf[n..]);
+        n += self.beneficiary.encode(&mut buf[n..]);
+        n += self.state_root.encode(&mut buf[n..]);
+        n += self.transactions_root.encode(&mut buf[n..]);
+        n += self.receipts_root.encode(&mut buf[n..]);
+        n += self.logs_bloom.encode(&mut buf[n..]);
+        n += self.difficulty.encode(&mut buf[n..]);
+        n += self.number.encode(&mut buf[n..]);
+        n += self.gas_limit.encode(&mut buf[n..]);
+        n += self.gas_used.encode(&mut buf[n..]);
+        n + self.timestamp.encode(&mut buf[n..])
+    }
+
+    /// Encode the fields following the extra data
+    fn encode_trailing(&self, buf: &mut [u8]) -> usize {
+        let mut n = self.mix_hash.encode(buf);
+        n += self.nonce.encode(&mut buf[n..]);
+
+        if let Some(base_fee_per_gas) = &self.base_fee_per_gas {
+            n += base_fee_per_gas.encode(&mut buf[n..]);
+        }
+        if let Some(withdrawals_root) = &self.withdrawals_root {
+            n += withdrawals_root.encode(&mut buf[n..]);
+        }
+        if let Some(blob_gas_used) = &self.blob_gas_used {
+            n += blob_gas_used.encode(&mut buf[n..]);
+        }
+        if let Some(excess_blob_gas) = &self.excess_blob_gas {
+            n += excess_blob_gas.encode(&mut buf[n..]);
+        }
+        if let Some(parent_beacon_block_root) = &self.parent_beacon_block_root {
+            n += parent_beacon_block_root.encode(&mut buf[n..]);
+        }
+        if let Some(requests_hash) = &self.requests_hash {
+            n += requests_hash.encode(&mut buf[n..]);
+        }
+
+        n
+    }
+}
+
+impl<const X: usize> Encodable for Header<X> {
+    fn length(&self) -> usize {
+        rlp::Header::list(self.fields_length()).length_with_payload()
+    }
+
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        let mut n = rlp::Header::list(self.fields_length()).encode(buf);
+        n += self.encode_leading(&mut buf[n..]);
+        n += self.extra_data.encode(&mut buf[n..]);
+        n + self.encode_trailing(&mut buf[n..])
+    }
+}
+
+/// Decode a trailing field, `None` if the list payload is over
+fn decode_optional<'a, T: Decodable<'a>>(buf: &mut &'a [u8]) -> Result<Option<T>, rlp::Error> {
+    match buf.is_empty() {
+        true => Ok(None),
+        false => T::decode(buf).map(Some),
+    }
+}
+
+impl<const X: usize> Decodable<'_> for Header<X> {
+    fn decode(buf: &mut &[u8]) -> Result<Self, rlp::Error> {
+        let mut payload = rlp::decode_list(buf)?;
+
+        let header = Self {
+            parent_hash: Decodable::decode(&mut payload)?,
+            ommers_hash: Decodable::decode(&mut payload)?,
+            beneficiary: Decodable::decode(&mut payload)?,
+            state_root: Decodable::decode(&mut payload)?,
+            transactions_root: Decodable::decode(&mut payload)?,
+            receipts_root: Decodable::decode(&mut payload)?,
+            logs_bloom: Decodable::decode(&mut payload)?,
+            difficulty: Decodable::decode(&mut payload)?,
+            number: Decodable::decode(&mut payload)?,
+            gas_limit: Decodable::decode(&mut payload)?,
+            gas_used: Decodable::decode(&mut payload)?,
+            timestamp: Decodable::decode(&mut payload)?,
+            extra_data: Decodable::decode(&mut payload)?,
+            mix_hash: Decodable::decode(&mut payload)?,
+            nonce: Decodable::decode(&mut payload)?,
+            base_fee_per_gas: decode_optional(&mut payload)?,
+            withdrawals_root: decode_optional(&mut payload)?,
+            blob_gas_used: decode_optional(&mut payload)?,
+            excess_blob_gas: decode_optional(&mut payload)?,
+            parent_beacon_block_root: decode_optional(&mut payload)?,
+            requests_hash: decode_optional(&mut payload)?,
+        };
+
+        match payload.is_empty() {
+            true => Ok(header),
+            false => Err(rlp::Error::LengthMismatch),
+        }
+    }
+}
+
+/// Collect the header fields of a JSON object
+macro_rules! fields {
+    ($($field:ident: $ty:ty = $key:literal,)*) => {
+        /// Header fields collected from a JSON object in any order
+        #[derive(Default)]
+        pub(super) struct Fields<const X: usize> {
+            $($field: Option<$ty>,)*
+        }
+
+        impl<const X: usize> Fields<X> {
+            pub(super) const KEYS: &'static [&'static str] = &[$($key),*];
+
+            /// Deserialize the value of a header field, `false` for other keys
+            pub(super) fn next_value<'de, A: MapAccess<'de>>(
+                &mut self,
+                key: &str,
+                map: &mut A,
+            ) -> Result<bool, A::Error> {
+                match key {
+                    $($key => self.$field = Some(map.next_value()?),)*
+                    _ => return Ok(false),
+                }
+
+                Ok(true)
+            }
+        }
+    };
+}
+
+fields! {
+    parent_hash: Data<U32> = "parentHash",
+    ommers_hash: Data<U32> = "sha3Uncles",
+    beneficiary: Address = "miner",
+    state_root: Data<U32> = "stateRoot",
+    transactions_root: Data<U32> = "transactionsRoot",
+    receipts_root: Data<U32> = "receiptsRoot",
+    logs_bloom: Data<U256> = "logsBloom",
+    difficulty: QuantityValue<uint256> = "difficulty",
+    number: QuantityValue<u64> = "number",
+    gas_limit: QuantityValue<u64> = "gasLimit",
+    gas_used: QuantityValue<u64> = "gasUsed",
+    timestamp: QuantityValue<u64> = "timestamp",
+    extra_data: ExtraData<X> = "extraData",
+    mix_hash: Data<U32> = "mixHash",
+    nonce: Data<U8> = "nonce",
+    base_fee_per_gas: QuantityValue<u128> = "baseFeePerGas",
+    withdrawals_root: Data<U32> = "withdrawalsRoot",
+    blob_gas_used: QuantityValue<u64> = "blobGasUsed",
+    excess_blob_gas: QuantityValue<u64> = "excessBlobGas",
+    parent_beacon_block_root: Data<U32> = "parentBeaconBlockRoot",
+    requests_hash: Data<U32> = "requestsHash",
+}
+
+impl<const X: usize> Fields<X> {
+    /// Build the header, failing if a mandatory field is missing
+    pub(super) fn finish<E: de::Error>(self) -> Result<Header<X>, E> {
+        Ok(Header {
+            parent_hash: required(self.parent_hash, "parentHash")?.0,
+            ommers_hash: required(self.ommers_hash, "sha3Uncles")?.0,
+            beneficiary: required(self.beneficiary, "miner")?,
+            state_root: required(self.state_root, "stateRoot")?.0,
+            transactions_root: required(self.transactions_root, "transactionsRoot")?.0,
+            receipts_root: required(self.receipts_root, "receiptsRoot")?.0,
+            logs_bloom: required(self.logs_bloom, "logsBloom")?.0,
+            difficulty: required(self.difficulty, "difficulty")?.0,
+            number: required(self.number, "number")?.0,
+            gas_limit: required(self.gas_limit, "gasLimit")?.0,
+            gas_used: required(self.gas_used, "gasUsed")?.0,
+            timestamp: required(self.timestamp, "timestamp")?.0,
+            extra_data: required(self.extra_data, "extraData")?,
+            mix_hash: required(self.mix_hash, "mixHash")?.0,
+            nonce: required(self.nonce, "nonce")?.0,
+            base_fee_per_gas: self.base_fee_per_gas.map(|v| v.0),
+            withdrawals_root: self.withdrawals_root.map(|v| v.0),
+            blob_gas_used: self.blob_gas_used.map(|v| v.0),
+            excess_blob_gas: self.excess_blob_gas.map(|v| v.0),
+            parent_beacon_block_root: self.parent_beacon_block_root.map(|v| v.0),
+            requests_hash: self.requests_hash.map(|v| v.0),
+        })
+    }
+}
+
+impl<'de, const X: usize> Deserialize<'de> for Header<X> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct HeaderVisitor<const X: usize>;
+
+        impl<'de, const X: usize> Visitor<'de> for HeaderVisitor<X> {
+            type Value = Header<X>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "block header object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Header<X>, A::Error> {
+                let mut fields = Fields::default();
+
+                while let Some(key) = map.next_key::<&str>()? {
+                    if !fields.next_value(key, &mut map)? {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+
+                fields.finish()
+            }
+        }
+
+        deserializer.deserialize_struct("Header", Fields::<X>::KEYS, HeaderVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn test_max_length() {
+        let hash = Bytes::from_array([0xff; 32]);
+
+        let header = Header {
+            parent_hash: hash,
+            ommers_hash: hash,
+            beneficiary: Address::new([0xff; 20]),
+            state_root: hash,
+            transactions_root: hash,
+            receipts_root: hash,
+            logs_bloom: Bytes::from_array([0xff; 256]),
+            difficulty: uint256::MAX,
+            number: u64::MAX,
+            gas_limit: u64::MAX,
+            gas_used: u64::MAX,
+            timestamp: u64::MAX,
+            extra_data: ExtraData::new(&[0xff; 32]).unwrap(),
+            mix_hash: hash,
+            nonce: Bytes::from_array([0xff; 8]),
+            base_fee_per_gas: Some(u128::MAX),
+            withdrawals_root: Some(hash),
+            blob_gas_used: Some(u64::MAX),
+            excess_blob_gas: Some(u64::MAX),
+            parent_beacon_block_root: Some(hash),
+            requests_hash: Some(hash),
+        };
+
+        let mut buf = [0u8; <Header>::MAX_LENGTH];
+        let n = header.encode(&mut buf);
+
+        assert_eq!(n, <Header>::MAX_LENGTH);
+        assert_eq!(n, header.length());
+        assert_eq!(rlp::decode::<Header>(&buf), Ok(header));
+
+        // Any field past the known ones is rejected
+        let mut long = [0u8; <Header>::MAX_LENGTH + 1];
+        let m = rlp::Header::list(header.fields_length() + 1).encode(&mut long);
+        long[m..m + n - 3].copy_from_slice(&buf[3..n]);
+        assert_eq!(
+            rlp::decode::<Header>(&long),
+            Err(rlp::Error::LengthMismatch)
+        );
+
+        // Proof of authority extra data of the vanity and the seal
+        let header = Header::<97> {
+            extra_data: ExtraData::new(&[0xff; 97]).unwrap(),
+            ..Header::default()
+        };
+        let mut buf = [0u8; Header::<97>::MAX_LENGTH];
+        let n = header.encode(&mut buf);
+        assert_eq!(Header::<97>::MAX_LENGTH, <Header>::MAX_LENGTH + 66);
+        assert_eq!(rlp::decode::<Header<97>>(&buf[..n]), Ok(header));
+    }
+
+    #[test]
+    fn test_hash() {
+        // The in place hashing of the extra data matches the encoding
+        for extra_data in [
+            &[][..],
+            &[0x7f],
+            &[0x80],
+            &[0xff; 55],
+            &[0xff; 56],
+            &[0xff; 256],
+        ] {
+            let header = Header::<256> {
+                extra_data: ExtraData::new(extra_data).unwrap(),
+                ..Header::default()
+            };
+
+            let mut buf = [0u8; Header::<256>::MAX_LENGTH];
+            let n = header.encode(&mut buf);
+            assert_eq!(header.hash(), crate::keccak256(&buf[..n]));
+        }
+    }
+
+    #[test]
+    fn test_extra_data() {
+        assert_eq!(<ExtraData>::new(&[0u8; 33]), None);
+        assert_eq!(&*<ExtraData>::new(b"ethgen").unwrap(), b"ethgen");
+        assert_eq!(ExtraData::<97>::new(&[0u8; 97]).unwrap().len(), 97);
+
+        let mut encoded = [0u8; 34];
+        encoded[0] = 0xa1;
+        assert_eq!(
+            rlp::decode::<ExtraData>(&encoded),
+            Err(rlp::Error::Overflow)
+        );
+
+        let (data, _): (ExtraData, usize) = serde_json_core::from_str(r#""0x""#).unwrap();
+        assert!(data.is_empty());
+
+        for json in [r#""0x0""#, r#""ab""#, r#""0xzz""#] {
+            assert!(serde_json_core::from_str::<ExtraData>(json).is_err());
+        }
+
+        // Proof of authority extra data of the vanity and the seal
+        let json = r#""0x0000000000000000000000000000000000000000000000000000000000000000ababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababab""#;
+        let (data, _): (ExtraData<97>, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(data.len(), 97);
+        assert!(serde_json_core::from_str::<ExtraData>(json).is_err());
+    }
+}
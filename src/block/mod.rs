@@ -0,0 +1,520 @@
+//! Blocks, block headers and receipts
+//!
+//! The types are deserialized from the JSON block objects returned by
+//! `eth_getBlockByNumber` and `eth_getBlockByHash` and from the receipt
+//! objects returned by `eth_getTransactionReceipt`. The header hash is
+//! computed from its RLP encoding so the response of a node can be verified
+//! against the block hash. DATA fields are parsed strictly, requiring the `0x`
+//! prefix and the exact length.
+use crate::{
+    as_hex,
+    as_quantity::{self, Quantity},
+    encoding::{data, Data},
+    rpc,
+    typenum::U32,
+    uint256, Address, Bytes, ListBuf,
+};
+use core::{fmt, marker::PhantomData};
+use generic_array::ArrayLength;
+use serde::{
+    de::{self, IgnoredAny, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+#[cfg(test)]
+#[macro_use]
+mod fixture;
+
+mod header;
+pub use header::{ExtraData, Header};
+
+mod receipt;
+pub use receipt::{Log, Topic, Topics, TransactionReceipt, MAX_TOPICS};
+
+/// Maximum amount of withdrawals in a block
+pub const MAX_WITHDRAWALS: usize = 16;
+
+/// Deserialize an optional DATA field, which may be missing or `null`
+fn optional_data<'de, D, N>(deserializer: D) -> Result<Option<Bytes<N>>, D::Error>
+where
+    D: Deserializer<'de>,
+    N: ArrayLength,
+{
+    Option::<Data<N>>::deserialize(deserializer).map(|v| v.map(|data| data.0))
+}
+
+/// Deserialize an optional QUANTITY field, which may be missing or `null`
+fn optional<'de, D: Deserializer<'de>, T: Quantity>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error> {
+    Option::<rpc::QuantityValue<T>>::deserialize(deserializer).map(|v| v.map(|q| q.0))
+}
+
+#[inline(always)]
+fn required<T, E: de::Error>(value: Option<T>, field: &'static str) -> Result<T, E> {
+    value.ok_or_else(|| E::missing_field(field))
+}
+
+/// Transaction hash, the transaction item of a block fetched without the
+/// full transaction objects
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TxHash(pub Bytes<U32>);
+
+impl Serialize for TxHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        as_hex::serialize(self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TxHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        data(deserializer).map(Self)
+    }
+}
+
+/// Transaction object, the transaction item of a block fetched with the full
+/// transaction objects
+///
+/// Holds the fields common to all transaction types, the fee fields which are
+/// not defined by the type are `None`. The access list, the blob versioned
+/// hashes, the authorization list and the signature are skipped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTransaction<'a> {
+    #[serde(deserialize_with = "data")]
+    pub hash: Bytes<U32>,
+    /// EIP-2718 transaction type, `0x00` for legacy transactions
+    #[serde(rename = "type", deserialize_with = "as_quantity::deserialize")]
+    pub ty: u8,
+    #[serde(default, deserialize_with = "optional")]
+    pub chain_id: Option<u64>,
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub nonce: u64,
+    pub from: Address,
+    /// Recipient, `None` for the contract creation
+    #[serde(default)]
+    pub to: Option<Address>,
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub value: uint256,
+    #[serde(rename = "gas", deserialize_with = "as_quantity::deserialize")]
+    pub gas_limit: u64,
+    /// Gas price of legacy transactions or the effective gas price
+    #[serde(default, deserialize_with = "optional")]
+    pub gas_price: Option<u128>,
+    #[serde(default, deserialize_with = "optional")]
+    pub max_fee_per_gas: Option<u128>,
+    #[serde(default, deserialize_with = "optional")]
+    pub max_priority_fee_per_gas: Option<u128>,
+    #[serde(default, deserialize_with = "optional")]
+    pub max_fee_per_blob_gas: Option<u128>,
+    /// Call data as `0x` prefixed hex string
+    pub input: &'a str,
+    #[serde(default, deserialize_with = "optional")]
+    pub transaction_index: Option<u64>,
+}
+
+/// Validator withdrawal introduced by Shanghai
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub index: u64,
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub validator_index: u64,
+    pub address: Address,
+    /// Amount in Gwei
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub amount: u64,
+}
+
+/// Block with up to `N` transactions
+///
+/// The transaction item is either [`TxHash`] or [`BlockTransaction`],
+/// corresponding to the `false` and `true` values of the full transactions
+/// flag of the `eth_getBlockByNumber` and `eth_getBlockByHash` requests. The
+/// uncle hashes are skipped, pending blocks (without hash and number) are not
+/// supported. The header extra data holds up to `X` bytes, see [`ExtraData`].
+///
+/// ```rust
+/// use ethgen::{block::{Block, TxHash}, rpc::Response};
+///
+///
+/// let json = r#"{"jsonrpc":"2.0","id":1,"result":{
+///     "difficulty":"0x400000000",
+///     "extraData":"0x11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+///     "gasLimit":"0x1388",
+///     "gasUsed":"0x0",
+///     "hash":"0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+///     "logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+///     "miner":"0x0000000000000000000000000000000000000000",
+///     "mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000",
+///     "nonce":"0x0000000000000042",
+///     "number":"0x0",
+///     "parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000",
+///     "receiptsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+///     "sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+///     "size":"0x21c",
+///     "stateRoot":"0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+///     "timestamp":"0x0",
+///     "totalDifficulty":"0x400000000",
+///     "transactions":[],
+///     "transactionsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+///     "uncles":[]
+/// }}"#;
+///
+/// let (res, _): (Response<Option<Block<TxHash, 256>>>, usize) =
+///     serde_json_core::from_str(json).unwrap();
+///
+/// let block = res.unwrap().unwrap();
+/// assert_eq!(block.header.number, 0);
+/// assert!(block.transactions.is_empty());
+/// assert!(block.verify_hash());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block<T, const N: usize, const X: usize = 32> {
+    /// Block hash as reported by the node
+    pub hash: Bytes<U32>,
+    pub header: Header<X>,
+    pub transactions: ListBuf<T, N>,
+    /// Withdrawals since Shanghai
+    pub withdrawals: Option<ListBuf<Withdrawal, MAX_WITHDRAWALS>>,
+}
+
+impl<T, const N: usize, const X: usize> Block<T, N, X> {
+    /// Check that the reported hash is the hash of the header
+    pub fn verify_hash(&self) -> bool {
+        self.header.hash() == self.hash
+    }
+}
+
+impl<'de, T, const N: usize, const X: usize> Deserialize<'de> for Block<T, N, X>
+where
+    T: Deserialize<'de> + Copy + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BlockVisitor<T, const N: usize, const X: usize>(PhantomData<T>);
+
+        impl<'de, T, const N: usize, const X: usize> Visitor<'de> for BlockVisitor<T, N, X>
+        where
+            T: Deserialize<'de> + Copy + Default,
+        {
+            type Value = Block<T, N, X>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "block object of at most {} transactions", N)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut hash = None;
+                let mut transactions = None;
+                let mut withdrawals = None;
+                let mut header = header::Fields::default();
+
+                while let Some(key) = map.next_key::<&str>()? {
+                    match key {
+                        "hash" => hash = Some(map.next_value::<Data<U32>>()?.0),
+                        "transactions" => transactions = Some(map.next_value()?),
+                        "withdrawals" => withdrawals = map.next_value()?,
+                        _ => {
+                            if !header.next_value(key, &mut map)? {
+                                map.next_value::<IgnoredAny>()?;
+                            }
+                        }
+                    }
+                }
+
+                Ok(Block {
+                    hash: required(hash, "hash")?,
+                    header: header.finish()?,
+                    transactions: required(transactions, "transactions")?,
+                    withdrawals,
+                })
+            }
+        }
+
+        deserializer.deserialize_struct("Block", &[], BlockVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{address, from_hex};
+
+    fn cancun() -> Header {
+        Header {
+            parent_hash: from_hex(
+                "0x6a1a5e56d2e8b4e0a1c8a1f2f5e1a2d8b3e0d4a1c2b3a4f5e6d7c8b9a0f1e2d3",
+            ),
+            ommers_hash: from_hex(
+                "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            ),
+            beneficiary: address!("0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5"),
+            state_root: from_hex(
+                "0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+            ),
+            transactions_root: from_hex(
+                "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            ),
+            receipts_root: from_hex(
+                "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            ),
+            number: 0x13a8c40,
+            gas_limit: 30_000_000,
+            gas_used: 21_000,
+            timestamp: 0x65f1b057,
+            extra_data: ExtraData::new(b"beaverbuild.org").unwrap(),
+            mix_hash: from_hex(
+                "0x0000000000000000000000000000000000000000000000000000000000000001",
+            ),
+            base_fee_per_gas: Some(0x3b9aca00),
+            withdrawals_root: Some(from_hex(
+                "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            )),
+            blob_gas_used: Some(0x20000),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(from_hex(
+                "0x0000000000000000000000000000000000000000000000000000000000000002",
+            )),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_header() {
+        let london = Header {
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            ..cancun()
+        };
+
+        let json = json!(header, r#""baseFeePerGas":"0x3b9aca00""#);
+        let (header, _): (Header, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(header, london);
+
+        let json = json!(
+            header,
+            r#""baseFeePerGas":"0x3b9aca00""#,
+            r#""withdrawalsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421""#,
+            r#""blobGasUsed":"0x20000""#,
+            r#""excessBlobGas":"0x0""#,
+            r#""parentBeaconBlockRoot":"0x0000000000000000000000000000000000000000000000000000000000000002""#,
+            r#""size":"0x2a5""#,
+            r#""uncles":[]"#
+        );
+        let (header, _): (Header, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(header, cancun());
+
+        let mut buf = [0u8; <Header>::MAX_LENGTH];
+        for header in [london, cancun()] {
+            let n = crate::rlp::Encodable::encode(&header, &mut buf);
+            assert_eq!(crate::rlp::decode::<Header>(&buf[..n]), Ok(header));
+        }
+
+        let invalid = [
+            // Missing mandatory fields
+            r#"{"number":"0x1"}"#,
+            // Short hash
+            json!(header, r#""withdrawalsRoot":"0x56e81f""#),
+            // Non canonical quantity
+            json!(header, r#""blobGasUsed":"0x020000""#),
+        ];
+
+        for json in invalid {
+            assert!(
+                serde_json_core::from_str::<Header>(json).is_err(),
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn test_block() {
+        let json = json!(
+            header,
+            r#""baseFeePerGas":"0x3b9aca00""#,
+            r#""withdrawalsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421""#,
+            r#""blobGasUsed":"0x20000""#,
+            r#""excessBlobGas":"0x0""#,
+            r#""parentBeaconBlockRoot":"0x0000000000000000000000000000000000000000000000000000000000000002""#,
+            r#""hash":"0x56f705e5b1144e7b88b6590165e7776119f1cfc44fb7d84c7b6cc20b3ae44cf9""#,
+            r#""transactions":["0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"]"#,
+            r#""withdrawals":[{"index":"0x2a","validatorIndex":"0x10f2c","address":"0x388c818ca8b9251b393131c08a736a67ccb19297","amount":"0x1c6bf"}]"#
+        );
+
+        let (block, _): (Block<TxHash, 4>, usize) = serde_json_core::from_str(json).unwrap();
+
+        assert_eq!(block.header, cancun());
+        assert_eq!(
+            block.transactions.as_slice(),
+            &[TxHash(from_hex(
+                "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"
+            ))]
+        );
+        assert_eq!(
+            block.withdrawals.unwrap().as_slice(),
+            &[Withdrawal {
+                index: 42,
+                validator_index: 0x10f2c,
+                address: address!("0x388c818ca8b9251b393131c08a736a67ccb19297"),
+                amount: 0x1c6bf,
+            }]
+        );
+
+        // The hashes are computed by an independent RLP encoder following the
+        // field order of the execution specs, so that the fork fields order
+        // is verified
+        assert!(block.verify_hash());
+
+        let london = Header {
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            ..cancun()
+        };
+        assert_eq!(
+            london.hash(),
+            from_hex::<Bytes<U32>, _>(
+                "0x4a3c6afeecc4e20d2107a654913797904d438dd8464b5600f833ee72b54d683e"
+            )
+        );
+
+        let prague = Header {
+            requests_hash: Some(from_hex(
+                "0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            )),
+            ..cancun()
+        };
+        assert_eq!(
+            prague.hash(),
+            from_hex::<Bytes<U32>, _>(
+                "0x903bb1079f7afdb549beedd945c08c70e1add332ad0cfb0910004c1313d9af06"
+            )
+        );
+
+        // Not the hash of the header
+        let block = Block {
+            hash: london.hash(),
+            ..block
+        };
+        assert!(!block.verify_hash());
+
+        // Transactions exceed the capacity
+        assert!(serde_json_core::from_str::<Block<TxHash, 0>>(json).is_err());
+
+        // Missing transactions
+        let json = json!(
+            header,
+            r#""hash":"0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b""#
+        );
+        assert!(serde_json_core::from_str::<Block<TxHash, 4>>(json).is_err());
+    }
+
+    #[test]
+    fn test_full_transactions() {
+        let json = json!(
+            header,
+            r#""baseFeePerGas":"0x3b9aca00""#,
+            r#""hash":"0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b""#,
+            r#""transactions":[{
+                "blockHash":"0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b",
+                "blockNumber":"0x13a8c40",
+                "hash":"0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060",
+                "type":"0x2",
+                "chainId":"0x1",
+                "nonce":"0x2",
+                "from":"0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+                "to":"0xd9e1459a7a482635700cbc20bbaf52d495ab9c96",
+                "value":"0x0",
+                "gas":"0x98f0",
+                "gasPrice":"0x29e7822d6",
+                "maxFeePerGas":"0x29e7822d6",
+                "maxPriorityFeePerGas":"0x3b9aca00",
+                "input":"0x1b55ba3a",
+                "accessList":[],
+                "transactionIndex":"0x0",
+                "v":"0x0",
+                "r":"0xc199674fcb29f353693dd779c017823b954b3c69dffa3cd6b2a6ff7888798039",
+                "s":"0x28ca912de909e7e6cdef9cdcaf24c54dd8c1032946dfa1d85c206b32a9064fe8",
+                "yParity":"0x0"
+            },{
+                "hash":"0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b",
+                "type":"0x0",
+                "nonce":"0x0",
+                "from":"0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+                "to":null,
+                "value":"0xde0b6b3a7640000",
+                "gas":"0x5208",
+                "gasPrice":"0x4a817c800",
+                "input":"0x"
+            }]"#
+        );
+
+        let (block, _): (Block<BlockTransaction, 2>, usize) =
+            serde_json_core::from_str(json).unwrap();
+
+        let [dynamic, legacy] = block.transactions.as_slice() else {
+            panic!("expected two transactions");
+        };
+
+        assert_eq!(
+            *dynamic,
+            BlockTransaction {
+                hash: from_hex(
+                    "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"
+                ),
+                ty: 2,
+                chain_id: Some(1),
+                nonce: 2,
+                from: address!("0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"),
+                to: Some(address!("0xd9e1459a7a482635700cbc20bbaf52d495ab9c96")),
+                value: uint256::ZERO,
+                gas_limit: 39_152,
+                gas_price: Some(11_248_607_958),
+                max_fee_per_gas: Some(11_248_607_958),
+                max_priority_fee_per_gas: Some(1_000_000_000),
+                max_fee_per_blob_gas: None,
+                input: "0x1b55ba3a",
+                transaction_index: Some(0),
+            }
+        );
+
+        assert_eq!(legacy.ty, 0);
+        assert_eq!(legacy.to, None);
+        assert_eq!(legacy.chain_id, None);
+        assert_eq!(legacy.max_fee_per_gas, None);
+        assert_eq!(legacy.value, uint256::from(1_000_000_000_000_000_000u64));
+        assert_eq!(block.withdrawals, None);
+    }
+
+    #[test]
+    fn test_malformed_address() {
+        let (withdrawal, _): (Withdrawal, usize) = serde_json_core::from_str(
+            r#"{"index":"0x1","validatorIndex":"0x2","address":"0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f","amount":"0x3"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            withdrawal.address,
+            address!("0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f")
+        );
+
+        for json in [
+            r#"{"index":"0x1","validatorIndex":"0x2","address":"0x7","amount":"0x3"}"#,
+            r#"{"index":"0x1","validatorIndex":"0x2","address":"","amount":"0x3"}"#,
+            r#"{"index":"0x1","validatorIndex":"0x2","address":"0xzz","amount":"0x3"}"#,
+        ] {
+            assert!(serde_json_core::from_str::<Withdrawal>(json).is_err());
+        }
+
+        for json in [
+            r#"{"hash":"0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060","type":"0x0","nonce":"0x0","from":"0x0","value":"0x0","gas":"0x5208","input":"0x"}"#,
+            r#"{"hash":"0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060","type":"0x0","nonce":"0x0","from":"0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f","to":"","value":"0x0","gas":"0x5208","input":"0x"}"#,
+        ] {
+            assert!(serde_json_core::from_str::<BlockTransaction>(json).is_err());
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use super::{optional, optional_data};
+use crate::{
+    as_hex, as_quantity,
+    encoding::{data, decode_data},
+    typenum::{U256, U32},
+    Address, Bytes, ListBuf,
+};
+use serde::{
+    de::{self, Unexpected},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Maximum amount of log topics, as of the `LOG4` opcode
+pub const MAX_TOPICS: usize = 4;
+
+/// Log topic, an indexed event argument or the event signature hash
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Topic(pub Bytes<U32>);
+
+impl Serialize for Topic {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        as_hex::serialize(self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Topic {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        data(deserializer).map(Self)
+    }
+}
+
+/// Topics of a log, the first one is the event signature hash unless the
+/// event is anonymous
+pub type Topics = ListBuf<Topic, MAX_TOPICS>;
+
+/// Deserialize the receipt status, which is either `0x0` or `0x1`
+fn status<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<bool>, D::Error> {
+    match optional::<D, u8>(deserializer)? {
+        None => Ok(None),
+        Some(0) => Ok(Some(false)),
+        Some(1) => Ok(Some(true)),
+        Some(v) => Err(de::Error::invalid_value(
+            Unexpected::Unsigned(v as u64),
+            &"0x0 or 0x1",
+        )),
+    }
+}
+
+/// Event log emitted by a transaction
+///
+/// The data is borrowed from the JSON as a `0x` prefixed hex string, the
+/// block and transaction fields are `None` for pending logs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log<'a> {
+    pub address: Address,
+    pub topics: Topics,
+    /// Hex encoded non-indexed event arguments
+    pub data: &'a str,
+    #[serde(default, deserialize_with = "optional")]
+    pub block_number: Option<u64>,
+    #[serde(default, deserialize_with = "optional_data")]
+    pub block_hash: Option<Bytes<U32>>,
+    #[serde(default, deserialize_with = "optional_data")]
+    pub transaction_hash: Option<Bytes<U32>>,
+    #[serde(default, deserialize_with = "optional")]
+    pub transaction_index: Option<u64>,
+    #[serde(default, deserialize_with = "optional")]
+    pub log_index: Option<u64>,
+    /// Whether the log was removed by a chain reorganization
+    #[serde(default)]
+    pub removed: bool,
+}
+
+impl Log<'_> {
+    /// Decode the data into the beginning of the buffer
+    ///
+    /// Returns `None` if the data is not a valid hex string or the buffer is
+    /// too short.
+    pub fn decode_data<'b>(&self, buf: &'b mut [u8]) -> Option<&'b [u8]> {
+        let n = decode_data(self.data, buf)?;
+        Some(&buf[..n])
+    }
+}
+
+/// Receipt of an executed transaction with up to `N` logs
+///
+/// Deserialized from the result of `eth_getTransactionReceipt`. The status is
+/// `None` for the pre-Byzantium receipts, which carry the intermediate state
+/// root instead, the blob gas fields are only set for the blob transactions.
+///
+/// ```rust
+/// use ethgen::{address, block::TransactionReceipt, rpc::Response};
+///
+///
+/// let json = r#"{"jsonrpc":"2.0","id":1,"result":{
+///     "transactionHash":"0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060",
+///     "transactionIndex":"0x1",
+///     "blockHash":"0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b",
+///     "blockNumber":"0x13a8c40",
+///     "from":"0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+///     "to":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+///     "cumulativeGasUsed":"0x1f7a4",
+///     "gasUsed":"0xb4c5",
+///     "effectiveGasPrice":"0x29e7822d6",
+///     "contractAddress":null,
+///     "logs":[{
+///         "address":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+///         "topics":[
+///             "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+///             "0x0000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+///             "0x000000000000000000000000d9e1459a7a482635700cbc20bbaf52d495ab9c96"
+///         ],
+///         "data":"0x00000000000000000000000000000000000000000000000000000000000f4240",
+///         "blockNumber":"0x13a8c40",
+///         "transactionHash":"0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060",
+///         "transactionIndex":"0x1",
+///         "blockHash":"0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b",
+///         "logIndex":"0x3",
+///         "removed":false
+///     }],
+///     "logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+///     "type":"0x2",
+///     "status":"0x1"
+/// }}"#;
+///
+/// let (res, _): (Response<Option<TransactionReceipt<4>>>, usize) =
+///     serde_json_core::from_str(json).unwrap();
+///
+/// let receipt = res.unwrap().unwrap();
+/// assert_eq!(receipt.status, Some(true));
+/// assert_eq!(receipt.gas_used, 46_277);
+///
+/// let log = receipt.logs[0];
+/// assert_eq!(log.address, address!("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"));
+/// assert_eq!(log.topics.len(), 3);
+///
+/// let mut buf = [0u8; 32];
+/// let amount = log.decode_data(&mut buf).unwrap();
+/// assert_eq!(&amount[29..], &[0x0f, 0x42, 0x40]);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReceipt<'a, const N: usize> {
+    #[serde(deserialize_with = "data")]
+    pub transaction_hash: Bytes<U32>,
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub transaction_index: u64,
+    #[serde(deserialize_with = "data")]
+    pub block_hash: Bytes<U32>,
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub block_number: u64,
+    /// EIP-2718 transaction type, `0x00` for legacy transactions
+    #[serde(
+        rename = "type",
+        default,
+        deserialize_with = "as_quantity::deserialize"
+    )]
+    pub ty: u8,
+    pub from: Address,
+    /// Recipient, `None` for the contract creation
+    #[serde(default)]
+    pub to: Option<Address>,
+    /// Address of the created contract
+    #[serde(default)]
+    pub contract_address: Option<Address>,
+    /// Gas used by the transaction and all the preceding ones in the block
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub cumulative_gas_used: u64,
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub gas_used: u64,
+    /// Price per gas paid, including the priority fee
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub effective_gas_price: u128,
+    #[serde(default, deserialize_with = "optional")]
+    pub blob_gas_used: Option<u64>,
+    #[serde(default, deserialize_with = "optional")]
+    pub blob_gas_price: Option<u128>,
+    /// Whether the transaction succeeded, since Byzantium
+    #[serde(default, deserialize_with = "status")]
+    pub status: Option<bool>,
+    /// Post transaction state root, before Byzantium
+    #[serde(default, deserialize_with = "optional_data")]
+    pub root: Option<Bytes<U32>>,
+    #[serde(borrow)]
+    pub logs: ListBuf<Log<'a>, N>,
+    #[serde(deserialize_with = "data")]
+    pub logs_bloom: Bytes<U256>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{address, from_hex};
+
+    #[test]
+    fn test_log() {
+        let json = r#"{
+            "address":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "topics":[],
+            "data":"0x",
+            "blockNumber":null,
+            "blockHash":null,
+            "transactionHash":null,
+            "transactionIndex":null,
+            "logIndex":null,
+            "removed":false
+        }"#;
+
+        let (log, _): (Log, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(
+            log,
+            Log {
+                address: address!("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+                data: "0x",
+                ..Default::default()
+            }
+        );
+        assert_eq!(log.decode_data(&mut []), Some(&[][..]));
+
+        let log = Log {
+            data: "0x0102",
+            ..log
+        };
+        assert_eq!(log.decode_data(&mut [0u8; 1]), None);
+        assert_eq!(log.decode_data(&mut [0u8; 4]), Some(&[1u8, 2][..]));
+
+        let log = Log {
+            data: "0x012",
+            ..log
+        };
+        assert_eq!(log.decode_data(&mut [0u8; 4]), None);
+    }
+
+    #[test]
+    fn test_topics() {
+        let json = r#"["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"]"#;
+        let (topics, _): (Topics, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(
+            topics.as_slice(),
+            &[Topic(from_hex(
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            )); 4]
+        );
+        assert_eq!(Topics::from_slice(&topics), Some(topics));
+
+        let json = r#"["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"]"#;
+        assert!(serde_json_core::from_str::<Topics>(json).is_err());
+        assert_eq!(Topics::from_slice(&[Topic::default(); 5]), None);
+
+        // Malformed topics are rejected rather than truncated or padded
+        for json in [
+            r#"["0xddf252ad"]"#,
+            r#"["ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"]"#,
+        ] {
+            assert!(serde_json_core::from_str::<Topics>(json).is_err());
+        }
+    }
+
+    #[test]
+    fn test_receipt() {
+        let json = json!(
+            receipt,
+            r#""to":null"#,
+            r#""contractAddress":"0x3535353535353535353535353535353535353535""#,
+            r#""logs":[]"#,
+            r#""type":"0x3""#,
+            r#""blobGasUsed":"0x20000""#,
+            r#""blobGasPrice":"0x1""#,
+            r#""status":"0x0""#
+        );
+
+        let (receipt, _): (TransactionReceipt<0>, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(
+            receipt,
+            TransactionReceipt {
+                transaction_hash: from_hex(
+                    "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"
+                ),
+                transaction_index: 0,
+                block_hash: from_hex(
+                    "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b"
+                ),
+                block_number: 0x13a8c40,
+                ty: 3,
+                from: address!("0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"),
+                to: None,
+                contract_address: Some(address!("0x3535353535353535353535353535353535353535")),
+                cumulative_gas_used: 21_000,
+                gas_used: 21_000,
+                effective_gas_price: 1_000_000_000,
+                blob_gas_used: Some(0x20000),
+                blob_gas_price: Some(1),
+                status: Some(false),
+                root: None,
+                logs: ListBuf::new(),
+                logs_bloom: Bytes::default(),
+            }
+        );
+
+        // Pre-Byzantium receipt
+        let json = json!(
+            receipt,
+            r#""to":"0x3535353535353535353535353535353535353535""#,
+            r#""contractAddress":null"#,
+            r#""logs":[]"#,
+            r#""root":"0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544""#
+        );
+
+        let (receipt, _): (TransactionReceipt<0>, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(receipt.ty, 0);
+        assert_eq!(receipt.status, None);
+        assert_eq!(
+            receipt.root,
+            Some(from_hex(
+                "0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544"
+            ))
+        );
+
+        let json = json!(
+            receipt,
+            r#""to":null"#,
+            r#""contractAddress":null"#,
+            r#""logs":[{"address":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48","topics":[],"data":"0x"},{"address":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48","topics":[],"data":"0x01"}]"#,
+            r#""status":"0x1""#
+        );
+
+        let (receipt, _): (TransactionReceipt<2>, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(receipt.logs.len(), 2);
+        assert_eq!(receipt.logs[1].data, "0x01");
+        assert!(serde_json_core::from_str::<TransactionReceipt<1>>(json).is_err());
+
+        let json = json!(
+            receipt,
+            r#""to":null"#,
+            r#""contractAddress":null"#,
+            r#""logs":[]"#,
+            r#""status":"0x2""#
+        );
+        assert!(serde_json_core::from_str::<TransactionReceipt<0>>(json).is_err());
+
+        // Malformed addresses are rejected rather than truncated or padded
+        for json in [
+            json!(
+                receipt,
+                r#""to":"0x35""#,
+                r#""contractAddress":null"#,
+                r#""logs":[]"#
+            ),
+            json!(
+                receipt,
+                r#""to":null"#,
+                r#""contractAddress":"""#,
+                r#""logs":[]"#
+            ),
+            json!(
+                receipt,
+                r#""to":null"#,
+                r#""contractAddress":null"#,
+                r#""logs":[{"address":"0xzz","topics":[],"data":"0x"}]"#
+            ),
+        ] {
+            assert!(serde_json_core::from_str::<TransactionReceipt<1>>(json).is_err());
+        }
+    }
+}
@@ -0,0 +1,1000 @@
+//! Solidity contract ABI encoding
+//!
+//! Head / tail encoding of the values passed to and returned from contract
+//! calls, as described by the specification:
+//! <https://docs.soliditylang.org/en/latest/abi-spec.html>
+//!
+//! Rust types map to the Solidity ones as follows:
+//!
+//! | Solidity      | Rust                                      |
+//! |---------------|-------------------------------------------|
+//! | `uintN`       | `u8`..`u128`, [`uint256`](type@uint256)   |
+//! | `intN`        | `i8`..`i128`, [`int256`](type@int256)     |
+//! | `address`     | [`Address`]                               |
+//! | `bool`        | `bool`                                    |
+//! | `bytesN`      | [`Bytes<N>`](crate::Bytes), `N <= 32`     |
+//! | `T[k]`        | `[T; k]`                                  |
+//! | `(T1, .. Tn)` | `(T1, .. Tn)`                             |
+//! | `bytes`       | `[u8]`, `&[u8]` when decoded              |
+//! | `string`      | `str`, `&str` when decoded                |
+//! | `T[]`         | `[T]`, [`Array<T>`] when decoded          |
+//!
+//! Since `[u8]` stands for `bytes`, arrays of `uint8` are not supported.
+//!
+//! Encoding is written into caller-provided buffers, for static types the
+//! encoded length is known at compilation time as [`Static::Length`], which
+//! allows encoding into [`Bytes`] of the exact length with [`encode_static`].
+//! Function arguments are encoded as a tuple:
+//!
+//! ```rust
+//! use ethgen::{abi, address, uint256};
+//!
+//!
+//!
+//! // transfer(address,uint256)
+//! let args = (
+//!     address!("0x5B38Da6a701c568545dCfcB03FcB875f56beddC4"),
+//!     uint256!("1000000000000000000"),
+//! );
+//!
+//! let data = abi::encode_static(&args);
+//! assert_eq!(data.len(), 64);
+//!
+//! let (to, value): (ethgen::Address, ethgen::uint256) = abi::decode(&data).unwrap();
+//! assert_eq!((to, value), args);
+//! ```
+//!
+//! Function selectors and event topics are computed from the canonical
+//! [`Signature`], at compilation time with [`selector!`](crate::selector)
+//! and [`event_topic!`](crate::event_topic) macros.
+//!
+//! Decoding borrows dynamic data from the input and validates every offset,
+//! length and padding, rejecting the input which is not a canonical encoding
+//! of the value bounds-wise.
+
+use crate::{int256, typenum, uint256, Address, Bytes};
+use core::{fmt, marker::PhantomData};
+use generic_array::ArrayLength;
+use typenum::{Const, IsLessOrEqual, Prod, Sum, ToUInt, True, U, U32};
+
+mod signature;
+#[doc(inline)]
+pub use signature::{event_topic, selector, Signature, SignatureError, MAX_SIGNATURE_LENGTH};
+
+/// Length of the ABI word
+const WORD: usize = 32;
+
+/// Error decoding ABI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The input ended before the value was complete
+    UnexpectedEnd,
+    /// Offset of the dynamic value is out of the tail bounds
+    InvalidOffset,
+    /// Length of the dynamic value is out of the input bounds
+    InvalidLength,
+    /// Non-zero padding or a value outside of the type range
+    InvalidPadding,
+    /// String is not a valid UTF-8
+    InvalidUtf8,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnexpectedEnd => "unexpected end of ABI input",
+            Self::InvalidOffset => "invalid ABI offset",
+            Self::InvalidLength => "invalid ABI length",
+            Self::InvalidPadding => "invalid ABI padding",
+            Self::InvalidUtf8 => "invalid UTF-8 in ABI string",
+        })
+    }
+}
+
+/// Solidity ABI type
+pub trait Type {
+    /// Whether the encoding length depends on the value
+    const DYNAMIC: bool;
+
+    /// Length of the value in the head of the enclosing tuple
+    ///
+    /// Equals to the encoded length for static types and to the length of
+    /// the offset word for dynamic ones.
+    const HEAD_LENGTH: usize;
+}
+
+/// Static ABI type with the encoded length known at compilation time
+pub trait Static: Type {
+    type Length: ArrayLength;
+}
+
+/// Type which can be an element of ABI array
+///
+/// Implemented for every ABI type except `u8`, as `[u8]` is `bytes`.
+pub trait Element: Type {}
+
+/// Data structure which can be ABI encoded
+pub trait Encodable: Type {
+    /// Exact length of the encoding product
+    fn length(&self) -> usize;
+
+    /// Encode into the beginning of the buffer
+    ///
+    /// Returns the amount of bytes written, which is always equal to
+    /// [`Encodable::length`].
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the encoding product;
+    fn encode(&self, buf: &mut [u8]) -> usize;
+}
+
+/// Data structure which can be decoded from ABI
+///
+/// The lifetime allows to borrow the decoded data from the input.
+pub trait Decodable<'a>: Type + Sized {
+    /// Decode a value, which encoding starts at the beginning of the input
+    fn decode(src: &'a [u8]) -> Result<Self, Error>;
+}
+
+/// Encode a static value into the byte array of the exact length
+pub fn encode_static<T: Static + Encodable>(value: &T) -> Bytes<T::Length> {
+    let mut bytes = Bytes::<T::Length>::default();
+    value.encode(&mut bytes);
+    bytes
+}
+
+/// Decode a value from the beginning of the input
+#[inline(always)]
+pub fn decode<'a, T: Decodable<'a>>(src: &'a [u8]) -> Result<T, Error> {
+    T::decode(src)
+}
+
+/// Get the word at the given position
+#[inline(always)]
+fn word(src: &[u8], pos: usize) -> Result<&[u8; WORD], Error> {
+    src.get(pos..pos + WORD)
+        .and_then(|w| w.try_into().ok())
+        .ok_or(Error::UnexpectedEnd)
+}
+
+/// Length of the data padded to the whole amount of words
+#[inline(always)]
+const fn padded(len: usize) -> usize {
+    len.div_ceil(WORD) * WORD
+}
+
+#[inline(always)]
+fn encode_usize(value: usize, buf: &mut [u8]) {
+    let be = value.to_be_bytes();
+    buf[..WORD - be.len()].fill(0);
+    buf[WORD - be.len()..WORD].copy_from_slice(&be);
+}
+
+/// Decode a word as usize, which is used for offsets and lengths
+#[inline(always)]
+fn decode_usize(word: &[u8; WORD]) -> Option<usize> {
+    const N: usize = core::mem::size_of::<usize>();
+
+    match word[..WORD - N].iter().all(|b| *b == 0) {
+        true => Some(usize::from_be_bytes(word[WORD - N..].try_into().ok()?)),
+        false => None,
+    }
+}
+
+/// Encode the item of a tuple or an array
+///
+/// Writes the item head at the given position and, for the dynamic item,
+/// its encoding at the end of the tail, which is advanced. Returns the
+/// length of the head.
+#[inline(always)]
+fn encode_item<T: Encodable + ?Sized>(
+    item: &T,
+    buf: &mut [u8],
+    pos: usize,
+    tail: &mut usize,
+) -> usize {
+    match T::DYNAMIC {
+        true => {
+            encode_usize(*tail, &mut buf[pos..]);
+            *tail += item.encode(&mut buf[*tail..]);
+            WORD
+        }
+        false => item.encode(&mut buf[pos..]),
+    }
+}
+
+/// Length of the item in the tail of the enclosing tuple
+#[inline(always)]
+fn tail_length<T: Encodable + ?Sized>(item: &T) -> usize {
+    match T::DYNAMIC {
+        true => item.length(),
+        false => 0,
+    }
+}
+
+/// Decode the item of a tuple or an array with the given head length
+#[inline(always)]
+fn decode_item<'a, T: Decodable<'a>>(src: &'a [u8], pos: usize, head: usize) -> Result<T, Error> {
+    match T::DYNAMIC {
+        true => {
+            let offset = decode_usize(word(src, pos)?).ok_or(Error::InvalidOffset)?;
+
+            // Dynamic data must be located in the tail
+            if offset < head || offset > src.len() {
+                return Err(Error::InvalidOffset);
+            }
+
+            T::decode(&src[offset..])
+        }
+        false => T::decode(src.get(pos..).ok_or(Error::UnexpectedEnd)?),
+    }
+}
+
+fn items_length<T: Encodable>(items: &[T]) -> usize {
+    items.len() * T::HEAD_LENGTH + items.iter().map(tail_length).sum::<usize>()
+}
+
+fn encode_items<T: Encodable>(items: &[T], buf: &mut [u8]) -> usize {
+    let (mut pos, mut tail) = (0usize, items.len() * T::HEAD_LENGTH);
+
+    for item in items {
+        pos += encode_item(item, buf, pos, &mut tail);
+    }
+
+    tail
+}
+
+/// Encode the length prefixed byte string padded to the whole words
+fn encode_bytes(src: &[u8], buf: &mut [u8]) -> usize {
+    let len = WORD + padded(src.len());
+
+    encode_usize(src.len(), buf);
+    buf[WORD..WORD + src.len()].copy_from_slice(src);
+    buf[WORD + src.len()..len].fill(0);
+
+    len
+}
+
+fn decode_bytes(src: &[u8]) -> Result<&[u8], Error> {
+    let len = decode_usize(word(src, 0)?).ok_or(Error::InvalidLength)?;
+    // The length is bounded by the input before padding, which may overflow
+    let data = src
+        .get(WORD..)
+        .filter(|data| len <= data.len())
+        .and_then(|data| data.get(..padded(len)))
+        .ok_or(Error::InvalidLength)?;
+
+    match data[len..].iter().all(|b| *b == 0) {
+        true => Ok(&data[..len]),
+        false => Err(Error::InvalidPadding),
+    }
+}
+
+impl<T: Type + ?Sized> Type for &T {
+    const DYNAMIC: bool = T::DYNAMIC;
+    const HEAD_LENGTH: usize = T::HEAD_LENGTH;
+}
+
+impl<T: Static + ?Sized> Static for &T {
+    type Length = T::Length;
+}
+
+impl<T: Element + ?Sized> Element for &T {}
+
+impl<T: Encodable + ?Sized> Encodable for &T {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        (**self).encode(buf)
+    }
+}
+
+macro_rules! impl_static_word {
+    ($($ty:ty),+) => {$(
+        impl Type for $ty {
+            const DYNAMIC: bool = false;
+            const HEAD_LENGTH: usize = WORD;
+        }
+
+        impl Static for $ty {
+            type Length = U32;
+        }
+    )+};
+}
+
+macro_rules! impl_element {
+    ($($ty:ty),+) => {$(
+        impl Element for $ty {}
+    )+};
+}
+
+macro_rules! impl_uint {
+    ($($ty:ty),+) => {$(
+        impl_static_word!($ty);
+
+        impl Encodable for $ty {
+            #[inline(always)]
+            fn length(&self) -> usize {
+                WORD
+            }
+
+            fn encode(&self, buf: &mut [u8]) -> usize {
+                let be = self.to_be_bytes();
+                buf[..WORD - be.len()].fill(0);
+                buf[WORD - be.len()..WORD].copy_from_slice(&be);
+                WORD
+            }
+        }
+
+        impl Decodable<'_> for $ty {
+            fn decode(src: &[u8]) -> Result<Self, Error> {
+                const N: usize = core::mem::size_of::<$ty>();
+
+                let word = word(src, 0)?;
+                if word[..WORD - N].iter().any(|b| *b != 0) {
+                    return Err(Error::InvalidPadding);
+                }
+
+                // Infallible, as the slice length is exactly N
+                let be = word[WORD - N..].try_into().map_err(|_| Error::InvalidPadding)?;
+                Ok(<$ty>::from_be_bytes(be))
+            }
+        }
+    )+};
+}
+
+impl_uint!(u8, u16, u32, u64, u128, uint256);
+
+macro_rules! impl_int {
+    ($($ty:ty),+) => {$(
+        impl_static_word!($ty);
+
+        impl Encodable for $ty {
+            #[inline(always)]
+            fn length(&self) -> usize {
+                WORD
+            }
+
+            fn encode(&self, buf: &mut [u8]) -> usize {
+                let be = self.to_be_bytes();
+                let ext = match be[0] & 0x80 {
+                    0 => 0x00,
+                    _ => 0xff,
+                };
+
+                buf[..WORD - be.len()].fill(ext);
+                buf[WORD - be.len()..WORD].copy_from_slice(&be);
+                WORD
+            }
+        }
+
+        impl Decodable<'_> for $ty {
+            fn decode(src: &[u8]) -> Result<Self, Error> {
+                const N: usize = core::mem::size_of::<$ty>();
+
+                let word = word(src, 0)?;
+                let ext = match word[WORD - N] & 0x80 {
+                    0 => 0x00,
+                    _ => 0xff,
+                };
+
+                if word[..WORD - N].iter().any(|b| *b != ext) {
+                    return Err(Error::InvalidPadding);
+                }
+
+                // Infallible, as the slice length is exactly N
+                let be = word[WORD - N..].try_into().map_err(|_| Error::InvalidPadding)?;
+                Ok(<$ty>::from_be_bytes(be))
+            }
+        }
+    )+};
+}
+
+impl_int!(i8, i16, i32, i64, i128, int256);
+
+impl_static_word!(bool, Address);
+
+impl_element!(u16, u32, u64, u128, uint256);
+impl_element!(i8, i16, i32, i64, i128, int256);
+impl_element!(bool, Address, str, [u8]);
+
+impl Encodable for bool {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        WORD
+    }
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        (*self as u8).encode(buf)
+    }
+}
+
+impl Decodable<'_> for bool {
+    fn decode(src: &[u8]) -> Result<Self, Error> {
+        match u8::decode(src)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::InvalidPadding),
+        }
+    }
+}
+
+impl Encodable for Address {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        WORD
+    }
+
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        buf[..12].fill(0);
+        buf[12..WORD].copy_from_slice(self.as_slice());
+        WORD
+    }
+}
+
+impl Decodable<'_> for Address {
+    fn decode(src: &[u8]) -> Result<Self, Error> {
+        let word = word(src, 0)?;
+
+        match word[..12].iter().all(|b| *b == 0) {
+            true => Ok(Address::from(*Bytes::from_slice(&word[12..]))),
+            false => Err(Error::InvalidPadding),
+        }
+    }
+}
+
+impl<N> Type for Bytes<N>
+where
+    N: ArrayLength + IsLessOrEqual<U32, Output = True>,
+{
+    const DYNAMIC: bool = false;
+    const HEAD_LENGTH: usize = WORD;
+}
+
+impl<N> Static for Bytes<N>
+where
+    N: ArrayLength + IsLessOrEqual<U32, Output = True>,
+{
+    type Length = U32;
+}
+
+impl<N> Element for Bytes<N> where N: ArrayLength + IsLessOrEqual<U32, Output = True> {}
+
+impl<N> Encodable for Bytes<N>
+where
+    N: ArrayLength + IsLessOrEqual<U32, Output = True>,
+{
+    #[inline(always)]
+    fn length(&self) -> usize {
+        WORD
+    }
+
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        buf[..N::USIZE].copy_from_slice(self);
+        buf[N::USIZE..WORD].fill(0);
+        WORD
+    }
+}
+
+impl<N> Decodable<'_> for Bytes<N>
+where
+    N: ArrayLength + IsLessOrEqual<U32, Output = True>,
+{
+    fn decode(src: &[u8]) -> Result<Self, Error> {
+        let word = word(src, 0)?;
+
+        match word[N::USIZE..].iter().all(|b| *b == 0) {
+            true => Ok(Bytes::from_slice(&word[..N::USIZE]).clone()),
+            false => Err(Error::InvalidPadding),
+        }
+    }
+}
+
+impl Type for [u8] {
+    const DYNAMIC: bool = true;
+    const HEAD_LENGTH: usize = WORD;
+}
+
+impl Encodable for [u8] {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        WORD + padded(self.len())
+    }
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        encode_bytes(self, buf)
+    }
+}
+
+impl<'a> Decodable<'a> for &'a [u8] {
+    #[inline(always)]
+    fn decode(src: &'a [u8]) -> Result<Self, Error> {
+        decode_bytes(src)
+    }
+}
+
+impl Type for str {
+    const DYNAMIC: bool = true;
+    const HEAD_LENGTH: usize = WORD;
+}
+
+impl Encodable for str {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        WORD + padded(self.len())
+    }
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        encode_bytes(self.as_bytes(), buf)
+    }
+}
+
+impl<'a> Decodable<'a> for &'a str {
+    fn decode(src: &'a [u8]) -> Result<Self, Error> {
+        core::str::from_utf8(decode_bytes(src)?).map_err(|_| Error::InvalidUtf8)
+    }
+}
+
+impl<T: Element> Type for [T] {
+    const DYNAMIC: bool = true;
+    const HEAD_LENGTH: usize = WORD;
+}
+
+impl<T: Element> Element for [T] {}
+
+impl<T: Element + Encodable> Encodable for [T] {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        WORD + items_length(self)
+    }
+
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        encode_usize(self.len(), buf);
+        WORD + encode_items(self, &mut buf[WORD..])
+    }
+}
+
+impl<T: Element, const N: usize> Type for [T; N] {
+    const DYNAMIC: bool = T::DYNAMIC;
+    const HEAD_LENGTH: usize = match T::DYNAMIC {
+        true => WORD,
+        false => N * T::HEAD_LENGTH,
+    };
+}
+
+impl<T: Element + Static, const N: usize> Static for [T; N]
+where
+    Const<N>: ToUInt,
+    T::Length: core::ops::Mul<U<N>>,
+    Prod<T::Length, U<N>>: ArrayLength,
+{
+    type Length = Prod<T::Length, U<N>>;
+}
+
+impl<T: Element, const N: usize> Element for [T; N] {}
+
+impl<T: Element + Encodable, const N: usize> Encodable for [T; N] {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        items_length(self)
+    }
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        encode_items(self, buf)
+    }
+}
+
+impl<'a, T: Element + Decodable<'a>, const N: usize> Decodable<'a> for [T; N] {
+    fn decode(src: &'a [u8]) -> Result<Self, Error> {
+        let head = N * T::HEAD_LENGTH;
+        let mut error = None;
+
+        let items = core::array::from_fn(|i| match decode_item(src, i * T::HEAD_LENGTH, head) {
+            Ok(item) => Some(item),
+            Err(e) => {
+                error.get_or_insert(e);
+                None
+            }
+        });
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(items.map(|item: Option<T>| item.expect("all items are decoded"))),
+        }
+    }
+}
+
+/// Lazily decoded dynamic array `T[]`
+///
+/// Borrows the encoded items from the input and decodes them on iteration,
+/// which allows to decode arrays of arbitrary length without allocation.
+/// The bounds of the array head are validated upfront.
+///
+/// ```rust
+/// use ethgen::abi::{self, Array, Encodable};
+///
+///
+///
+/// let mut buf = [0u8; 256];
+/// let n = (&["cat", "dog"][..],).encode(&mut buf);
+///
+/// let (items,): (Array<&str>,) = abi::decode(&buf[..n]).unwrap();
+/// assert_eq!(items.len(), 2);
+///
+/// let mut items = items.into_iter();
+/// assert_eq!(Some(Ok("cat")), items.next());
+/// assert_eq!(Some(Ok("dog")), items.next());
+/// assert_eq!(None, items.next());
+/// ```
+pub struct Array<'a, T> {
+    data: &'a [u8],
+    len: usize,
+    pos: usize,
+    item: PhantomData<T>,
+}
+
+impl<T> Array<'_, T> {
+    /// Amount of the items left
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len - self.pos
+    }
+
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == self.pos
+    }
+}
+
+impl<T> Clone for Array<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Array<'_, T> {}
+
+impl<T> fmt::Debug for Array<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Array").field("len", &self.len()).finish()
+    }
+}
+
+impl<'a, T: Element + Decodable<'a>> Iterator for Array<'a, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.len {
+            return None;
+        }
+
+        let head = self.len * T::HEAD_LENGTH;
+        let item = decode_item(self.data, self.pos * T::HEAD_LENGTH, head);
+        self.pos += 1;
+
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl<T: Element> Element for Array<'_, T> {}
+
+impl<T: Element> Type for Array<'_, T> {
+    const DYNAMIC: bool = true;
+    const HEAD_LENGTH: usize = WORD;
+}
+
+impl<'a, T: Element> Decodable<'a> for Array<'a, T> {
+    fn decode(src: &'a [u8]) -> Result<Self, Error> {
+        let len = decode_usize(word(src, 0)?).ok_or(Error::InvalidLength)?;
+        let data = &src[WORD..];
+
+        match len.checked_mul(T::HEAD_LENGTH) {
+            Some(head) if head <= data.len() => Ok(Self {
+                data,
+                len,
+                pos: 0,
+                item: PhantomData,
+            }),
+            _ => Err(Error::InvalidLength),
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident $idx:tt),+) => {
+        impl<$($name: Type),+> Type for ($($name,)+) {
+            const DYNAMIC: bool = false $(|| $name::DYNAMIC)+;
+            const HEAD_LENGTH: usize = match Self::DYNAMIC {
+                true => WORD,
+                false => 0 $(+ $name::HEAD_LENGTH)+,
+            };
+        }
+
+        impl<$($name: Type),+> Element for ($($name,)+) {}
+
+        impl<$($name: Encodable),+> Encodable for ($($name,)+) {
+            fn length(&self) -> usize {
+                0 $(+ $name::HEAD_LENGTH + tail_length(&self.$idx))+
+            }
+
+            #[allow(unused_assignments)]
+            fn encode(&self, buf: &mut [u8]) -> usize {
+                let mut pos = 0usize;
+                let mut tail = 0 $(+ $name::HEAD_LENGTH)+;
+                $(pos += encode_item(&self.$idx, buf, pos, &mut tail);)+
+                tail
+            }
+        }
+
+        impl<'a, $($name: Decodable<'a>),+> Decodable<'a> for ($($name,)+) {
+            #[allow(unused_assignments)]
+            fn decode(src: &'a [u8]) -> Result<Self, Error> {
+                let head = 0 $(+ $name::HEAD_LENGTH)+;
+                let mut pos = 0usize;
+
+                Ok(($({
+                    let item = decode_item::<$name>(src, pos, head)?;
+                    pos += $name::HEAD_LENGTH;
+                    item
+                },)+))
+            }
+        }
+    };
+}
+
+impl_tuple!(A 0);
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+impl<A: Static> Static for (A,) {
+    type Length = A::Length;
+}
+
+/// Static tuple length is the sum of the prefix tuple and the last item
+macro_rules! impl_static_tuple {
+    ($($prefix:ident),+; $last:ident) => {
+        impl<$($prefix,)+ $last> Static for ($($prefix,)+ $last,)
+        where
+            $($prefix: Type,)+
+            ($($prefix,)+): Static,
+            $last: Static,
+            <($($prefix,)+) as Static>::Length: core::ops::Add<$last::Length>,
+            Sum<<($($prefix,)+) as Static>::Length, $last::Length>: ArrayLength,
+        {
+            type Length = Sum<<($($prefix,)+) as Static>::Length, $last::Length>;
+        }
+    };
+}
+
+impl_static_tuple!(A; B);
+impl_static_tuple!(A, B; C);
+impl_static_tuple!(A, B, C; D);
+impl_static_tuple!(A, B, C, D; E);
+impl_static_tuple!(A, B, C, D, E; F);
+impl_static_tuple!(A, B, C, D, E, F; G);
+impl_static_tuple!(A, B, C, D, E, F, G; H);
+impl_static_tuple!(A, B, C, D, E, F, G, H; I);
+impl_static_tuple!(A, B, C, D, E, F, G, H, I; J);
+impl_static_tuple!(A, B, C, D, E, F, G, H, I, J; K);
+impl_static_tuple!(A, B, C, D, E, F, G, H, I, J, K; L);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{int256, typenum::*, unhex};
+
+    fn check<T>(value: T, expected: &str)
+    where
+        T: Encodable + for<'a> Decodable<'a> + PartialEq + fmt::Debug,
+    {
+        let mut bytes = [0u8; 512];
+        let pad = crate::hex::decode(expected.as_bytes(), &mut bytes);
+        let expected = &bytes[pad..];
+
+        let mut buf = [0u8; 512];
+        let n = value.encode(&mut buf);
+
+        assert_eq!(n, value.length());
+        assert_eq!(&buf[..n], expected);
+        assert_eq!(decode::<T>(expected), Ok(value));
+    }
+
+    #[test]
+    fn test_static() {
+        check(
+            1u8,
+            "0000000000000000000000000000000000000000000000000000000000000001",
+        );
+        check(
+            -1i64,
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        );
+        check(
+            int256::MIN,
+            "8000000000000000000000000000000000000000000000000000000000000000",
+        );
+        check(
+            true,
+            "0000000000000000000000000000000000000000000000000000000000000001",
+        );
+        check(
+            Bytes::from_array(*b"abc"),
+            "6162630000000000000000000000000000000000000000000000000000000000",
+        );
+        check(
+            crate::address!("0x5B38Da6a701c568545dCfcB03FcB875f56beddC4"),
+            "0000000000000000000000005b38da6a701c568545dcfcb03fcb875f56beddc4",
+        );
+
+        // baz(uint32,bool) from the specification
+        check(
+            (69u32, true),
+            "0000000000000000000000000000000000000000000000000000000000000045\
+                0000000000000000000000000000000000000000000000000000000000000001",
+        );
+
+        // bar(bytes3[2]) from the specification
+        check(
+            [Bytes::from_array(*b"abc"), Bytes::from_array(*b"def")],
+            "6162630000000000000000000000000000000000000000000000000000000000\
+                6465660000000000000000000000000000000000000000000000000000000000",
+        );
+    }
+
+    #[test]
+    fn test_static_length() {
+        assert_eq!(<(u8, bool, Address) as Static>::Length::USIZE, 96);
+        assert_eq!(<[(u8, bool); 3] as Static>::Length::USIZE, 192);
+        assert_eq!(<([u64; 2], Bytes<U4>) as Static>::Length::USIZE, 96);
+
+        let data: Bytes<U64> = encode_static(&(1u64, [2i8]));
+        assert_eq!(data[31], 1);
+        assert_eq!(data[63], 2);
+    }
+
+    #[test]
+    fn test_dynamic() {
+        // sam(bytes,bool,uint256[]) from the specification
+        let expected: [u8; 288] = unhex!(
+            "0000000000000000000000000000000000000000000000000000000000000060\
+            0000000000000000000000000000000000000000000000000000000000000001\
+            00000000000000000000000000000000000000000000000000000000000000a0\
+            0000000000000000000000000000000000000000000000000000000000000004\
+            6461766500000000000000000000000000000000000000000000000000000000\
+            0000000000000000000000000000000000000000000000000000000000000003\
+            0000000000000000000000000000000000000000000000000000000000000001\
+            0000000000000000000000000000000000000000000000000000000000000002\
+            0000000000000000000000000000000000000000000000000000000000000003"
+        );
+
+        let value = (&b"dave"[..], true, &[1u64, 2, 3][..]);
+        let mut buf = [0u8; 1024];
+        let n = value.encode(&mut buf);
+        assert_eq!(n, value.length());
+        assert_eq!(&buf[..n], &expected);
+
+        let (data, flag, items): (&[u8], bool, Array<u64>) = decode(&expected).unwrap();
+        assert_eq!(data, b"dave");
+        assert!(flag);
+        assert!(items.eq([Ok(1), Ok(2), Ok(3)]));
+
+        // g(uint256[][],string[]) from the specification
+        let expected: [u8; 640] = unhex!(
+            "0000000000000000000000000000000000000000000000000000000000000040\
+            0000000000000000000000000000000000000000000000000000000000000140\
+            0000000000000000000000000000000000000000000000000000000000000002\
+            0000000000000000000000000000000000000000000000000000000000000040\
+            00000000000000000000000000000000000000000000000000000000000000a0\
+            0000000000000000000000000000000000000000000000000000000000000002\
+            0000000000000000000000000000000000000000000000000000000000000001\
+            0000000000000000000000000000000000000000000000000000000000000002\
+            0000000000000000000000000000000000000000000000000000000000000001\
+            0000000000000000000000000000000000000000000000000000000000000003\
+            0000000000000000000000000000000000000000000000000000000000000003\
+            0000000000000000000000000000000000000000000000000000000000000060\
+            00000000000000000000000000000000000000000000000000000000000000a0\
+            00000000000000000000000000000000000000000000000000000000000000e0\
+            0000000000000000000000000000000000000000000000000000000000000003\
+            6f6e650000000000000000000000000000000000000000000000000000000000\
+            0000000000000000000000000000000000000000000000000000000000000003\
+            74776f0000000000000000000000000000000000000000000000000000000000\
+            0000000000000000000000000000000000000000000000000000000000000005\
+            7468726565000000000000000000000000000000000000000000000000000000"
+        );
+
+        let value = (
+            &[&[1u64, 2][..], &[3u64][..]][..],
+            &["one", "two", "three"][..],
+        );
+        let n = value.encode(&mut buf);
+        assert_eq!(n, value.length());
+        assert_eq!(&buf[..n], &expected);
+
+        let (nested, strings): (Array<Array<u64>>, Array<&str>) = decode(&expected).unwrap();
+        let mut nested = nested.map(Result::unwrap);
+        assert!(nested.next().unwrap().eq([Ok(1), Ok(2)]));
+        assert!(nested.next().unwrap().eq([Ok(3)]));
+        assert!(strings.eq([Ok("one"), Ok("two"), Ok("three")]));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let mut word = [0u8; 32];
+        assert_eq!(decode::<u64>(&word[..31]), Err(Error::UnexpectedEnd));
+
+        word[0] = 1;
+        assert_eq!(decode::<u64>(&word), Err(Error::InvalidPadding));
+        assert_eq!(decode::<Address>(&word), Err(Error::InvalidPadding));
+        assert_eq!(decode::<i8>(&word), Err(Error::InvalidPadding));
+        assert_eq!(
+            decode::<Bytes<U4>>(&[0xffu8; 32]),
+            Err(Error::InvalidPadding)
+        );
+
+        word = [0u8; 32];
+        word[31] = 2;
+        assert_eq!(decode::<bool>(&word), Err(Error::InvalidPadding));
+
+        // Offset pointing into the head
+        let mut buf = [0u8; 96];
+        buf[31] = 0x00;
+        assert_eq!(decode::<(&[u8],)>(&buf), Err(Error::InvalidOffset));
+
+        // Offset out of bounds
+        buf[31] = 0x80;
+        assert_eq!(decode::<(&[u8],)>(&buf), Err(Error::InvalidOffset));
+
+        // Length exceeding the input
+        buf[31] = 0x20;
+        buf[63] = 0x21;
+        assert_eq!(decode::<(&[u8],)>(&buf), Err(Error::InvalidLength));
+        assert_eq!(
+            decode::<(Array<u64>,)>(&buf).unwrap_err(),
+            Error::InvalidLength
+        );
+
+        // Length word overflowing the padding
+        buf[32..64].fill(0);
+        buf[64 - core::mem::size_of::<usize>()..64].fill(0xff);
+        assert_eq!(decode::<(&[u8],)>(&buf), Err(Error::InvalidLength));
+        assert_eq!(decode::<(&str,)>(&buf), Err(Error::InvalidLength));
+        assert_eq!(
+            decode::<(Array<u64>,)>(&buf).unwrap_err(),
+            Error::InvalidLength
+        );
+        buf[32..64].fill(0);
+
+        // Dirty padding of the byte string
+        buf[63] = 0x01;
+        buf[64] = 0x61;
+        buf[65] = 0x62;
+        assert_eq!(decode::<(&[u8],)>(&buf), Err(Error::InvalidPadding));
+
+        // Invalid UTF-8
+        buf[64] = 0xff;
+        buf[65] = 0x00;
+        assert_eq!(decode::<(&str,)>(&buf), Err(Error::InvalidUtf8));
+    }
+}
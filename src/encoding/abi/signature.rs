@@ -0,0 +1,564 @@
+//! Canonical function and event signatures
+//!
+//! Function selectors and event topics are computed from the canonical
+//! signature: the name followed by the parenthesised comma separated list of
+//! parameter types, with no whitespace and type aliases expanded.
+
+use crate::{
+    keccak::const_keccak256,
+    typenum::{U32, U4},
+    Bytes,
+};
+use core::fmt;
+
+/// Max length of the canonical signature
+pub const MAX_SIGNATURE_LENGTH: usize = 1024;
+
+/// Keywords allowed between the parameter type and its name
+const MODIFIERS: [&[u8]; 5] = [b"indexed", b"memory", b"calldata", b"storage", b"payable"];
+
+/// The error type returned when parsing a signature fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureError {
+    /// Function or event name is not a valid identifier
+    InvalidName,
+    /// Unknown type or type with invalid size
+    InvalidType,
+    /// Misplaced or missing parentheses, commas, brackets or names
+    InvalidSyntax,
+    /// Canonical signature exceeds [`MAX_SIGNATURE_LENGTH`]
+    TooLong,
+}
+
+impl SignatureError {
+    /// Panic with the error description in const context
+    pub(crate) const fn panic(self) -> ! {
+        match self {
+            Self::InvalidName => panic!("Invalid name in signature"),
+            Self::InvalidType => panic!("Invalid type in signature"),
+            Self::InvalidSyntax => panic!("Invalid signature syntax"),
+            Self::TooLong => panic!("Signature is too long"),
+        }
+    }
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName => write!(f, "invalid name in signature"),
+            Self::InvalidType => write!(f, "invalid type in signature"),
+            Self::InvalidSyntax => write!(f, "invalid signature syntax"),
+            Self::TooLong => write!(f, "signature is too long"),
+        }
+    }
+}
+
+/// Propagate the error in const context, where `?` is not available
+macro_rules! tri {
+    ($e:expr) => {
+        match $e {
+            Ok(value) => value,
+            Err(e) => return Err(e),
+        }
+    };
+}
+
+#[inline(always)]
+const fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_' || b == b'$'
+}
+
+#[inline(always)]
+const fn is_ident(b: u8) -> bool {
+    is_ident_start(b) || b.is_ascii_digit()
+}
+
+const fn skip_whitespace(src: &[u8], mut pos: usize) -> usize {
+    while pos < src.len() && src[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+    pos
+}
+
+const fn ident_end(src: &[u8], mut pos: usize) -> usize {
+    while pos < src.len() && is_ident(src[pos]) {
+        pos += 1;
+    }
+    pos
+}
+
+/// Compare the source range with the literal
+const fn eq(src: &[u8], start: usize, end: usize, lit: &[u8]) -> bool {
+    if end - start != lit.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < lit.len() {
+        if src[start + i] != lit[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// Check if the source range starts with the literal
+const fn starts_with(src: &[u8], start: usize, end: usize, lit: &[u8]) -> bool {
+    end - start >= lit.len() && eq(src, start, start + lit.len(), lit)
+}
+
+/// Parse decimal size without leading zeroes, `None` for invalid or too
+/// large values
+const fn parse_size(src: &[u8], mut pos: usize, end: usize) -> Option<usize> {
+    if pos == end || src[pos] == b'0' {
+        return None;
+    }
+
+    let mut value = 0usize;
+    while pos < end {
+        if !src[pos].is_ascii_digit() || value > usize::MAX / 100 {
+            return None;
+        }
+
+        value = value * 10 + (src[pos] - b'0') as usize;
+        pos += 1;
+    }
+
+    Some(value)
+}
+
+/// Canonical function or event signature
+///
+/// Normalizes a human readable signature: strips whitespace, parameter
+/// names and `indexed` / data location keywords, expands `uint`, `int` and
+/// `byte` aliases. Validates the name and every type, including sizes of
+/// `uintN`, `intN` and `bytesN`, arrays and tuples.
+///
+/// ```rust
+/// use ethgen::abi::Signature;
+///
+///
+///
+/// let sig = Signature::parse("Transfer(address indexed from, address indexed to, uint value)");
+/// assert_eq!("Transfer(address,address,uint256)", sig.unwrap().as_str());
+///
+/// let sig = Signature::parse("swap((address, uint24)[] calldata path, bytes memory)");
+/// assert_eq!("swap((address,uint24)[],bytes)", sig.unwrap().as_str());
+///
+/// assert!(Signature::parse("transfer(address to uint256)").is_err());
+/// ```
+#[derive(Clone, Copy)]
+pub struct Signature {
+    buf: [u8; MAX_SIGNATURE_LENGTH],
+    len: usize,
+}
+
+impl Signature {
+    const EMPTY: Self = Self {
+        buf: [0u8; MAX_SIGNATURE_LENGTH],
+        len: 0,
+    };
+
+    const fn push(mut self, src: &[u8], start: usize, end: usize) -> Result<Self, SignatureError> {
+        if self.len + end - start > MAX_SIGNATURE_LENGTH {
+            return Err(SignatureError::TooLong);
+        }
+
+        let mut i = start;
+        while i < end {
+            self.buf[self.len] = src[i];
+            self.len += 1;
+            i += 1;
+        }
+
+        Ok(self)
+    }
+
+    #[inline(always)]
+    const fn push_lit(self, lit: &[u8]) -> Result<Self, SignatureError> {
+        self.push(lit, 0, lit.len())
+    }
+
+    /// Validate and push the elementary type, expanding aliases
+    const fn push_type(self, src: &[u8], start: usize, end: usize) -> Result<Self, SignatureError> {
+        if eq(src, start, end, b"address")
+            || eq(src, start, end, b"bool")
+            || eq(src, start, end, b"string")
+            || eq(src, start, end, b"bytes")
+        {
+            return self.push(src, start, end);
+        }
+
+        if eq(src, start, end, b"uint") {
+            return self.push_lit(b"uint256");
+        }
+
+        if eq(src, start, end, b"int") {
+            return self.push_lit(b"int256");
+        }
+
+        if eq(src, start, end, b"byte") {
+            return self.push_lit(b"bytes1");
+        }
+
+        let valid = match src[start] {
+            b'u' if starts_with(src, start, end, b"uint") => {
+                matches!(parse_size(src, start + 4, end), Some(n) if n % 8 == 0 && n <= 256)
+            }
+            b'i' if starts_with(src, start, end, b"int") => {
+                matches!(parse_size(src, start + 3, end), Some(n) if n % 8 == 0 && n <= 256)
+            }
+            b'b' if starts_with(src, start, end, b"bytes") => {
+                matches!(parse_size(src, start + 5, end), Some(n) if n <= 32)
+            }
+            _ => false,
+        };
+
+        match valid {
+            true => self.push(src, start, end),
+            false => Err(SignatureError::InvalidType),
+        }
+    }
+
+    /// Parse and normalize the signature
+    pub const fn parse(src: &str) -> Result<Self, SignatureError> {
+        let src = src.as_bytes();
+        let mut sig = Self::EMPTY;
+
+        let mut pos = skip_whitespace(src, 0);
+        if pos == src.len() || !is_ident_start(src[pos]) {
+            return Err(SignatureError::InvalidName);
+        }
+
+        let end = ident_end(src, pos);
+        sig = tri!(sig.push(src, pos, end));
+
+        pos = skip_whitespace(src, end);
+        if pos == src.len() || src[pos] != b'(' {
+            return Err(SignatureError::InvalidSyntax);
+        }
+
+        sig = tri!(sig.push_lit(b"("));
+        pos += 1;
+
+        // Parser state: nesting of tuples, whether a type is expected next,
+        // whether an empty parameter list can be closed, and whether the
+        // modifiers or the name of the current parameter were seen.
+        let mut depth = 1usize;
+        let mut expect_type = true;
+        let mut allow_close = true;
+        let (mut modified, mut named) = (false, false);
+
+        loop {
+            pos = skip_whitespace(src, pos);
+            if pos == src.len() {
+                return Err(SignatureError::InvalidSyntax);
+            }
+
+            let b = src[pos];
+
+            if expect_type {
+                if b == b')' && allow_close {
+                    sig = tri!(sig.push_lit(b")"));
+                    pos += 1;
+                    break;
+                }
+
+                if b == b'(' {
+                    sig = tri!(sig.push_lit(b"("));
+                    depth += 1;
+                    pos += 1;
+                    allow_close = false;
+                    continue;
+                }
+
+                if !is_ident_start(b) {
+                    return Err(SignatureError::InvalidType);
+                }
+
+                let end = ident_end(src, pos);
+                sig = tri!(sig.push_type(src, pos, end));
+                pos = end;
+                expect_type = false;
+                continue;
+            }
+
+            // Array suffix `[]` or `[k]` directly follows the type
+            if b == b'[' && !modified && !named {
+                let mut end = pos + 1;
+                while end < src.len() && src[end] != b']' {
+                    end += 1;
+                }
+
+                if end == src.len() {
+                    return Err(SignatureError::InvalidSyntax);
+                }
+
+                if end > pos + 1 && parse_size(src, pos + 1, end).is_none() {
+                    return Err(SignatureError::InvalidType);
+                }
+
+                sig = tri!(sig.push(src, pos, end + 1));
+                pos = end + 1;
+                continue;
+            }
+
+            // Modifier keywords followed by at most one parameter name
+            if is_ident_start(b) && !named {
+                let end = ident_end(src, pos);
+
+                let mut i = 0;
+                let mut modifier = false;
+                while i < MODIFIERS.len() {
+                    modifier = modifier || eq(src, pos, end, MODIFIERS[i]);
+                    i += 1;
+                }
+
+                match modifier {
+                    true => modified = true,
+                    false => named = true,
+                }
+
+                pos = end;
+                continue;
+            }
+
+            match b {
+                b',' => {
+                    sig = tri!(sig.push_lit(b","));
+                    expect_type = true;
+                    allow_close = false;
+                }
+                b')' => {
+                    sig = tri!(sig.push_lit(b")"));
+                    depth -= 1;
+                }
+                _ => return Err(SignatureError::InvalidSyntax),
+            }
+
+            pos += 1;
+            (modified, named) = (false, false);
+
+            if depth == 0 {
+                break;
+            }
+        }
+
+        match skip_whitespace(src, pos) == src.len() {
+            true => Ok(sig),
+            false => Err(SignatureError::InvalidSyntax),
+        }
+    }
+
+    /// Parse the signature in const context
+    ///
+    /// ## Panics
+    ///
+    /// - if the signature is malformed;
+    pub const fn const_parse(src: &str) -> Self {
+        match Self::parse(src) {
+            Ok(sig) => sig,
+            Err(e) => e.panic(),
+        }
+    }
+
+    #[inline(always)]
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.buf.split_at(self.len).0
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only ASCII characters are copied from the source
+        match core::str::from_utf8(self.as_bytes()) {
+            Ok(s) => s,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// First 4 bytes of the signature hash identifying a function
+    pub const fn selector(&self) -> Bytes<U4> {
+        let hash = const_keccak256(self.as_bytes());
+        Bytes::from_array([hash[0], hash[1], hash[2], hash[3]])
+    }
+
+    /// Signature hash identifying an event, the first topic of the log
+    pub const fn topic(&self) -> Bytes<U32> {
+        Bytes::from_array(const_keccak256(self.as_bytes()))
+    }
+}
+
+impl PartialEq for Signature {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for Signature {}
+
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Signature {}", self.as_str())
+    }
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Compute the function selector from the signature
+///
+/// ```rust
+/// use ethgen::abi;
+///
+///
+/// let selector = abi::selector("transfer(address to, uint amount)").unwrap();
+/// assert_eq!([0xa9, 0x05, 0x9c, 0xbb], selector.into_array());
+/// ```
+pub fn selector(signature: &str) -> Result<Bytes<U4>, SignatureError> {
+    Signature::parse(signature).map(|sig| sig.selector())
+}
+
+/// Compute the event topic from the signature
+pub fn event_topic(signature: &str) -> Result<Bytes<U32>, SignatureError> {
+    Signature::parse(signature).map(|sig| sig.topic())
+}
+
+/// Compute the function selector in compilation time
+///
+/// ## Panics
+///
+/// - if the signature is malformed;
+///
+/// <br>
+///
+/// ## Example
+///
+/// ```
+/// use ethgen::{selector, Bytes, typenum::U4};
+///
+///
+/// const TRANSFER: Bytes<U4> = selector!("transfer(address,uint256)");
+/// assert_eq!([0xa9, 0x05, 0x9c, 0xbb], TRANSFER.into_array());
+/// ```
+///
+/// ```compile_fail
+/// use ethgen::{selector, Bytes, typenum::U4};
+///
+///
+/// const TRANSFER: Bytes<U4> = selector!("transfer(address,uint255)");
+/// ```
+#[macro_export]
+macro_rules! selector {
+    ($s:literal) => {{
+        const SELECTOR: $crate::Bytes<$crate::typenum::U4> =
+            $crate::abi::Signature::const_parse($s).selector();
+        SELECTOR
+    }};
+}
+
+/// Compute the event topic in compilation time
+///
+/// ## Panics
+///
+/// - if the signature is malformed;
+///
+/// <br>
+///
+/// ## Example
+///
+/// ```
+/// use ethgen::{event_topic, unhex};
+///
+///
+/// let topic = event_topic!("Transfer(address indexed from, address indexed to, uint value)");
+/// let expected: [u8; 32] =
+///     unhex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+///
+/// assert_eq!(expected, topic.into_array());
+/// ```
+///
+/// ```compile_fail
+/// use ethgen::event_topic;
+///
+///
+/// let topic = event_topic!("Transfer(address,address,uint256");
+/// ```
+#[macro_export]
+macro_rules! event_topic {
+    ($s:literal) => {{
+        const TOPIC: $crate::Bytes<$crate::typenum::U32> =
+            $crate::abi::Signature::const_parse($s).topic();
+        TOPIC
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let cases = [
+            ("foo()", "foo()"),
+            (" foo ( ) ", "foo()"),
+            ("transfer(address,uint256)", "transfer(address,uint256)"),
+            ("f(uint, int, byte)", "f(uint256,int256,bytes1)"),
+            ("f(uint8[2][] memory xs, bytes32)", "f(uint8[2][],bytes32)"),
+            (
+                "f((uint,(bool,string)[])[3] calldata)",
+                "f((uint256,(bool,string)[])[3])",
+            ),
+            ("Log(address payable indexed from)", "Log(address)"),
+        ];
+
+        for (src, expected) in cases {
+            assert_eq!(Signature::parse(src).unwrap().as_str(), expected, "{}", src);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        let cases = [
+            ("", SignatureError::InvalidName),
+            ("(address)", SignatureError::InvalidName),
+            ("1foo()", SignatureError::InvalidName),
+            ("foo", SignatureError::InvalidSyntax),
+            ("foo(", SignatureError::InvalidSyntax),
+            ("foo(address", SignatureError::InvalidSyntax),
+            ("foo(address,)", SignatureError::InvalidType),
+            ("foo(,address)", SignatureError::InvalidType),
+            ("foo(address to uint256)", SignatureError::InvalidSyntax),
+            ("foo(address)bar", SignatureError::InvalidSyntax),
+            ("foo(address to[])", SignatureError::InvalidSyntax),
+            ("foo(())", SignatureError::InvalidType),
+            ("foo(uint7)", SignatureError::InvalidType),
+            ("foo(uint264)", SignatureError::InvalidType),
+            ("foo(uint08)", SignatureError::InvalidType),
+            ("foo(bytes0)", SignatureError::InvalidType),
+            ("foo(bytes33)", SignatureError::InvalidType),
+            ("foo(uint256[0])", SignatureError::InvalidType),
+            ("foo(uint256[x])", SignatureError::InvalidType),
+            ("foo(uint256[)", SignatureError::InvalidSyntax),
+            ("foo(address256)", SignatureError::InvalidType),
+        ];
+
+        for (src, expected) in cases {
+            assert_eq!(Signature::parse(src), Err(expected), "{}", src);
+        }
+    }
+
+    #[test]
+    fn test_selector() {
+        const APPROVE: Bytes<U4> = crate::selector!("approve(address,uint256)");
+        assert_eq!(APPROVE.into_array(), [0x09, 0x5e, 0xa7, 0xb3]);
+
+        let topic = event_topic("Approval(address,address,uint256)").unwrap();
+        let expected: [u8; 32] =
+            crate::unhex!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+        assert_eq!(topic.into_array(), expected);
+    }
+}
@@ -0,0 +1,359 @@
+//! Quantity hex string serialization / deserialization
+//!
+//! Ethereum JSON RPC distinguishes two hex encodings: unformatted DATA, which
+//! is the zero padded byte representation served by `as_hex`, and QUANTITY
+//! used for integers (block numbers, nonces, gas, balances), which must be
+//! the most compact `0x` prefixed representation without leading zeroes:
+//! <https://ethereum.org/en/developers/docs/apis/json-rpc/#hex-encoding>
+//!
+//! The module contains `serialize` and `deserialize` functions to be used
+//! with `serde` macros for any type implementing `Quantity`, which includes
+//! primitive unsigned integers, `uint256` and `Bytes<N>`.
+//! Types which only implement `Into<Bytes<N>>` and `From<Bytes<N>>` can use
+//! the `as_quantity::bytes` module instead.
+//!
+//! Deserialization is strict and rejects non-canonical input such as an
+//! empty `0x`, leading zeroes (`0x01`), a missing prefix or a value which
+//! doesn't fit into the target type.
+//!
+//! ```rust
+//! use serde::{Serialize, Deserialize};
+//! use ethgen::{uint256, as_quantity};
+//!
+//!
+//!
+//! #[derive(Debug, Serialize, Deserialize, PartialEq)]
+//! struct Tx {
+//!     #[serde(with = "as_quantity")]
+//!     nonce: u64,
+//!     #[serde(with = "as_quantity")]
+//!     value: uint256,
+//! }
+//!
+//!
+//!
+//! let tx = Tx { nonce: 0, value: uint256::from(1024u64) };
+//!
+//! let mut buf = [0u8; 256];
+//! let n = serde_json_core::to_slice(&tx, &mut buf).unwrap();
+//! let json = core::str::from_utf8(&buf[..n]).unwrap();
+//!
+//! assert_eq!(r#"{"nonce":"0x0","value":"0x400"}"#, json);
+//!
+//!
+//! let (result, _): (Tx, usize) = serde_json_core::from_str(json).unwrap();
+//! assert_eq!(tx, result);
+//!
+//!
+//! let json = r#"{"nonce":"0x01","value":"0x400"}"#;
+//! assert!(serde_json_core::from_str::<Tx>(json).is_err());
+//! ```
+
+use super::{hex, Bytes};
+use crate::biguint::uint256;
+use core::fmt;
+use core::marker::PhantomData;
+use generic_array::ArrayLength;
+use serde::de::{Error, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+/// Maximum amount of bytes in a quantity
+const LEN: usize = 32;
+
+/// Integer value which can be encoded as a quantity
+///
+/// The quantity is limited to 256 bits, as this is the largest integer
+/// processed by EVM.
+pub trait Quantity: Sized {
+    /// Big Endian byte representation, zero padded from the higher end
+    fn to_quantity(&self) -> [u8; 32];
+
+    /// Construct from Big Endian byte representation, `None` if the value
+    /// doesn't fit into the type
+    fn from_quantity(bytes: &[u8; 32]) -> Option<Self>;
+}
+
+macro_rules! impl_quantity {
+    ($($t:ty),*) => {$(
+        impl Quantity for $t {
+            fn to_quantity(&self) -> [u8; LEN] {
+                let mut buf = [0u8; LEN];
+                buf[LEN - core::mem::size_of::<$t>()..].copy_from_slice(&self.to_be_bytes());
+                buf
+            }
+
+            fn from_quantity(bytes: &[u8; LEN]) -> Option<Self> {
+                let (hi, lo) = bytes.split_at(LEN - core::mem::size_of::<$t>());
+                match hi.iter().all(|b| *b == 0) {
+                    true => Some(<$t>::from_be_bytes(lo.try_into().ok()?)),
+                    false => None,
+                }
+            }
+        }
+    )*};
+}
+
+impl_quantity!(u8, u16, u32, u64, u128, usize);
+
+impl Quantity for uint256 {
+    fn to_quantity(&self) -> [u8; LEN] {
+        self.to_be_bytes()
+    }
+
+    fn from_quantity(bytes: &[u8; LEN]) -> Option<Self> {
+        Some(Self::from_be_bytes(*bytes))
+    }
+}
+
+/// ## Panics
+///
+/// - if the array is longer than 32 bytes;
+impl<N: ArrayLength> Quantity for Bytes<N> {
+    fn to_quantity(&self) -> [u8; LEN] {
+        assert!(N::USIZE <= LEN, "Quantity can not exceed 32 bytes");
+
+        let mut buf = [0u8; LEN];
+        buf[LEN - N::USIZE..].copy_from_slice(self);
+        buf
+    }
+
+    fn from_quantity(bytes: &[u8; LEN]) -> Option<Self> {
+        assert!(N::USIZE <= LEN, "Quantity can not exceed 32 bytes");
+
+        let (hi, lo) = bytes.split_at(LEN - N::USIZE);
+        match hi.iter().all(|b| *b == 0) {
+            true => Some(Self::from_slice(lo).clone()),
+            false => None,
+        }
+    }
+}
+
+/// Encode into the most compact `0x` prefixed hex string
+///
+/// Returns the buffer and the length of the encoding.
+///
+/// ```rust
+/// use ethgen::{as_quantity, uint256};
+///
+///
+/// let (buf, n) = as_quantity::encode(&1024u64);
+/// assert_eq!(b"0x400", &buf[..n]);
+///
+/// let (buf, n) = as_quantity::encode(&uint256::ZERO);
+/// assert_eq!(b"0x0", &buf[..n]);
+/// ```
+pub fn encode<T: Quantity>(data: &T) -> ([u8; 2 + (LEN << 1)], usize) {
+    let mut nybls = [0u8; LEN << 1];
+    hex::encode(&data.to_quantity(), &mut nybls);
+
+    // Leading zero nibbles are stripped, zero value is encoded as `0x0`
+    let start = nybls
+        .iter()
+        .position(|b| *b != b'0')
+        .unwrap_or(nybls.len() - 1);
+    let n = nybls.len() - start;
+
+    let mut buf = [0u8; 2 + (LEN << 1)];
+    buf[..2].copy_from_slice(b"0x");
+    buf[2..2 + n].copy_from_slice(&nybls[start..]);
+
+    (buf, 2 + n)
+}
+
+/// Decode from the most compact `0x` prefixed hex string
+///
+/// Returns `None` for a non-canonical or overflowing input.
+///
+/// ```rust
+/// use ethgen::as_quantity;
+///
+///
+/// assert_eq!(as_quantity::decode::<u64>("0x400"), Some(1024));
+/// assert_eq!(as_quantity::decode::<u64>("0x0"), Some(0));
+/// assert_eq!(as_quantity::decode::<u64>("0x"), None);
+/// assert_eq!(as_quantity::decode::<u64>("0x0400"), None);
+/// assert_eq!(as_quantity::decode::<u64>("400"), None);
+/// assert_eq!(as_quantity::decode::<u8>("0x100"), None);
+/// ```
+pub fn decode<T: Quantity>(src: &str) -> Option<T> {
+    let src = src.as_bytes().strip_prefix(b"0x")?;
+
+    let canonical = match src {
+        [] => false,
+        [b'0'] => true,
+        [first, ..] => *first != b'0',
+    };
+
+    if !canonical || src.len() > LEN << 1 || !src.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+
+    // Odd amount of nibbles is padded with a leading zero nibble
+    let mut nybls = [b'0'; LEN << 1];
+    nybls[(LEN << 1) - src.len()..].copy_from_slice(src);
+
+    let mut bytes = [0u8; LEN];
+    hex::decode(&nybls, &mut bytes);
+
+    T::from_quantity(&bytes)
+}
+
+/// Serialize an integer into the most compact `0x` prefixed hex string
+pub fn serialize<S, T>(data: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Quantity,
+{
+    let (buf, n) = encode(data);
+
+    // Safe since `encode` can return only valid ascii characters
+    let s = unsafe { core::str::from_utf8_unchecked(&buf[..n]) };
+
+    serializer.serialize_str(s)
+}
+
+/// Deserialize an integer from the most compact `0x` prefixed hex string
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Quantity,
+{
+    struct QuantityVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Quantity> Visitor<'de> for QuantityVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "0x prefixed hex quantity without leading zeroes")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            decode(v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+        }
+    }
+
+    deserializer.deserialize_str(QuantityVisitor(PhantomData))
+}
+
+pub mod bytes {
+    //! Quantity serialization for types convertible to `Bytes<N>`
+    //!
+    //! Works the same way as `as_hex` does, requiring a type to implement
+    //! `From<Bytes<N>>` and `Into<Bytes<N>>` traits, but produces the most
+    //! compact quantity encoding.
+    //!
+    //! ```rust
+    //! use serde::{Serialize, Deserialize};
+    //! use ethgen::{Bytes, typenum::U4, as_quantity};
+    //!
+    //!
+    //!
+    //! #[derive(Debug, PartialEq)]
+    //! struct Foo(u32);
+    //!
+    //! impl From<Bytes<U4>> for Foo {
+    //!     fn from(value: Bytes<U4>) -> Self {
+    //!         Self(u32::from_be_bytes(value.into()))
+    //!     }
+    //! }
+    //!
+    //! impl From<&Foo> for Bytes<U4> {
+    //!     fn from(value: &Foo) -> Self {
+    //!         Self::from_array(value.0.to_be_bytes())
+    //!     }
+    //! }
+    //!
+    //!
+    //!
+    //! #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    //! struct Bar {
+    //!     #[serde(with = "as_quantity::bytes")]
+    //!     foo: Foo
+    //! }
+    //!
+    //!
+    //!
+    //! let bar = Bar{ foo: Foo(262988610u32) };
+    //!
+    //! let mut buf = [0u8; 256];
+    //! let n = serde_json_core::to_slice(&bar, &mut buf).unwrap();
+    //! let json = core::str::from_utf8(&buf[..n]).unwrap();
+    //!
+    //! assert_eq!(r#"{"foo":"0xface342"}"#, json);
+    //!
+    //!
+    //! let (result, _): (Bar, usize) = serde_json_core::from_str(json).unwrap();
+    //! assert_eq!(bar, result);
+    //! ```
+
+    use super::Bytes;
+    use generic_array::ArrayLength;
+    use serde::{Deserializer, Serializer};
+
+    /// Serialize a data struct into the most compact `0x` prefixed hex string
+    pub fn serialize<S, T, N>(data: T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        N: ArrayLength,
+        T: Into<Bytes<N>>,
+    {
+        super::serialize(&data.into(), serializer)
+    }
+
+    /// Deserialize a data struct from the most compact `0x` prefixed hex string
+    pub fn deserialize<'de, D, T, N>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        N: ArrayLength,
+        T: From<Bytes<N>>,
+    {
+        super::deserialize::<D, Bytes<N>>(deserializer).map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let cases: [(u64, &[u8]); 5] = [
+            (0, b"0x0"),
+            (1, b"0x1"),
+            (0x41, b"0x41"),
+            (0x400, b"0x400"),
+            (u64::MAX, b"0xffffffffffffffff"),
+        ];
+
+        for (value, expected) in cases {
+            let (buf, n) = encode(&value);
+            assert_eq!(&buf[..n], expected);
+        }
+
+        let (buf, n) = encode(&uint256::MAX);
+        assert_eq!(n, 66);
+        assert!(buf[2..n].iter().all(|b| *b == b'f'));
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode::<u64>("0x41"), Some(0x41));
+        assert_eq!(decode::<u64>("0xFACE"), Some(0xface));
+        assert_eq!(decode::<u64>("0x00"), None);
+        assert_eq!(decode::<u64>("0xg"), None);
+        assert_eq!(decode::<u64>("0x10000000000000000"), None);
+        assert_eq!(decode::<u64>(""), None);
+
+        let max = "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff";
+        assert_eq!(decode::<uint256>(max), Some(uint256::MAX));
+        assert_eq!(
+            decode::<uint256>(
+                "0x1ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+            ),
+            None
+        );
+    }
+}
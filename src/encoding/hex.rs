@@ -11,6 +11,8 @@ const fn nybl_lower(b: u8) -> u8 {
     (b + 0x30 + (((0x9 - b) >> 8) & (0x61i16 - 0x3a))) as u8
 }
 
+// Only used by the fallback of `encode_upper`
+#[cfg(not(feature = "faster-hex"))]
 #[inline(always)]
 const fn nybl_upper(b: u8) -> u8 {
     let b = b as i16;
@@ -135,7 +137,7 @@ impl_encode! {
 /// ```
 #[inline(always)]
 pub const fn const_encode<const U: usize>(src: &[u8]) -> [u8; U] {
-    assert!(U % 2 == 0, "Unable to encode to odd number of hex nibbles");
+    assert!(U & 1 == 0, "Unable to encode to odd number of hex nibbles");
     assert!(src.len() << 1 <= U, "Incufficient capacity to hex encode");
 
     let pad = U - (src.len() << 1);
@@ -187,7 +189,7 @@ const fn decode_nybl(b: u8) -> u8 {
 /// assert_eq!(b"\0\0ethgen", &buf);
 /// ```
 pub fn decode(src: &[u8], dst: &mut [u8]) -> usize {
-    assert!(src.len() % 2 == 0, "Invalid odd length of hex input");
+    assert!(src.len() & 1 == 0, "Invalid odd length of hex input");
 
     let pad = match dst.len().checked_sub(src.len() >> 1) {
         Some(pad) => pad,
@@ -203,7 +205,7 @@ pub fn decode(src: &[u8], dst: &mut [u8]) -> usize {
         faster_hex::hex_decode_unchecked(src, &mut dst[pad..]);
     }
 
-    #[cfg(not(feautre = "faster-hex"))]
+    #[cfg(not(feature = "faster-hex"))]
     {
         src.chunks(2)
             .zip(dst.iter_mut().skip(pad))
@@ -246,7 +248,7 @@ pub fn decode(src: &[u8], dst: &mut [u8]) -> usize {
 /// ```
 #[inline(always)]
 pub const fn const_decode<const V: usize>(src: &[u8]) -> [u8; V] {
-    assert!(src.len() % 2 == 0, "Odd number of hex characters");
+    assert!(src.len() & 1 == 0, "Odd number of hex characters");
     assert!(src.len() <= V << 1, "Incufficient capacity for hex decode");
 
     let pad = V - (src.len() >> 1);
@@ -256,7 +258,7 @@ pub const fn const_decode<const V: usize>(src: &[u8]) -> [u8; V] {
         let nyb = decode_nybl(src[i]);
         assert!(nyb < 16, "Invalid hex character");
 
-        dst[pad + (i >> 1)] |= nyb << (1 - (i % 2)) * 4;
+        dst[pad + (i >> 1)] |= nyb << ((1 - (i % 2)) * 4);
         i += 1;
     }
 
@@ -0,0 +1,146 @@
+use core::{fmt, marker::PhantomData, ops::Deref, slice};
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+/// Fixed capacity list deserialized from a JSON array
+///
+/// Holds up to `N` items, a longer array fails to deserialize.
+///
+/// ```rust
+/// use ethgen::{block::TxHash, ListBuf};
+///
+///
+/// let json = r#"[
+///     "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060",
+///     "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b"
+/// ]"#;
+///
+/// let (hashes, _): (ListBuf<TxHash, 4>, usize) = serde_json_core::from_str(json).unwrap();
+/// assert_eq!(hashes.len(), 2);
+///
+/// assert!(serde_json_core::from_str::<ListBuf<TxHash, 1>>(json).is_err());
+/// ```
+#[derive(Clone, Copy)]
+pub struct ListBuf<T, const N: usize> {
+    items: [T; N],
+    len: usize,
+}
+
+impl<T: Copy + Default, const N: usize> ListBuf<T, N> {
+    pub fn new() -> Self {
+        Self {
+            items: [T::default(); N],
+            len: 0,
+        }
+    }
+
+    /// Copy the items, `None` if they exceed the capacity
+    pub fn from_slice(src: &[T]) -> Option<Self> {
+        let mut list = Self::new();
+        list.items.get_mut(..src.len())?.copy_from_slice(src);
+        list.len = src.len();
+        Some(list)
+    }
+}
+
+impl<T, const N: usize> ListBuf<T, N> {
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[T] {
+        &self.items[..self.len]
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Append an item, returning it back if the list is full
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        match self.items.get_mut(self.len) {
+            Some(slot) => {
+                *slot = item;
+                self.len += 1;
+                Ok(())
+            }
+            None => Err(item),
+        }
+    }
+}
+
+impl<T: Copy + Default, const N: usize> Default for ListBuf<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for ListBuf<T, N> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ListBuf<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for ListBuf<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ListBuf<T, N> {}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ListBuf<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<'de, T, const N: usize> Deserialize<'de> for ListBuf<T, N>
+where
+    T: Deserialize<'de> + Copy + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ListVisitor<T, const N: usize>(PhantomData<T>);
+
+        impl<'de, T, const N: usize> Visitor<'de> for ListVisitor<T, N>
+        where
+            T: Deserialize<'de> + Copy + Default,
+        {
+            type Value = ListBuf<T, N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "array of at most {} items", N)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut list = ListBuf::new();
+
+                while let Some(item) = seq.next_element()? {
+                    if list.push(item).is_err() {
+                        return Err(de::Error::invalid_length(N + 1, &self));
+                    }
+                }
+
+                Ok(list)
+            }
+        }
+
+        deserializer.deserialize_seq(ListVisitor(PhantomData))
+    }
+}
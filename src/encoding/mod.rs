@@ -1,7 +1,18 @@
+use core::{fmt, marker::PhantomData};
 use generic_array::typenum::*;
 use generic_array::{sequence::GenericSequence, ArrayLength, GenericArray};
+use serde::{
+    de::{self, Unexpected, Visitor},
+    Deserialize, Deserializer,
+};
 
+pub mod abi;
+pub mod as_quantity;
 pub mod hex;
+pub mod rlp;
+
+mod list_buf;
+pub use list_buf::ListBuf;
 
 /// Stack-allocated byte array with a constant length
 ///
@@ -96,12 +107,9 @@ where
     // The padding is applied to the source string to insure that the allocated
     // byte buffer will fit the decoding product. In case if the source require
     // larger buffer, the overflowing nybls are stripped (form the lower end).
-    let pad = match src.len().checked_sub(1 + (N::USIZE << 1)) {
-        Some(pad) => pad,
-        // No padding required, the whole string when decoded will fit
-        // into the byte representation of the data structure.
-        None => 0,
-    };
+    // No padding required if the whole string when decoded will fit into the
+    // byte representation of the data structure.
+    let pad = src.len().saturating_sub(1 + (N::USIZE << 1));
 
     let mut bytes = Bytes::<N>::default();
     hex::decode(&src[pad..], &mut bytes);
@@ -109,6 +117,62 @@ where
     bytes.into()
 }
 
+/// Decode `0x` prefixed DATA of at most `dst.len()` bytes into the beginning
+/// of the buffer, returns the amount of decoded bytes
+///
+/// Unlike `from_hex` the source is validated, `None` is returned for a missing
+/// prefix, an odd length or a non-hex digit.
+pub(crate) fn decode_data(src: &str, dst: &mut [u8]) -> Option<usize> {
+    let src = src.as_bytes().strip_prefix(b"0x")?;
+    let n = src.len() >> 1;
+
+    if src.len() & 1 != 0 || n > dst.len() || !src.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+
+    hex::decode(src, &mut dst[..n]);
+    Some(n)
+}
+
+/// Fixed length DATA, `0x` followed by exactly `2 * N` hex digits
+pub(crate) struct Data<N: ArrayLength>(pub(crate) Bytes<N>);
+
+impl<'de, N: ArrayLength> Deserialize<'de> for Data<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DataVisitor<N>(PhantomData<N>);
+
+        impl<N: ArrayLength> Visitor<'_> for DataVisitor<N> {
+            type Value = Data<N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "0x prefixed hex string of {} bytes", N::USIZE)
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                let mut bytes = Bytes::<N>::default();
+
+                match decode_data(v, &mut bytes) {
+                    Some(n) if n == N::USIZE => Ok(Data(bytes)),
+                    _ => Err(E::invalid_value(Unexpected::Str(v), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(DataVisitor(PhantomData))
+    }
+}
+
+/// Deserialize a fixed length DATA field strictly, the counterpart of
+/// `as_hex::deserialize` for the untrusted input
+pub(crate) fn data<'de, D, T, N>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    N: ArrayLength,
+    T: From<Bytes<N>>,
+{
+    Data::deserialize(deserializer).map(|data| data.0.into())
+}
+
 pub mod as_hex {
     //! Hex string serialization / deserialization
     //!
@@ -297,7 +361,7 @@ pub mod as_hex {
             where
                 E: Error,
             {
-                Ok(from_hex(&v))
+                Ok(from_hex(v))
             }
         }
 
@@ -0,0 +1,826 @@
+//! Recursive Length Prefix (RLP) encoding
+//!
+//! The serialization format used by the execution layer for transactions,
+//! block headers and receipts:
+//! <https://ethereum.org/en/developers/docs/data-structures-and-encoding/rlp>
+//!
+//! Everything is written into caller-provided buffers, no allocation happens
+//! during encoding or decoding. The exact length of the encoding product is
+//! available in advance via [`Encodable::length`], so the buffer can be sized
+//! exactly. Decoding is strict and rejects every non-canonical encoding, so
+//! that any byte string has at most one decoded representation.
+//!
+//! Lists of a homogeneous items are encoded from slices with [`encode_list`]
+//! and decoded with [`List`], which borrows the source buffer. Tuples
+//! are encoded as lists of heterogeneous items, structs can derive both
+//! traits with `#[derive(Rlp)]` when the `derive` feature is enabled.
+//!
+//! ```rust
+//! use ethgen::{rlp::{self, Encodable}, uint256};
+//!
+//!
+//!
+//! let tx = (9u64, uint256!("20000000000"), &b"dog"[..]);
+//!
+//! let mut buf = [0u8; 64];
+//! let n = tx.encode(&mut buf);
+//! assert_eq!(n, tx.length());
+//!
+//! let (nonce, price, data): (u64, ethgen::uint256, &[u8]) =
+//!     rlp::decode(&buf[..n]).unwrap();
+//!
+//! assert_eq!(nonce, 9);
+//! assert_eq!(price, uint256!("20000000000"));
+//! assert_eq!(data, b"dog");
+//! ```
+
+use crate::{uint256, Address, Bytes};
+use core::fmt;
+use generic_array::ArrayLength;
+
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use ethgen_derive::Rlp;
+
+/// Offset of the string header
+const STRING: u8 = 0x80;
+
+/// Offset of the list header
+const LIST: u8 = 0xc0;
+
+/// Max payload length which fits in the short form header
+const SHORT: usize = 55;
+
+/// Error decoding RLP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The input ended before the item was complete
+    UnexpectedEnd,
+    /// Expected a string, but found a list
+    UnexpectedList,
+    /// Expected a list, but found a string
+    UnexpectedString,
+    /// Item is not encoded in its shortest form
+    NonCanonical,
+    /// The decoded value does not fit the target type
+    Overflow,
+    /// Fixed size string has a different length
+    InvalidLength,
+    /// The list payload or the input has bytes left after decoding
+    LengthMismatch,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnexpectedEnd => "unexpected end of RLP input",
+            Self::UnexpectedList => "unexpected RLP list",
+            Self::UnexpectedString => "unexpected RLP string",
+            Self::NonCanonical => "non-canonical RLP encoding",
+            Self::Overflow => "RLP value overflow",
+            Self::InvalidLength => "invalid RLP string length",
+            Self::LengthMismatch => "RLP length mismatch",
+        })
+    }
+}
+
+/// Header of an RLP item
+///
+/// A single byte in range `[0x00, 0x7f]` is its own encoding and has no
+/// header, such item is decoded as a string header with payload length 1,
+/// leaving the input intact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub list: bool,
+    pub payload_length: usize,
+}
+
+impl Header {
+    #[inline(always)]
+    pub const fn string(payload_length: usize) -> Self {
+        Self {
+            list: false,
+            payload_length,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn list(payload_length: usize) -> Self {
+        Self {
+            list: true,
+            payload_length,
+        }
+    }
+
+    /// Length of the encoded header
+    #[inline(always)]
+    pub const fn length(&self) -> usize {
+        match self.payload_length <= SHORT {
+            true => 1,
+            false => 1 + be_length(self.payload_length),
+        }
+    }
+
+    /// Length of the encoded header followed by the payload
+    #[inline(always)]
+    pub const fn length_with_payload(&self) -> usize {
+        self.length() + self.payload_length
+    }
+
+    /// Encode the header into the beginning of the buffer
+    ///
+    /// Returns the amount of bytes written.
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the header;
+    pub fn encode(&self, buf: &mut [u8]) -> usize {
+        let offset = match self.list {
+            true => LIST,
+            false => STRING,
+        };
+
+        if self.payload_length <= SHORT {
+            buf[0] = offset + self.payload_length as u8;
+            return 1;
+        }
+
+        let n = be_length(self.payload_length);
+        let be = self.payload_length.to_be_bytes();
+
+        buf[0] = offset + SHORT as u8 + n as u8;
+        buf[1..=n].copy_from_slice(&be[be.len() - n..]);
+
+        1 + n
+    }
+
+    /// Decode the header advancing the input
+    ///
+    /// Checks that the input has enough bytes to contain the payload.
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, Error> {
+        let b = *buf.first().ok_or(Error::UnexpectedEnd)?;
+
+        let header = match b {
+            0x00..=0x7f => return Ok(Self::string(1)),
+            0x80..=0xb7 => {
+                *buf = &buf[1..];
+                let header = Self::string((b - STRING) as usize);
+
+                // A single byte below `0x80` must be encoded as itself
+                if header.payload_length == 1 && *buf.first().ok_or(Error::UnexpectedEnd)? < STRING
+                {
+                    return Err(Error::NonCanonical);
+                }
+
+                header
+            }
+            0xb8..=0xbf => Self::string(decode_long_length(buf, b - STRING - SHORT as u8)?),
+            0xc0..=0xf7 => {
+                *buf = &buf[1..];
+                Self::list((b - LIST) as usize)
+            }
+            0xf8..=0xff => Self::list(decode_long_length(buf, b - LIST - SHORT as u8)?),
+        };
+
+        match buf.len() < header.payload_length {
+            true => Err(Error::UnexpectedEnd),
+            false => Ok(header),
+        }
+    }
+}
+
+/// Amount of bytes in the Big Endian representation without leading zeroes
+#[inline(always)]
+const fn be_length(value: usize) -> usize {
+    ((usize::BITS - value.leading_zeros() + 7) >> 3) as usize
+}
+
+/// Decode the length of the long form header
+fn decode_long_length(buf: &mut &[u8], n: u8) -> Result<usize, Error> {
+    let n = n as usize;
+    let src = buf.get(1..=n).ok_or(Error::UnexpectedEnd)?;
+
+    if src[0] == 0 {
+        return Err(Error::NonCanonical);
+    }
+
+    if n > core::mem::size_of::<usize>() {
+        return Err(Error::Overflow);
+    }
+
+    let len = src.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+
+    if len <= SHORT {
+        return Err(Error::NonCanonical);
+    }
+
+    *buf = &buf[1 + n..];
+    Ok(len)
+}
+
+/// Take the payload of the given length advancing the input
+#[inline(always)]
+fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+    if buf.len() < len {
+        return Err(Error::UnexpectedEnd);
+    }
+
+    let (payload, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(payload)
+}
+
+/// Data structure which can be RLP encoded
+pub trait Encodable {
+    /// Exact length of the encoding product
+    fn length(&self) -> usize;
+
+    /// Encode into the beginning of the buffer
+    ///
+    /// Returns the amount of bytes written, which is always equal to
+    /// [`Encodable::length`].
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the encoding product;
+    fn encode(&self, buf: &mut [u8]) -> usize;
+}
+
+/// Data structure which can be decoded from RLP
+///
+/// The lifetime allows to borrow the decoded data from the input.
+pub trait Decodable<'a>: Sized {
+    /// Decode from the beginning of the input advancing it
+    fn decode(buf: &mut &'a [u8]) -> Result<Self, Error>;
+}
+
+/// Decode a data structure, which takes the entire input
+pub fn decode<'a, T: Decodable<'a>>(mut src: &'a [u8]) -> Result<T, Error> {
+    let value = T::decode(&mut src)?;
+
+    match src.is_empty() {
+        true => Ok(value),
+        false => Err(Error::LengthMismatch),
+    }
+}
+
+/// Decode a string payload advancing the input
+pub fn decode_string<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8], Error> {
+    let header = Header::decode(buf)?;
+
+    match header.list {
+        true => Err(Error::UnexpectedList),
+        false => take(buf, header.payload_length),
+    }
+}
+
+/// Decode a list payload advancing the input
+pub fn decode_list<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8], Error> {
+    let header = Header::decode(buf)?;
+
+    match header.list {
+        true => take(buf, header.payload_length),
+        false => Err(Error::UnexpectedString),
+    }
+}
+
+/// Exact length of the byte string encoding
+#[inline(always)]
+pub const fn string_length(src: &[u8]) -> usize {
+    match src.len() == 1 && src[0] < STRING {
+        true => 1,
+        false => Header::string(src.len()).length_with_payload(),
+    }
+}
+
+/// Encode a byte string into the beginning of the buffer
+///
+/// ## Panics
+///
+/// - if buffer capacity is incufficient to contain the encoding product;
+pub fn encode_string(src: &[u8], buf: &mut [u8]) -> usize {
+    if src.len() == 1 && src[0] < STRING {
+        buf[0] = src[0];
+        return 1;
+    }
+
+    let n = Header::string(src.len()).encode(buf);
+    buf[n..n + src.len()].copy_from_slice(src);
+
+    n + src.len()
+}
+
+/// Exact length of the list encoding
+pub fn list_length<T: Encodable>(items: &[T]) -> usize {
+    Header::list(items.iter().map(Encodable::length).sum()).length_with_payload()
+}
+
+/// Encode a list of items into the beginning of the buffer
+///
+/// ```rust
+/// use ethgen::rlp;
+///
+///
+///
+/// let mut buf = [0u8; 9];
+/// let n = rlp::encode_list(&[&b"cat"[..], &b"dog"[..]], &mut buf);
+///
+/// assert_eq!(b"\xc8\x83cat\x83dog", &buf[..n]);
+/// ```
+///
+/// ## Panics
+///
+/// - if buffer capacity is incufficient to contain the encoding product;
+pub fn encode_list<T: Encodable>(items: &[T], buf: &mut [u8]) -> usize {
+    let payload_length = items.iter().map(Encodable::length).sum();
+    let mut n = Header::list(payload_length).encode(buf);
+
+    for item in items {
+        n += item.encode(&mut buf[n..]);
+    }
+
+    n
+}
+
+/// List of a homogeneous items
+///
+/// Either borrows a slice of items or the payload of a decoded list, which
+/// allows to decode lists of arbitrary length without allocation. The payload
+/// is validated on decoding and the items are decoded again on iteration, so
+/// that the iteration is infallible.
+///
+/// ```rust
+/// use ethgen::rlp::{self, Encodable, List};
+///
+///
+///
+/// let list: List<&[u8]> = rlp::decode(b"\xc8\x83cat\x83dog").unwrap();
+/// let mut items = list.into_iter();
+///
+/// assert_eq!(Some(&b"cat"[..]), items.next());
+/// assert_eq!(Some(&b"dog"[..]), items.next());
+/// assert_eq!(None, items.next());
+///
+/// assert_eq!(list, List::new(&[&b"cat"[..], &b"dog"[..]]));
+///
+/// let mut buf = [0u8; 9];
+/// let n = List::new(&[1u64, 1024]).encode(&mut buf);
+/// assert_eq!(b"\xc4\x01\x82\x04\x00", &buf[..n]);
+///
+/// assert_eq!(rlp::decode::<List<u8>>(&buf[..n]), Err(rlp::Error::Overflow));
+/// ```
+pub struct List<'a, T>(Items<'a, T>);
+
+enum Items<'a, T> {
+    Slice(&'a [T]),
+    Rlp(&'a [u8]),
+}
+
+impl<'a, T> List<'a, T> {
+    pub const EMPTY: Self = Self::new(&[]);
+
+    #[inline(always)]
+    pub const fn new(items: &'a [T]) -> Self {
+        Self(Items::Slice(items))
+    }
+
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        match self.0 {
+            Items::Slice(items) => items.is_empty(),
+            Items::Rlp(payload) => payload.is_empty(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn iter(&self) -> ListIter<'a, T> {
+        ListIter(match self.0 {
+            Items::Slice(items) => Items::Slice(items),
+            Items::Rlp(payload) => Items::Rlp(payload),
+        })
+    }
+}
+
+impl<'a, T: Copy + Decodable<'a>> List<'a, T> {
+    pub fn len(&self) -> usize {
+        match self.0 {
+            Items::Slice(items) => items.len(),
+            Items::Rlp(_) => self.iter().count(),
+        }
+    }
+}
+
+impl<T: Encodable> List<'_, T> {
+    fn payload_length(&self) -> usize {
+        match self.0 {
+            Items::Slice(items) => items.iter().map(Encodable::length).sum(),
+            Items::Rlp(payload) => payload.len(),
+        }
+    }
+}
+
+impl<T> Clone for List<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for List<'_, T> {}
+
+impl<T> Clone for Items<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Items<'_, T> {}
+
+impl<T> Default for List<'_, T> {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<'a, T> From<&'a [T]> for List<'a, T> {
+    fn from(items: &'a [T]) -> Self {
+        Self::new(items)
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a [T; N]> for List<'a, T> {
+    fn from(items: &'a [T; N]) -> Self {
+        Self::new(items)
+    }
+}
+
+impl<'a, T: Copy + Decodable<'a>> IntoIterator for List<'a, T> {
+    type Item = T;
+    type IntoIter = ListIter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Copy + Decodable<'a> + PartialEq> PartialEq for List<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, T: Copy + Decodable<'a> + Eq> Eq for List<'a, T> {}
+
+impl<'a, T: Copy + Decodable<'a> + fmt::Debug> fmt::Debug for List<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: Decodable<'a>> Decodable<'a> for List<'a, T> {
+    fn decode(buf: &mut &'a [u8]) -> Result<Self, Error> {
+        let payload = decode_list(buf)?;
+
+        // Validate items upfront, so that iteration is infallible
+        let mut rest = payload;
+        while !rest.is_empty() {
+            T::decode(&mut rest)?;
+        }
+
+        Ok(Self(Items::Rlp(payload)))
+    }
+}
+
+impl<T: Encodable> Encodable for List<'_, T> {
+    fn length(&self) -> usize {
+        Header::list(self.payload_length()).length_with_payload()
+    }
+
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        match self.0 {
+            Items::Slice(items) => encode_list(items, buf),
+            Items::Rlp(payload) => {
+                let n = Header::list(payload.len()).encode(buf);
+                buf[n..n + payload.len()].copy_from_slice(payload);
+                n + payload.len()
+            }
+        }
+    }
+}
+
+/// Iterator over the list items
+pub struct ListIter<'a, T>(Items<'a, T>);
+
+impl<T> Clone for ListIter<'_, T> {
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+impl<'a, T: Copy + Decodable<'a>> Iterator for ListIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            Items::Slice(items) => {
+                let (item, rest) = items.split_first()?;
+                *items = rest;
+                Some(*item)
+            }
+            // The payload is validated on decoding
+            Items::Rlp(payload) => match payload.is_empty() {
+                true => None,
+                false => T::decode(payload).ok(),
+            },
+        }
+    }
+}
+
+impl<T: Encodable + ?Sized> Encodable for &T {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        (**self).encode(buf)
+    }
+}
+
+impl Encodable for [u8] {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        string_length(self)
+    }
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        encode_string(self, buf)
+    }
+}
+
+impl<'a> Decodable<'a> for &'a [u8] {
+    #[inline(always)]
+    fn decode(buf: &mut &'a [u8]) -> Result<Self, Error> {
+        decode_string(buf)
+    }
+}
+
+impl<const N: usize> Encodable for [u8; N] {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        string_length(self)
+    }
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        encode_string(self, buf)
+    }
+}
+
+impl<const N: usize> Decodable<'_> for [u8; N] {
+    fn decode(buf: &mut &[u8]) -> Result<Self, Error> {
+        decode_string(buf)?
+            .try_into()
+            .map_err(|_| Error::InvalidLength)
+    }
+}
+
+impl<N: ArrayLength> Encodable for Bytes<N> {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        string_length(self)
+    }
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        encode_string(self, buf)
+    }
+}
+
+impl<N: ArrayLength> Decodable<'_> for Bytes<N> {
+    fn decode(buf: &mut &[u8]) -> Result<Self, Error> {
+        Bytes::try_from_slice(decode_string(buf)?)
+            .cloned()
+            .map_err(|_| Error::InvalidLength)
+    }
+}
+
+impl Encodable for Address {
+    #[inline(always)]
+    fn length(&self) -> usize {
+        string_length(self.as_slice())
+    }
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        encode_string(self.as_slice(), buf)
+    }
+}
+
+impl Decodable<'_> for Address {
+    #[inline(always)]
+    fn decode(buf: &mut &[u8]) -> Result<Self, Error> {
+        <[u8; 20]>::decode(buf).map(Address::new)
+    }
+}
+
+/// Strip leading zeroes of the Big Endian integer representation
+#[inline(always)]
+pub(crate) fn trim(be: &[u8]) -> &[u8] {
+    let zeroes = be.iter().take_while(|b| **b == 0).count();
+    &be[zeroes..]
+}
+
+/// Decode a Big Endian integer representation into fixed size buffer
+fn decode_integer<const N: usize>(buf: &mut &[u8]) -> Result<[u8; N], Error> {
+    let src = decode_string(buf)?;
+
+    if src.first() == Some(&0) {
+        return Err(Error::NonCanonical);
+    }
+
+    if src.len() > N {
+        return Err(Error::Overflow);
+    }
+
+    let mut be = [0u8; N];
+    be[N - src.len()..].copy_from_slice(src);
+    Ok(be)
+}
+
+macro_rules! impl_integer {
+    ($($ty:ty),+) => {$(
+        impl Encodable for $ty {
+            #[inline(always)]
+            fn length(&self) -> usize {
+                string_length(trim(&self.to_be_bytes()))
+            }
+
+            #[inline(always)]
+            fn encode(&self, buf: &mut [u8]) -> usize {
+                encode_string(trim(&self.to_be_bytes()), buf)
+            }
+        }
+
+        impl Decodable<'_> for $ty {
+            #[inline(always)]
+            fn decode(buf: &mut &[u8]) -> Result<Self, Error> {
+                decode_integer(buf).map(<$ty>::from_be_bytes)
+            }
+        }
+    )+};
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, uint256);
+
+macro_rules! impl_tuple {
+    ($($name:ident $idx:tt),+) => {
+        impl<$($name: Encodable),+> Encodable for ($($name,)+) {
+            fn length(&self) -> usize {
+                Header::list(0 $(+ self.$idx.length())+).length_with_payload()
+            }
+
+            fn encode(&self, buf: &mut [u8]) -> usize {
+                let mut n = Header::list(0 $(+ self.$idx.length())+).encode(buf);
+                $(n += self.$idx.encode(&mut buf[n..]);)+
+                n
+            }
+        }
+
+        impl<'a, $($name: Decodable<'a>),+> Decodable<'a> for ($($name,)+) {
+            fn decode(buf: &mut &'a [u8]) -> Result<Self, Error> {
+                let mut payload = decode_list(buf)?;
+                let value = ($($name::decode(&mut payload)?,)+);
+
+                match payload.is_empty() {
+                    true => Ok(value),
+                    false => Err(Error::LengthMismatch),
+                }
+            }
+        }
+    };
+}
+
+impl_tuple!(A 0);
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check<'a, T>(value: T, expected: &'a [u8])
+    where
+        T: Encodable + Decodable<'a> + PartialEq + fmt::Debug,
+    {
+        let mut buf = [0u8; 128];
+        let n = value.encode(&mut buf);
+
+        assert_eq!(n, value.length());
+        assert_eq!(&buf[..n], expected);
+        assert_eq!(decode::<T>(expected), Ok(value));
+    }
+
+    #[test]
+    fn test_strings() {
+        check(&b""[..], b"\x80");
+        check(&b"\x00"[..], b"\x00");
+        check(&b"\x7f"[..], b"\x7f");
+        check(&b"\x80"[..], b"\x81\x80");
+        check(&b"dog"[..], b"\x83dog");
+        check(*b"dog", b"\x83dog");
+        check(Bytes::from_array(*b"dog"), b"\x83dog");
+
+        let lorem = b"Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+        let mut expected = [0u8; 58];
+        expected[..2].copy_from_slice(b"\xb8\x38");
+        expected[2..].copy_from_slice(lorem);
+        check(&lorem[..], &expected);
+    }
+
+    #[test]
+    fn test_integers() {
+        check(0u64, b"\x80");
+        check(0u8, b"\x80");
+        check(15u8, b"\x0f");
+        check(0x80u16, b"\x81\x80");
+        check(1024u32, b"\x82\x04\x00");
+        check(u64::MAX, b"\x88\xff\xff\xff\xff\xff\xff\xff\xff");
+        check(uint256::ZERO, b"\x80");
+        check(
+            uint256!("0x0100020003000400050006000700080009"),
+            b"\x91\x01\x00\x02\x00\x03\x00\x04\x00\x05\x00\x06\x00\x07\x00\x08\x00\x09",
+        );
+
+        let mut max = [0xffu8; 33];
+        max[0] = 0xa0;
+        check(uint256::MAX, &max);
+    }
+
+    #[test]
+    fn test_lists() {
+        check((&b"cat"[..], &b"dog"[..]), b"\xc8\x83cat\x83dog");
+        check((0u8, (1u8, 2u8)), b"\xc4\x80\xc2\x01\x02");
+
+        let mut buf = [0u8; 16];
+        let n = encode_list::<u8>(&[], &mut buf);
+        assert_eq!(&buf[..n], b"\xc0");
+
+        let items = [1u16, 2, 1024];
+        let n = encode_list(&items, &mut buf);
+        assert_eq!(n, list_length(&items));
+        assert_eq!(&buf[..n], b"\xc5\x01\x02\x82\x04\x00");
+
+        let list: List<u16> = decode(&buf[..n]).unwrap();
+        assert_eq!(list, List::new(&items));
+        assert_eq!(list.len(), 3);
+
+        let mut out = [0u8; 16];
+        assert_eq!(list.encode(&mut out), n);
+        assert_eq!(&out[..n], &buf[..n]);
+    }
+
+    #[test]
+    fn test_non_canonical() {
+        // Single byte below 0x80 with a header
+        assert_eq!(decode::<&[u8]>(b"\x81\x00"), Err(Error::NonCanonical));
+        // Long form for a short payload
+        assert_eq!(decode::<&[u8]>(b"\xb8\x03dog"), Err(Error::NonCanonical));
+        // Long form length with leading zero
+        assert_eq!(decode::<&[u8]>(b"\xb9\x00\x38"), Err(Error::NonCanonical));
+        // Integers with leading zeroes
+        assert_eq!(decode::<u64>(b"\x00"), Err(Error::NonCanonical));
+        assert_eq!(decode::<u64>(b"\x82\x00\x01"), Err(Error::NonCanonical));
+        assert_eq!(decode::<uint256>(b"\x81\x00"), Err(Error::NonCanonical));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(decode::<&[u8]>(b""), Err(Error::UnexpectedEnd));
+        assert_eq!(decode::<&[u8]>(b"\x83do"), Err(Error::UnexpectedEnd));
+        assert_eq!(decode::<&[u8]>(b"\xb8"), Err(Error::UnexpectedEnd));
+        assert_eq!(decode::<&[u8]>(b"\xc0"), Err(Error::UnexpectedList));
+        assert_eq!(decode::<(u8,)>(b"\x80"), Err(Error::UnexpectedString));
+        assert_eq!(decode::<u8>(b"\x82\x01\x00"), Err(Error::Overflow));
+        assert_eq!(decode::<[u8; 2]>(b"\x83dog"), Err(Error::InvalidLength));
+        assert_eq!(decode::<Address>(b"\x83dog"), Err(Error::InvalidLength));
+        assert_eq!(decode::<u8>(b"\x01\x02"), Err(Error::LengthMismatch));
+        assert_eq!(decode::<(u8,)>(b"\xc2\x01\x02"), Err(Error::LengthMismatch));
+
+        assert_eq!(
+            decode::<List<u8>>(b"\xc4\x01\x82\x01\x00"),
+            Err(Error::Overflow)
+        );
+    }
+}
@@ -0,0 +1,317 @@
+//! Keccak-256
+//!
+//! The original Keccak submission (with `0x01` padding) as used by Ethereum,
+//! which differs from the standardized SHA3-256 only by the padding byte:
+//! <https://keccak.team/keccak_specs_summary.html>
+//!
+//! Keccak-256 is used everywhere across Ethereum: address derivation, EIP-55
+//! checksums, function selectors, event topics and transaction hashes.
+
+use crate::{typenum::U32, Bytes};
+
+/// Rate of Keccak-256 sponge in bytes
+const RATE: usize = 136;
+
+const RC: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+const ROTC: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+const PILN: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// Keccak-f\[1600\] permutation
+const fn keccak_f(mut st: [u64; 25]) -> [u64; 25] {
+    let mut round = 0;
+
+    while round < 24 {
+        // Theta
+        let mut bc = [0u64; 5];
+        let mut i = 0;
+        while i < 5 {
+            bc[i] = st[i] ^ st[i + 5] ^ st[i + 10] ^ st[i + 15] ^ st[i + 20];
+            i += 1;
+        }
+
+        i = 0;
+        while i < 5 {
+            let t = bc[(i + 4) % 5] ^ bc[(i + 1) % 5].rotate_left(1);
+            let mut j = 0;
+            while j < 25 {
+                st[j + i] ^= t;
+                j += 5;
+            }
+            i += 1;
+        }
+
+        // Rho and Pi
+        let mut t = st[1];
+        i = 0;
+        while i < 24 {
+            let j = PILN[i];
+            let tmp = st[j];
+            st[j] = t.rotate_left(ROTC[i]);
+            t = tmp;
+            i += 1;
+        }
+
+        // Chi
+        let mut j = 0;
+        while j < 25 {
+            i = 0;
+            while i < 5 {
+                bc[i] = st[j + i];
+                i += 1;
+            }
+
+            i = 0;
+            while i < 5 {
+                st[j + i] ^= !bc[(i + 1) % 5] & bc[(i + 2) % 5];
+                i += 1;
+            }
+            j += 5;
+        }
+
+        // Iota
+        st[0] ^= RC[round];
+        round += 1;
+    }
+
+    st
+}
+
+/// Absorb the data into the sponge state at the given position
+#[inline(always)]
+const fn absorb(mut st: [u64; 25], mut pos: usize, src: &[u8]) -> ([u64; 25], usize) {
+    let mut i = 0usize;
+
+    while i < src.len() {
+        st[pos >> 3] ^= (src[i] as u64) << ((pos & 7) << 3);
+        pos += 1;
+        i += 1;
+
+        if pos == RATE {
+            st = keccak_f(st);
+            pos = 0;
+        }
+    }
+
+    (st, pos)
+}
+
+/// Pad the last block and squeeze the digest out of the sponge
+#[inline(always)]
+const fn squeeze(mut st: [u64; 25], pos: usize) -> [u8; 32] {
+    // Keccak padding: `0x01` after the message and `0x80` at the end of the
+    // block, both may end up in the same byte.
+    st[pos >> 3] ^= 0x01 << ((pos & 7) << 3);
+    st[(RATE - 1) >> 3] ^= 0x80 << (((RATE - 1) & 7) << 3);
+    st = keccak_f(st);
+
+    let (mut out, mut i) = ([0u8; 32], 0usize);
+    while i < 32 {
+        out[i] = (st[i >> 3] >> ((i & 7) << 3)) as u8;
+        i += 1;
+    }
+
+    out
+}
+
+/// Streaming Keccak-256 hasher
+///
+/// Allocation free hasher for the data which is not available at once, the
+/// state takes 208 bytes on the stack.
+///
+/// ```rust
+/// use ethgen::{keccak256, Keccak256};
+///
+///
+/// let mut hasher = Keccak256::new();
+/// hasher.update(b"ethgen");
+/// hasher.update(b" rules");
+///
+/// assert_eq!(keccak256(b"ethgen rules"), hasher.finalize());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Keccak256 {
+    state: [u64; 25],
+    pos: usize,
+}
+
+impl Keccak256 {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            state: [0u64; 25],
+            pos: 0,
+        }
+    }
+
+    /// Absorb the data into the hasher
+    #[inline(always)]
+    pub fn update(&mut self, data: &[u8]) {
+        (self.state, self.pos) = absorb(self.state, self.pos, data);
+    }
+
+    /// Absorb the data into the hasher in const context
+    #[inline(always)]
+    pub const fn chain(self, data: &[u8]) -> Self {
+        let (state, pos) = absorb(self.state, self.pos, data);
+        Self { state, pos }
+    }
+
+    /// Consume the hasher returning the digest
+    #[inline(always)]
+    pub fn finalize(self) -> Bytes<U32> {
+        Bytes::from_array(squeeze(self.state, self.pos))
+    }
+
+    /// Consume the hasher returning the digest in const context
+    #[inline(always)]
+    pub const fn const_finalize(self) -> [u8; 32] {
+        squeeze(self.state, self.pos)
+    }
+}
+
+impl Default for Keccak256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compute Keccak-256 digest
+///
+/// ```rust
+/// use ethgen::{keccak256, unhex};
+///
+///
+/// let hash = keccak256(b"");
+/// let expected: [u8; 32] =
+///     unhex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+///
+/// assert_eq!(expected, hash.into_array());
+/// ```
+#[inline(always)]
+pub fn keccak256(src: &[u8]) -> Bytes<U32> {
+    Bytes::from_array(const_keccak256(src))
+}
+
+/// Compute Keccak-256 digest in const context
+///
+/// ```rust
+/// use ethgen::keccak::const_keccak256;
+///
+///
+/// const TRANSFER: [u8; 32] = const_keccak256(b"Transfer(address,address,uint256)");
+/// assert_eq!(
+///     [0xdd, 0xf2, 0x52, 0xad],
+///     [TRANSFER[0], TRANSFER[1], TRANSFER[2], TRANSFER[3]]
+/// );
+/// ```
+#[inline(always)]
+pub const fn const_keccak256(src: &[u8]) -> [u8; 32] {
+    let (st, pos) = absorb([0u64; 25], 0, src);
+    squeeze(st, pos)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_keccak256() {
+        let empty =
+            crate::unhex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+        assert_eq!(const_keccak256(b""), empty);
+
+        let abc = crate::unhex!("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45");
+        assert_eq!(const_keccak256(b"abc"), abc);
+    }
+
+    #[test]
+    fn test_multi_block() {
+        // Inputs of `a` bytes at and past the rate, hashes from OpenSSL
+        const VECTORS: [(usize, [u8; 32]); 4] = [
+            (
+                RATE,
+                crate::unhex!("a6c4d403279fe3e0af03729caada8374b5ca54d8065329a3ebcaeb4b60aa386e"),
+            ),
+            (
+                RATE + 1,
+                crate::unhex!("d869f639c7046b4929fc92a4d988a8b22c55fbadb802c0c66ebcd484f1915f39"),
+            ),
+            (
+                200,
+                crate::unhex!("96ea54061def936c4be90b518992fdc6f12f535068a256229aca54267b4d084d"),
+            ),
+            (
+                3 * RATE + 1,
+                crate::unhex!("646107664ca328313bef2a0198244e36fe6451b062974b10548977a7d81c5154"),
+            ),
+        ];
+
+        // Evaluated at compile time
+        const EXACT: [u8; 32] = const_keccak256(&[b'a'; RATE]);
+        const LONG: [u8; 32] = Keccak256::new().chain(&[b'a'; 200]).const_finalize();
+        assert_eq!(EXACT, VECTORS[0].1);
+        assert_eq!(LONG, VECTORS[2].1);
+
+        let data = [b'a'; 3 * RATE + 1];
+
+        for (len, expected) in VECTORS {
+            assert_eq!(keccak256(&data[..len]), expected.into(), "len {}", len);
+            assert_eq!(const_keccak256(&data[..len]), expected, "len {}", len);
+
+            let mut hasher = Keccak256::new();
+            hasher.update(&data[..len]);
+            assert_eq!(hasher.finalize(), expected.into(), "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_streaming() {
+        // Inputs around the rate boundary exercise the padding in the same
+        // byte as well as in a separate block.
+        let data = [0xa5u8; 3 * RATE + 1];
+
+        for len in [0, 1, RATE - 1, RATE, RATE + 1, 2 * RATE, data.len()] {
+            let expected = keccak256(&data[..len]);
+
+            for chunk in [1, 7, RATE - 1, RATE, RATE + 3] {
+                let mut hasher = Keccak256::new();
+                data[..len].chunks(chunk).for_each(|c| hasher.update(c));
+                assert_eq!(hasher.finalize(), expected, "len {} chunk {}", len, chunk);
+            }
+        }
+
+        let hash = Keccak256::new().chain(b"a").chain(b"bc").const_finalize();
+        assert_eq!(hash, const_keccak256(b"abc"));
+    }
+}
@@ -9,10 +9,31 @@ pub use generic_array::typenum;
 
 mod encoding;
 #[doc(inline)]
-pub use encoding::{as_hex, from_hex, hex, into_hex, Bytes};
+pub use encoding::{abi, as_hex, as_quantity, from_hex, hex, into_hex, rlp, Bytes, ListBuf};
 
 mod biguint;
 #[doc(inline)]
-pub use biguint::{uint, uint256, BigUint};
+pub use biguint::{uint, uint256, BigUint, ParseIntError, TryFromIntError};
 
+mod bigint;
+#[doc(inline)]
+pub use bigint::{int, int256, BigInt};
+
+pub mod keccak;
+#[doc(inline)]
+pub use keccak::{keccak256, Keccak256};
+
+mod address;
+#[doc(inline)]
+pub use address::{Address, ParseAddressError};
+
+mod signature;
+#[doc(inline)]
+pub use signature::{Signature, SignatureError};
+
+pub mod units;
+
+pub mod block;
 pub mod rpc;
+pub mod secp256k1;
+pub mod transaction;
@@ -0,0 +1,388 @@
+use crate::{as_hex, as_quantity, encoding::Data, typenum::U32, Bytes};
+use core::fmt;
+use serde::{
+    de::{self, MapAccess, Unexpected, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Block number or one of the block tags
+///
+/// Serialized as the QUANTITY hex string or as the tag name.
+///
+/// ```rust
+/// use ethgen::rpc::BlockNumberOrTag;
+///
+///
+/// let mut buf = [0u8; 32];
+/// let n = serde_json_core::to_slice(&BlockNumberOrTag::Number(1024), &mut buf).unwrap();
+/// assert_eq!(br#""0x400""#, &buf[..n]);
+///
+/// let (tag, _): (BlockNumberOrTag, usize) = serde_json_core::from_str(r#""finalized""#).unwrap();
+/// assert_eq!(tag, BlockNumberOrTag::Finalized);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BlockNumberOrTag {
+    /// The most recent block
+    #[default]
+    Latest,
+    /// The pending state and transactions
+    Pending,
+    /// The genesis block
+    Earliest,
+    /// The most recent safe head block
+    Safe,
+    /// The most recent finalized block
+    Finalized,
+    Number(u64),
+}
+
+impl BlockNumberOrTag {
+    /// The tag name, `None` for the block number
+    pub const fn tag(&self) -> Option<&'static str> {
+        match self {
+            Self::Latest => Some("latest"),
+            Self::Pending => Some("pending"),
+            Self::Earliest => Some("earliest"),
+            Self::Safe => Some("safe"),
+            Self::Finalized => Some("finalized"),
+            Self::Number(_) => None,
+        }
+    }
+
+    fn parse(src: &str) -> Option<Self> {
+        match src {
+            "latest" => Some(Self::Latest),
+            "pending" => Some(Self::Pending),
+            "earliest" => Some(Self::Earliest),
+            "safe" => Some(Self::Safe),
+            "finalized" => Some(Self::Finalized),
+            _ => as_quantity::decode(src).map(Self::Number),
+        }
+    }
+}
+
+impl From<u64> for BlockNumberOrTag {
+    fn from(value: u64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl Serialize for BlockNumberOrTag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Number(number) => as_quantity::serialize(number, serializer),
+            tag => serializer.serialize_str(tag.tag().unwrap_or_default()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockNumberOrTag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TagVisitor;
+
+        impl Visitor<'_> for TagVisitor {
+            type Value = BlockNumberOrTag;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "block number or tag")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                BlockNumberOrTag::parse(v)
+                    .ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(TagVisitor)
+    }
+}
+
+/// Block parameter of the JSON RPC methods
+///
+/// Either a block number, a tag or the
+/// [EIP-1898](https://eips.ethereum.org/EIPS/eip-1898) block hash object.
+/// The number and the tag are serialized as strings, the hash is serialized
+/// as `{"blockHash": .., "requireCanonical": ..}` (the latter is omitted when
+/// not set). The `{"blockNumber": ..}` object is accepted on deserialization.
+///
+/// Deserialization relies on `deserialize_ignored_any` dispatching on the
+/// type of the value, the way `serde_json_core` 0.5 does (it doesn't support
+/// `deserialize_any`). This is not a documented guarantee of the format, the
+/// dev dependency is pinned to the tested version. Formats which skip the
+/// ignored value instead, such as `serde_json`, are not supported and fail
+/// with an error.
+///
+/// ```rust
+/// use ethgen::{from_hex, rpc::{BlockId, BlockNumberOrTag}};
+///
+///
+/// let params = (BlockId::from(0x10), BlockId::default());
+///
+/// let mut buf = [0u8; 128];
+/// let n = serde_json_core::to_slice(&params, &mut buf).unwrap();
+/// assert_eq!(br#"["0x10","latest"]"#, &buf[..n]);
+///
+///
+/// let hash = from_hex("0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3");
+/// let block = BlockId::Hash { hash, require_canonical: Some(true) };
+///
+/// let n = serde_json_core::to_slice(&block, &mut buf).unwrap();
+/// let json = core::str::from_utf8(&buf[..n]).unwrap();
+///
+/// assert_eq!(
+///     json,
+///     r#"{"blockHash":"0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3","requireCanonical":true}"#,
+/// );
+///
+/// let (parsed, _): (BlockId, usize) = serde_json_core::from_str(json).unwrap();
+/// assert_eq!(parsed, block);
+///
+/// let (parsed, _): (BlockId, usize) = serde_json_core::from_str(r#"{"blockNumber":"0x0"}"#).unwrap();
+/// assert_eq!(parsed, BlockId::Number(BlockNumberOrTag::Number(0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockId {
+    Number(BlockNumberOrTag),
+    Hash {
+        hash: Bytes<U32>,
+        /// Whether the block must be in the canonical chain
+        require_canonical: Option<bool>,
+    },
+}
+
+impl BlockId {
+    pub const LATEST: Self = Self::Number(BlockNumberOrTag::Latest);
+    pub const PENDING: Self = Self::Number(BlockNumberOrTag::Pending);
+    pub const EARLIEST: Self = Self::Number(BlockNumberOrTag::Earliest);
+    pub const SAFE: Self = Self::Number(BlockNumberOrTag::Safe);
+    pub const FINALIZED: Self = Self::Number(BlockNumberOrTag::Finalized);
+
+    /// Block by hash, without the canonical chain requirement
+    #[inline(always)]
+    pub const fn hash(hash: Bytes<U32>) -> Self {
+        Self::Hash {
+            hash,
+            require_canonical: None,
+        }
+    }
+}
+
+impl Default for BlockId {
+    fn default() -> Self {
+        Self::LATEST
+    }
+}
+
+impl From<u64> for BlockId {
+    fn from(value: u64) -> Self {
+        Self::Number(BlockNumberOrTag::Number(value))
+    }
+}
+
+impl From<BlockNumberOrTag> for BlockId {
+    fn from(value: BlockNumberOrTag) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<Bytes<U32>> for BlockId {
+    fn from(value: Bytes<U32>) -> Self {
+        Self::hash(value)
+    }
+}
+
+/// Block hash serialized with `as_hex`, deserialized strictly
+pub(super) struct Hash(pub(super) Bytes<U32>);
+
+impl Serialize for Hash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        as_hex::serialize(self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Hash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Data::deserialize(deserializer).map(|data| Self(data.0))
+    }
+}
+
+impl Serialize for BlockId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Number(number) => number.serialize(serializer),
+            Self::Hash {
+                hash,
+                require_canonical,
+            } => {
+                let len = 1 + require_canonical.is_some() as usize;
+                let mut map = serializer.serialize_map(Some(len))?;
+                map.serialize_entry("blockHash", &Hash(*hash))?;
+                if let Some(require_canonical) = require_canonical {
+                    map.serialize_entry("requireCanonical", require_canonical)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // `serde_json_core` does not support `deserialize_any`, while its
+        // `deserialize_ignored_any` dispatches on the leading character to
+        // `deserialize_str` or to `deserialize_struct`, as required here.
+        // Formats skipping the value end up in `visit_unit`.
+        deserializer.deserialize_ignored_any(BlockIdVisitor)
+    }
+}
+
+struct BlockIdVisitor;
+
+impl<'de> Visitor<'de> for BlockIdVisitor {
+    type Value = BlockId;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "block number, tag or EIP-1898 block object")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Err(E::custom(
+            "the format skips the value in `deserialize_ignored_any`",
+        ))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        BlockNumberOrTag::parse(v)
+            .map(BlockId::Number)
+            .ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut number = None;
+        let mut hash = None;
+        let mut require_canonical = None;
+
+        while let Some(key) = map.next_key::<&str>()? {
+            match key {
+                "blockNumber" if number.is_none() => {
+                    number = Some(map.next_value::<BlockNumberOrTag>()?)
+                }
+                "blockHash" if hash.is_none() => hash = Some(map.next_value::<Hash>()?.0),
+                "requireCanonical" if require_canonical.is_none() => {
+                    require_canonical = Some(map.next_value()?)
+                }
+                "blockNumber" | "blockHash" | "requireCanonical" => {
+                    return Err(de::Error::custom("duplicate block object field"))
+                }
+                _ => return Err(de::Error::unknown_field(key, FIELDS)),
+            }
+        }
+
+        match (number, hash) {
+            (Some(number), None) if require_canonical.is_none() => Ok(BlockId::Number(number)),
+            (None, Some(hash)) => Ok(BlockId::Hash {
+                hash,
+                require_canonical,
+            }),
+            _ => Err(de::Error::custom(
+                "either blockNumber or blockHash is expected",
+            )),
+        }
+    }
+}
+
+const FIELDS: &[&str] = &["blockNumber", "blockHash", "requireCanonical"];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::from_hex;
+    use serde::de::value::{self, StrDeserializer, UnitDeserializer};
+
+    const HASH: &str = "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3";
+
+    fn to_str<'a, T: Serialize>(value: &T, buf: &'a mut [u8]) -> &'a str {
+        let n = serde_json_core::to_slice(value, buf).unwrap();
+        core::str::from_utf8(&buf[..n]).unwrap()
+    }
+
+    #[test]
+    fn test_serialize() {
+        let mut buf = [0u8; 128];
+
+        let cases = [
+            (BlockId::LATEST, r#""latest""#),
+            (BlockId::PENDING, r#""pending""#),
+            (BlockId::EARLIEST, r#""earliest""#),
+            (BlockId::SAFE, r#""safe""#),
+            (BlockId::FINALIZED, r#""finalized""#),
+            (BlockId::from(0), r#""0x0""#),
+            (BlockId::from(0x1b4), r#""0x1b4""#),
+        ];
+
+        for (block, expected) in cases {
+            assert_eq!(to_str(&block, &mut buf), expected);
+
+            let (parsed, _): (BlockId, usize) = serde_json_core::from_str(expected).unwrap();
+            assert_eq!(parsed, block);
+        }
+
+        let block = BlockId::hash(from_hex(HASH));
+        let expected =
+            r#"{"blockHash":"0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"}"#;
+        assert_eq!(to_str(&block, &mut buf), expected);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let json = r#"{"requireCanonical":false,"blockHash":"0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"}"#;
+        let (parsed, _): (BlockId, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(
+            parsed,
+            BlockId::Hash {
+                hash: from_hex(HASH),
+                require_canonical: Some(false),
+            }
+        );
+
+        // Inside of the params array
+        let json = r#"["0xc94770007dda54cf92009bff0de90c06f603a09f",{"blockNumber":"0x1b4"}]"#;
+        let (parsed, _): ((&str, BlockId), usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(parsed.1, BlockId::from(0x1b4));
+
+        let invalid = [
+            r#""0x01""#,
+            r#""0x""#,
+            r#""Latest""#,
+            r#"42"#,
+            r#"{}"#,
+            r#"{"blockNumber":"0x1","requireCanonical":true}"#,
+            r#"{"blockNumber":"0x1","blockHash":"0x00"}"#,
+            r#"{"blockNumber":"0x1","blockNumber":"0x2"}"#,
+            r#"{"blockNumber":"0x1","foo":1}"#,
+            r#"{"blockHash":"0x0"}"#,
+            r#"{"blockHash":""}"#,
+            r#"{"blockHash":"0xzz"}"#,
+            r#"{"blockHash":"d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"}"#,
+        ];
+
+        for json in invalid {
+            assert!(
+                serde_json_core::from_str::<BlockId>(json).is_err(),
+                "{}",
+                json
+            );
+        }
+
+        assert!(serde_json_core::from_str::<BlockNumberOrTag>(r#"{"blockNumber":"0x1"}"#).is_err());
+
+        // Formats forwarding `deserialize_ignored_any` to the value are
+        // supported, the ones skipping it (e.g. `serde_json`) are rejected
+        let str = StrDeserializer::<value::Error>::new("safe");
+        assert_eq!(BlockId::deserialize(str), Ok(BlockId::SAFE));
+
+        let unit = UnitDeserializer::<value::Error>::new();
+        assert!(BlockId::deserialize(unit).is_err());
+    }
+}
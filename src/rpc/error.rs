@@ -83,10 +83,10 @@ impl From<&str> for Message {
     }
 }
 
-impl<'a> Into<&'a str> for &'a Message {
+impl<'a> From<&'a Message> for &'a str {
     #[inline(always)]
-    fn into(self) -> &'a str {
-        unsafe { str::from_utf8_unchecked(&self.0[..self.1]) }
+    fn from(value: &'a Message) -> Self {
+        unsafe { str::from_utf8_unchecked(&value.0[..value.1]) }
     }
 }
 
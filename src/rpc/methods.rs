@@ -0,0 +1,1217 @@
+//! Typed JSON RPC methods
+//!
+//! Each method is a struct holding its params, which is serialized as the
+//! JSON array of params in the order defined by the specification. The
+//! [`RpcMethod`] trait pairs the params with the method name and the result
+//! type, so that a request built with [`Request::new`] can not be sent with
+//! wrong params, and its response is deserialized into the matching type.
+//!
+//! QUANTITY values are wrapped into [`QuantityValue`], while DATA values
+//! (hashes, code, call results) are borrowed as `0x` prefixed hex strings.
+//!
+//! ```rust
+//! use ethgen::{
+//!     address, uint256,
+//!     rpc::{methods::EthGetBalance, BlockId, MethodResponse, QuantityValue, Request},
+//! };
+//!
+//!
+//! let req = Request::new(1, EthGetBalance {
+//!     address: address!("0xc94770007dda54cF92009BFF0dE90c06F603a09f"),
+//!     block: BlockId::LATEST,
+//! });
+//!
+//! let mut buf = [0u8; 256];
+//! let n = serde_json_core::to_slice(&req, &mut buf).unwrap();
+//!
+//! assert_eq!(
+//!     core::str::from_utf8(&buf[..n]).unwrap(),
+//!     r#"{"jsonrpc":"2.0","method":"eth_getBalance","params":["0xc94770007dda54cf92009bff0de90c06f603a09f","latest"],"id":1}"#,
+//! );
+//!
+//!
+//! let json = r#"{"jsonrpc":"2.0","id":1,"result":"0x234c8a3397aab58"}"#;
+//!
+//! let (res, _): (MethodResponse<EthGetBalance>, usize) = serde_json_core::from_str(json).unwrap();
+//!
+//! assert_eq!(res.unwrap(), QuantityValue(uint256::from(158972490234375000u64)));
+//! ```
+
+use super::{
+    block_id::Hash, AccessListResult, AccountProof, BlockId, BlockNumberOrTag, FeeHistory, Method,
+    Request, Response, SyncStatus, Version,
+};
+use crate::{
+    as_quantity,
+    block::{Block, BlockTransaction, Header, Log, TransactionReceipt, TxHash},
+    transaction::Words,
+    typenum::U32,
+    uint256, Address, Bytes, ListBuf, Signature,
+};
+use core::marker::PhantomData;
+use serde::{
+    ser::{SerializeSeq, SerializeStruct, SerializeTuple},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// JSON RPC method with statically typed params and result
+pub trait RpcMethod: Serialize {
+    /// Method name
+    const METHOD: Method;
+
+    /// Type of the `result` field of the response, which may borrow from
+    /// the response JSON
+    type Result<'de>: Deserialize<'de>;
+}
+
+/// Response of the method `M`, which may borrow from the response JSON
+pub type MethodResponse<'de, M> = Response<<M as RpcMethod>::Result<'de>>;
+
+/// Item of a list result, named without the lifetime of the response
+///
+/// Lets a method type select the item of its result, which may borrow from
+/// the response JSON, e.g. [`EthGetFilterChanges`] of [`Log`] results in
+/// `ListBuf<Log<'de>, N>`.
+pub trait ResultItem {
+    type Item<'de>: Deserialize<'de> + Copy + Default;
+}
+
+impl ResultItem for TxHash {
+    type Item<'de> = TxHash;
+}
+
+impl ResultItem for BlockTransaction<'_> {
+    type Item<'de> = BlockTransaction<'de>;
+}
+
+impl ResultItem for Log<'_> {
+    type Item<'de> = Log<'de>;
+}
+
+/// Transaction item of a block result, which selects the full transactions
+/// flag of `eth_getBlockByNumber` and `eth_getBlockByHash`
+pub trait BlockItem: ResultItem {
+    const FULL: bool;
+}
+
+impl BlockItem for TxHash {
+    const FULL: bool = false;
+}
+
+impl BlockItem for BlockTransaction<'_> {
+    const FULL: bool = true;
+}
+
+impl<M: RpcMethod> Request<M> {
+    /// Request of the method, paired with its params by the type
+    pub const fn new(id: u64, params: M) -> Self {
+        Self {
+            jsonrpc: Version::V2,
+            method: M::METHOD,
+            params,
+            id,
+        }
+    }
+}
+
+/// Integer serialized as the QUANTITY hex string
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuantityValue<T>(pub T);
+
+impl<T: as_quantity::Quantity> Serialize for QuantityValue<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        as_quantity::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: as_quantity::Quantity> Deserialize<'de> for QuantityValue<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        as_quantity::deserialize(deserializer).map(Self)
+    }
+}
+
+impl<T> From<T> for QuantityValue<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+/// Transaction call object of `eth_call`, `eth_estimateGas`,
+/// `eth_createAccessList`, `eth_sendTransaction` and `eth_signTransaction`,
+/// only the present fields are serialized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<QuantityValue<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<QuantityValue<u128>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<QuantityValue<u128>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<QuantityValue<u128>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<QuantityValue<uint256>>,
+    /// Call data as `0x` prefixed hex string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<&'a str>,
+}
+
+/// Log filter of `eth_getLogs`, `eth_newFilter` and the `logs`
+/// subscription, only the present fields are serialized
+///
+/// Each topic position is either `None`, which matches any topic, or the list
+/// of the accepted topics. The block hash excludes the block range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Filter<'a> {
+    pub from_block: Option<BlockNumberOrTag>,
+    pub to_block: Option<BlockNumberOrTag>,
+    pub block_hash: Option<Bytes<U32>>,
+    /// Emitting contracts, any contract if empty
+    pub address: &'a [Address],
+    pub topics: &'a [Option<&'a [Bytes<U32>]>],
+}
+
+impl Serialize for Filter<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Topics<'a>(&'a [Option<&'a [Bytes<U32>]>]);
+
+        impl Serialize for Topics<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+                for topic in self.0 {
+                    seq.serialize_element(&topic.map(Words::new))?;
+                }
+                seq.end()
+            }
+        }
+
+        let mut filter = serializer.serialize_struct("Filter", 5)?;
+        if let Some(block) = &self.from_block {
+            filter.serialize_field("fromBlock", block)?;
+        }
+        if let Some(block) = &self.to_block {
+            filter.serialize_field("toBlock", block)?;
+        }
+        if let Some(hash) = self.block_hash {
+            filter.serialize_field("blockHash", &Hash(hash))?;
+        }
+        if !self.address.is_empty() {
+            filter.serialize_field("address", self.address)?;
+        }
+        if !self.topics.is_empty() {
+            filter.serialize_field("topics", &Topics(self.topics))?;
+        }
+        filter.end()
+    }
+}
+
+/// Subscription of `eth_subscribe`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Subscription<'a> {
+    /// Headers of the new chain heads
+    #[default]
+    NewHeads,
+    /// Logs matching the filter, the block range is ignored
+    Logs(Filter<'a>),
+    /// Hashes of the new pending transactions
+    NewPendingTransactions,
+}
+
+/// Serialize the params struct fields as a JSON array, a field may be
+/// wrapped into a type serializing it (`hash as Hash`)
+macro_rules! params {
+    ($serializer:expr, $self:expr, $($field:ident $(as $wrap:ident)?),*) => {{
+        let len = <[&str]>::len(&[$(stringify!($field)),*]);
+        #[allow(unused_mut)]
+        let mut params = $serializer.serialize_tuple(len)?;
+        $(params.serialize_element(&$($wrap)?($self.$field))?;)*
+        params.end()
+    }};
+}
+
+macro_rules! method {
+    (
+        $(#[$doc:meta])*
+        $name:ident $(<$lt:lifetime>)? {
+            $($(#[$field_doc:meta])* $field:ident: $ty:ty $(as $wrap:ident)?),* $(,)?
+        } => $method:ident -> $result:ty;
+    ) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct $name $(<$lt>)? {
+            $($(#[$field_doc])* pub $field: $ty,)*
+        }
+
+        impl $(<$lt>)? Serialize for $name $(<$lt>)? {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                params!(serializer, self, $($field $(as $wrap)?),*)
+            }
+        }
+
+        impl $(<$lt>)? RpcMethod for $name $(<$lt>)? {
+            const METHOD: Method = Method::$method;
+            type Result<'de> = $result;
+        }
+    };
+}
+
+method! {
+    /// `eth_chainId`
+    EthChainId {} => EthChainId -> QuantityValue<u64>;
+}
+
+method! {
+    /// `eth_blockNumber`
+    EthBlockNumber {} => EthBlockNumber -> QuantityValue<u64>;
+}
+
+method! {
+    /// `eth_gasPrice`
+    EthGasPrice {} => EthGasPrice -> QuantityValue<u128>;
+}
+
+method! {
+    /// `eth_maxPriorityFeePerGas`
+    EthMaxPriorityFeePerGas {} => EthMaxPriorityFeePerGas -> QuantityValue<u128>;
+}
+
+method! {
+    /// `eth_blobBaseFee`
+    EthBlobBaseFee {} => EthBlobBaseFee -> QuantityValue<u128>;
+}
+
+method! {
+    /// `eth_getBalance`
+    EthGetBalance {
+        address: Address,
+        /// Block number, tag or hash
+        block: BlockId,
+    } => EthGetBalance -> QuantityValue<uint256>;
+}
+
+method! {
+    /// `eth_getTransactionCount`
+    EthGetTransactionCount {
+        address: Address,
+        /// Block number, tag or hash
+        block: BlockId,
+    } => EthGetTransactionCount -> QuantityValue<u64>;
+}
+
+method! {
+    /// `eth_getCode`, the result is the hex encoded code
+    EthGetCode {
+        address: Address,
+        /// Block number, tag or hash
+        block: BlockId,
+    } => EthGetCode -> &'de str;
+}
+
+method! {
+    /// `eth_getStorageAt`, the result is the hex encoded 32 bytes value
+    EthGetStorageAt {
+        address: Address,
+        slot: QuantityValue<uint256>,
+        /// Block number, tag or hash
+        block: BlockId,
+    } => EthGetStorageAt -> &'de str;
+}
+
+method! {
+    /// `eth_call`, the result is the hex encoded return data
+    EthCall<'a> {
+        call: CallRequest<'a>,
+        /// Block number, tag or hash
+        block: BlockId,
+    } => EthCall -> &'de str;
+}
+
+method! {
+    /// `eth_estimateGas`
+    EthEstimateGas<'a> {
+        call: CallRequest<'a>,
+        /// Block number, tag or hash
+        block: BlockId,
+    } => EthEstimateGas -> QuantityValue<u64>;
+}
+
+method! {
+    /// `eth_sendRawTransaction`, the result is the transaction hash
+    EthSendRawTransaction<'a> {
+        /// Hex encoded signed transaction
+        transaction: &'a str,
+    } => EthSendRawTransaction -> &'de str;
+}
+
+method! {
+    /// `net_version`, the result is the decimal network id
+    NetVersion {} => NetVersion -> &'de str;
+}
+
+method! {
+    /// `web3_clientVersion`
+    Web3ClientVersion {} => Web3ClientVersion -> &'de str;
+}
+
+method! {
+    /// `web3_sha3`, the result is the hex encoded Keccak-256 hash
+    Web3Sha3<'a> {
+        /// Hex encoded data to hash
+        data: &'a str,
+    } => Web3Sha3 -> &'de str;
+}
+
+method! {
+    /// `net_listening`
+    NetListening {} => NetListening -> bool;
+}
+
+method! {
+    /// `net_peerCount`
+    NetPeerCount {} => NetPeerCount -> QuantityValue<u64>;
+}
+
+method! {
+    /// `eth_protocolVersion`
+    EthProtocolVersion {} => EthProtocolVersion -> &'de str;
+}
+
+method! {
+    /// `eth_syncing`, see [`SyncStatus`]
+    EthSyncing {} => EthSyncing -> SyncStatus;
+}
+
+method! {
+    /// `eth_coinbase`
+    EthCoinbase {} => EthCoinbase -> Address;
+}
+
+method! {
+    /// `eth_sendTransaction`, signed by the client with the key of the `from`
+    /// account, the result is the transaction hash
+    EthSendTransaction<'a> {
+        transaction: CallRequest<'a>,
+    } => EthSendTransaction -> &'de str;
+}
+
+method! {
+    /// `eth_signTransaction`, signed by the client with the key of the
+    /// `from` account, the result is the hex encoded signed transaction
+    EthSignTransaction<'a> {
+        transaction: CallRequest<'a>,
+    } => EthSignTransaction -> &'de str;
+}
+
+method! {
+    /// `eth_sign`, the client signs the EIP-191 message with the key of the
+    /// account
+    EthSign<'a> {
+        address: Address,
+        /// Hex encoded message
+        message: &'a str,
+    } => EthSign -> Signature;
+}
+
+/// `eth_createAccessList`, the result access list holds up to `N` addresses
+/// and `K` storage keys
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthCreateAccessList<'a, const N: usize, const K: usize> {
+    pub call: CallRequest<'a>,
+    /// Block number, tag or hash
+    pub block: BlockId,
+}
+
+impl<const N: usize, const K: usize> Serialize for EthCreateAccessList<'_, N, K> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        params!(serializer, self, call, block)
+    }
+}
+
+impl<const N: usize, const K: usize> RpcMethod for EthCreateAccessList<'_, N, K> {
+    const METHOD: Method = Method::EthCreateAccessList;
+    type Result<'de> = AccessListResult<N, K>;
+}
+
+/// `eth_getTransactionReceipt`, the result receipt holds up to `N` logs and
+/// is `None` for an unknown or pending transaction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthGetTransactionReceipt<const N: usize> {
+    pub hash: TxHash,
+}
+
+impl<const N: usize> Serialize for EthGetTransactionReceipt<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        params!(serializer, self, hash)
+    }
+}
+
+impl<const N: usize> RpcMethod for EthGetTransactionReceipt<N> {
+    const METHOD: Method = Method::EthGetTransactionReceipt;
+    type Result<'de> = Option<TransactionReceipt<'de, N>>;
+}
+
+method! {
+    /// `eth_getTransactionByHash`, the result is `None` for an unknown
+    /// transaction
+    EthGetTransactionByHash {
+        hash: TxHash,
+    } => EthGetTransactionByHash -> Option<BlockTransaction<'de>>;
+}
+
+method! {
+    /// `eth_getRawTransactionByHash`, the result is the hex encoded signed
+    /// transaction, `None` for an unknown transaction
+    EthGetRawTransactionByHash {
+        hash: TxHash,
+    } => EthGetRawTransactionByHash -> Option<&'de str>;
+}
+
+method! {
+    /// `eth_getTransactionByBlockHashAndIndex`, the result is `None` for an
+    /// unknown block or an index out of range
+    EthGetTransactionByBlockHashAndIndex {
+        hash: Bytes<U32> as Hash,
+        index: QuantityValue<u64>,
+    } => EthGetTransactionByBlockHashAndIndex -> Option<BlockTransaction<'de>>;
+}
+
+method! {
+    /// `eth_getTransactionByBlockNumberAndIndex`, the result is `None` for an
+    /// unknown block or an index out of range
+    EthGetTransactionByBlockNumberAndIndex {
+        block: BlockNumberOrTag,
+        index: QuantityValue<u64>,
+    } => EthGetTransactionByBlockNumberAndIndex -> Option<BlockTransaction<'de>>;
+}
+
+method! {
+    /// `eth_getBlockTransactionCountByHash`, the result is `None` for an
+    /// unknown block
+    EthGetBlockTransactionCountByHash {
+        hash: Bytes<U32> as Hash,
+    } => EthGetBlockTransactionCountByHash -> Option<QuantityValue<u64>>;
+}
+
+method! {
+    /// `eth_getBlockTransactionCountByNumber`, the result is `None` for an
+    /// unknown block
+    EthGetBlockTransactionCountByNumber {
+        block: BlockNumberOrTag,
+    } => EthGetBlockTransactionCountByNumber -> Option<QuantityValue<u64>>;
+}
+
+method! {
+    /// `eth_getUncleCountByBlockHash`, the result is `None` for an unknown
+    /// block
+    EthGetUncleCountByBlockHash {
+        hash: Bytes<U32> as Hash,
+    } => EthGetUncleCountByBlockHash -> Option<QuantityValue<u64>>;
+}
+
+method! {
+    /// `eth_getUncleCountByBlockNumber`, the result is `None` for an unknown
+    /// block
+    EthGetUncleCountByBlockNumber {
+        block: BlockNumberOrTag,
+    } => EthGetUncleCountByBlockNumber -> Option<QuantityValue<u64>>;
+}
+
+method! {
+    /// `eth_newFilter`, the result is the filter id
+    EthNewFilter<'a> {
+        filter: Filter<'a>,
+    } => EthNewFilter -> &'de str;
+}
+
+method! {
+    /// `eth_newBlockFilter`, the result is the filter id
+    EthNewBlockFilter {} => EthNewBlockFilter -> &'de str;
+}
+
+method! {
+    /// `eth_newPendingTransactionFilter`, the result is the filter id
+    EthNewPendingTransactionFilter {} => EthNewPendingTransactionFilter -> &'de str;
+}
+
+method! {
+    /// `eth_uninstallFilter`, the result is `false` for an unknown filter
+    EthUninstallFilter<'a> {
+        id: &'a str,
+    } => EthUninstallFilter -> bool;
+}
+
+method! {
+    /// `eth_unsubscribe`, the result is `false` for an unknown subscription
+    EthUnsubscribe<'a> {
+        id: &'a str,
+    } => EthUnsubscribe -> bool;
+}
+
+/// `eth_getBlockByNumber`, the result block holds up to `N` transactions of
+/// [`TxHash`] or [`BlockTransaction`] type, which selects the full
+/// transactions flag, and is `None` for an unknown block
+///
+/// ```rust
+/// use ethgen::{
+///     block::BlockTransaction,
+///     rpc::{methods::EthGetBlockByNumber, BlockNumberOrTag, Request},
+/// };
+///
+///
+/// let req = Request::new(1, EthGetBlockByNumber::<BlockTransaction, 256>::new(
+///     BlockNumberOrTag::Finalized,
+/// ));
+///
+/// let mut buf = [0u8; 128];
+/// let n = serde_json_core::to_slice(&req, &mut buf).unwrap();
+///
+/// assert_eq!(
+///     core::str::from_utf8(&buf[..n]).unwrap(),
+///     r#"{"jsonrpc":"2.0","method":"eth_getBlockByNumber","params":["finalized",true],"id":1}"#,
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthGetBlockByNumber<T, const N: usize, const X: usize = 32> {
+    pub block: BlockNumberOrTag,
+    transactions: PhantomData<T>,
+}
+
+impl<T, const N: usize, const X: usize> EthGetBlockByNumber<T, N, X> {
+    pub const fn new(block: BlockNumberOrTag) -> Self {
+        Self {
+            block,
+            transactions: PhantomData,
+        }
+    }
+}
+
+impl<T: BlockItem, const N: usize, const X: usize> Serialize for EthGetBlockByNumber<T, N, X> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut params = serializer.serialize_tuple(2)?;
+        params.serialize_element(&self.block)?;
+        params.serialize_element(&T::FULL)?;
+        params.end()
+    }
+}
+
+impl<T: BlockItem, const N: usize, const X: usize> RpcMethod for EthGetBlockByNumber<T, N, X> {
+    const METHOD: Method = Method::EthGetBlockByNumber;
+    type Result<'de> = Option<Block<T::Item<'de>, N, X>>;
+}
+
+/// `eth_getBlockByHash`, the result block holds up to `N` transactions of
+/// [`TxHash`] or [`BlockTransaction`] type, which selects the full
+/// transactions flag, and is `None` for an unknown block
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthGetBlockByHash<T, const N: usize, const X: usize = 32> {
+    pub hash: Bytes<U32>,
+    transactions: PhantomData<T>,
+}
+
+impl<T, const N: usize, const X: usize> EthGetBlockByHash<T, N, X> {
+    pub const fn new(hash: Bytes<U32>) -> Self {
+        Self {
+            hash,
+            transactions: PhantomData,
+        }
+    }
+}
+
+impl<T: BlockItem, const N: usize, const X: usize> Serialize for EthGetBlockByHash<T, N, X> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut params = serializer.serialize_tuple(2)?;
+        params.serialize_element(&Hash(self.hash))?;
+        params.serialize_element(&T::FULL)?;
+        params.end()
+    }
+}
+
+impl<T: BlockItem, const N: usize, const X: usize> RpcMethod for EthGetBlockByHash<T, N, X> {
+    const METHOD: Method = Method::EthGetBlockByHash;
+    type Result<'de> = Option<Block<T::Item<'de>, N, X>>;
+}
+
+/// `eth_getLogs`, the result holds up to `N` logs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthGetLogs<'a, const N: usize> {
+    pub filter: Filter<'a>,
+}
+
+impl<const N: usize> Serialize for EthGetLogs<'_, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        params!(serializer, self, filter)
+    }
+}
+
+impl<const N: usize> RpcMethod for EthGetLogs<'_, N> {
+    const METHOD: Method = Method::EthGetLogs;
+    type Result<'de> = ListBuf<Log<'de>, N>;
+}
+
+/// `eth_getFilterLogs`, the result holds up to `N` logs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthGetFilterLogs<'a, const N: usize> {
+    /// Log filter id
+    pub id: &'a str,
+}
+
+impl<const N: usize> Serialize for EthGetFilterLogs<'_, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        params!(serializer, self, id)
+    }
+}
+
+impl<const N: usize> RpcMethod for EthGetFilterLogs<'_, N> {
+    const METHOD: Method = Method::EthGetFilterLogs;
+    type Result<'de> = ListBuf<Log<'de>, N>;
+}
+
+/// `eth_getFilterChanges`, the result holds up to `N` items of [`Log`] type
+/// for a log filter or of [`TxHash`] type for a block or pending transaction
+/// filter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthGetFilterChanges<'a, T, const N: usize> {
+    pub id: &'a str,
+    changes: PhantomData<T>,
+}
+
+impl<'a, T, const N: usize> EthGetFilterChanges<'a, T, N> {
+    pub const fn new(id: &'a str) -> Self {
+        Self {
+            id,
+            changes: PhantomData,
+        }
+    }
+}
+
+impl<T: ResultItem, const N: usize> Serialize for EthGetFilterChanges<'_, T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        params!(serializer, self, id)
+    }
+}
+
+impl<T: ResultItem, const N: usize> RpcMethod for EthGetFilterChanges<'_, T, N> {
+    const METHOD: Method = Method::EthGetFilterChanges;
+    type Result<'de> = ListBuf<T::Item<'de>, N>;
+}
+
+/// `eth_feeHistory`, the result holds up to `N` blocks and up to `P` reward
+/// percentiles per block, see [`FeeHistory`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EthFeeHistory<'a, const N: usize, const P: usize> {
+    pub block_count: QuantityValue<u64>,
+    pub newest_block: BlockNumberOrTag,
+    /// Increasing percentiles of the priority fees to sample
+    pub reward_percentiles: &'a [f64],
+}
+
+impl<const N: usize, const P: usize> Serialize for EthFeeHistory<'_, N, P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        params!(
+            serializer,
+            self,
+            block_count,
+            newest_block,
+            reward_percentiles
+        )
+    }
+}
+
+impl<const N: usize, const P: usize> RpcMethod for EthFeeHistory<'_, N, P> {
+    const METHOD: Method = Method::EthFeeHistory;
+    type Result<'de> = FeeHistory<N, P>;
+}
+
+/// `eth_getProof`, the result holds up to `N` nodes per Merkle proof and up
+/// to `K` storage proofs, see [`AccountProof`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthGetProof<'a, const N: usize, const K: usize> {
+    pub address: Address,
+    pub storage_keys: Words<'a>,
+    /// Block number, tag or hash
+    pub block: BlockId,
+}
+
+impl<const N: usize, const K: usize> Serialize for EthGetProof<'_, N, K> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        params!(serializer, self, address, storage_keys, block)
+    }
+}
+
+impl<const N: usize, const K: usize> RpcMethod for EthGetProof<'_, N, K> {
+    const METHOD: Method = Method::EthGetProof;
+    type Result<'de> = AccountProof<'de, N, K>;
+}
+
+/// `eth_subscribe`, the result is the subscription id
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthSubscribe<'a> {
+    pub subscription: Subscription<'a>,
+}
+
+impl Serialize for EthSubscribe<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.subscription {
+            Subscription::NewHeads => ("newHeads",).serialize(serializer),
+            Subscription::Logs(filter) => ("logs", filter).serialize(serializer),
+            Subscription::NewPendingTransactions => {
+                ("newPendingTransactions",).serialize(serializer)
+            }
+        }
+    }
+}
+
+impl RpcMethod for EthSubscribe<'_> {
+    const METHOD: Method = Method::EthSubscribe;
+    type Result<'de> = &'de str;
+}
+
+/// `eth_accounts`, the result holds up to `N` addresses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthAccounts<const N: usize> {}
+
+impl<const N: usize> Serialize for EthAccounts<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        params!(serializer, self,)
+    }
+}
+
+impl<const N: usize> RpcMethod for EthAccounts<N> {
+    const METHOD: Method = Method::EthAccounts;
+    type Result<'de> = ListBuf<Address, N>;
+}
+
+/// `eth_getBlockReceipts`, the result holds up to `N` receipts of up to `L`
+/// logs each and is `None` for an unknown block
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthGetBlockReceipts<const N: usize, const L: usize> {
+    /// Block number, tag or hash
+    pub block: BlockId,
+}
+
+impl<const N: usize, const L: usize> Serialize for EthGetBlockReceipts<N, L> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        params!(serializer, self, block)
+    }
+}
+
+impl<const N: usize, const L: usize> RpcMethod for EthGetBlockReceipts<N, L> {
+    const METHOD: Method = Method::EthGetBlockReceipts;
+    type Result<'de> = Option<ListBuf<TransactionReceipt<'de, L>, N>>;
+}
+
+/// `eth_getUncleByBlockHashAndIndex`, the result is the uncle header of up
+/// to `X` bytes of extra data, `None` for an unknown block or an index out
+/// of range
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthGetUncleByBlockHashAndIndex<const X: usize = 32> {
+    pub hash: Bytes<U32>,
+    pub index: QuantityValue<u64>,
+}
+
+impl<const X: usize> Serialize for EthGetUncleByBlockHashAndIndex<X> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        params!(serializer, self, hash as Hash, index)
+    }
+}
+
+impl<const X: usize> RpcMethod for EthGetUncleByBlockHashAndIndex<X> {
+    const METHOD: Method = Method::EthGetUncleByBlockHashAndIndex;
+    type Result<'de> = Option<Header<X>>;
+}
+
+/// `eth_getUncleByBlockNumberAndIndex`, the result is the uncle header of up
+/// to `X` bytes of extra data, `None` for an unknown block or an index out
+/// of range
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EthGetUncleByBlockNumberAndIndex<const X: usize = 32> {
+    pub block: BlockNumberOrTag,
+    pub index: QuantityValue<u64>,
+}
+
+impl<const X: usize> Serialize for EthGetUncleByBlockNumberAndIndex<X> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        params!(serializer, self, block, index)
+    }
+}
+
+impl<const X: usize> RpcMethod for EthGetUncleByBlockNumberAndIndex<X> {
+    const METHOD: Method = Method::EthGetUncleByBlockNumberAndIndex;
+    type Result<'de> = Option<Header<X>>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{address, from_hex, rpc::SyncProgress};
+
+    fn to_str<'a, T: Serialize>(value: &T, buf: &'a mut [u8]) -> &'a str {
+        let n = serde_json_core::to_slice(value, buf).unwrap();
+        core::str::from_utf8(&buf[..n]).unwrap()
+    }
+
+    #[test]
+    fn test_params() {
+        let mut buf = [0u8; 512];
+
+        assert_eq!(
+            to_str(&Request::new(1, EthChainId {}), &mut buf),
+            r#"{"jsonrpc":"2.0","method":"eth_chainId","params":[],"id":1}"#
+        );
+
+        assert_eq!(
+            to_str(
+                &Request::new(
+                    2,
+                    EthSendRawTransaction {
+                        transaction: "0x02f8"
+                    }
+                ),
+                &mut buf
+            ),
+            r#"{"jsonrpc":"2.0","method":"eth_sendRawTransaction","params":["0x02f8"],"id":2}"#
+        );
+
+        let params = EthGetStorageAt {
+            address: address!("0x295a70b2de5e3953354a6a8344e616ed314d7251"),
+            slot: QuantityValue(uint256::ZERO),
+            block: BlockId::LATEST,
+        };
+        assert_eq!(
+            to_str(&params, &mut buf),
+            r#"["0x295a70b2de5e3953354a6a8344e616ed314d7251","0x0","latest"]"#
+        );
+
+        let call = CallRequest {
+            to: Some(address!("0x295a70b2de5e3953354a6a8344e616ed314d7251")),
+            gas: Some(QuantityValue(30_000)),
+            input: Some("0x06fdde03"),
+            ..Default::default()
+        };
+        assert_eq!(
+            to_str(
+                &Request::new(
+                    3,
+                    EthCall {
+                        call,
+                        block: BlockId::from(0x10)
+                    }
+                ),
+                &mut buf
+            ),
+            r#"{"jsonrpc":"2.0","method":"eth_call","params":[{"to":"0x295a70b2de5e3953354a6a8344e616ed314d7251","gas":"0x7530","input":"0x06fdde03"},"0x10"],"id":3}"#
+        );
+
+        let req = Request::new(
+            4,
+            EthCreateAccessList::<4, 16> {
+                call,
+                block: BlockId::LATEST,
+            },
+        );
+        assert_eq!(req.method(), Method::EthCreateAccessList);
+
+        let params = EthGetTransactionReceipt::<4> {
+            hash: TxHash(from_hex(
+                "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060",
+            )),
+        };
+        assert_eq!(
+            to_str(&params, &mut buf),
+            r#"["0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"]"#
+        );
+    }
+
+    #[test]
+    fn test_result() {
+        type ChainId<'de> = MethodResponse<'de, EthChainId>;
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#;
+        let (res, _): (ChainId, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.unwrap(), QuantityValue(1));
+
+        type Call<'de> = MethodResponse<'de, EthCall<'static>>;
+        let json = r#"{"jsonrpc":"2.0","id":3,"result":"0x0000000000000000000000000000000000000000000000000000000000000001"}"#;
+        let (res, _): (Call, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(
+            res.unwrap(),
+            "0x0000000000000000000000000000000000000000000000000000000000000001"
+        );
+
+        type Receipt<'de> = MethodResponse<'de, EthGetTransactionReceipt<4>>;
+        let json = r#"{"jsonrpc":"2.0","id":4,"result":null}"#;
+        let (res, _): (Receipt, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.unwrap(), None);
+
+        // Non canonical quantity is rejected
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":"0x01"}"#;
+        assert!(serde_json_core::from_str::<ChainId>(json).is_err());
+    }
+
+    #[test]
+    fn test_filter_params() {
+        let mut buf = [0u8; 512];
+
+        let topic = from_hex("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+        let topics = [Some(&[topic][..]), None];
+        let filter = Filter {
+            from_block: Some(BlockNumberOrTag::Number(0x10)),
+            to_block: Some(BlockNumberOrTag::Latest),
+            address: &[address!("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")],
+            topics: &topics,
+            ..Default::default()
+        };
+        assert_eq!(
+            to_str(&Request::new(1, EthGetLogs::<16> { filter }), &mut buf),
+            r#"{"jsonrpc":"2.0","method":"eth_getLogs","params":[{"fromBlock":"0x10","toBlock":"latest","address":["0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"],"topics":[["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"],null]}],"id":1}"#
+        );
+
+        let filter = Filter {
+            block_hash: Some(topic),
+            ..Default::default()
+        };
+        assert_eq!(
+            to_str(&EthNewFilter { filter }, &mut buf),
+            r#"[{"blockHash":"0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"}]"#
+        );
+
+        let subscribe = |subscription| EthSubscribe { subscription };
+        assert_eq!(
+            to_str(&subscribe(Subscription::NewHeads), &mut buf),
+            r#"["newHeads"]"#
+        );
+        assert_eq!(
+            to_str(&subscribe(Subscription::Logs(Filter::default())), &mut buf),
+            r#"["logs",{}]"#
+        );
+
+        let req = Request::new(2, EthGetFilterChanges::<TxHash, 16>::new("0x1"));
+        assert_eq!(req.method(), Method::EthGetFilterChanges);
+        assert_eq!(to_str(&req.params, &mut buf), r#"["0x1"]"#);
+        assert_eq!(
+            to_str(
+                &EthUnsubscribe {
+                    id: "0x9cef478923ff08bf67fde6c64013158d"
+                },
+                &mut buf
+            ),
+            r#"["0x9cef478923ff08bf67fde6c64013158d"]"#
+        );
+    }
+
+    #[test]
+    fn test_block_params() {
+        let mut buf = [0u8; 512];
+
+        let hash = from_hex("0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3");
+        assert_eq!(
+            to_str(&EthGetBlockByHash::<TxHash, 16>::new(hash), &mut buf),
+            r#"["0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",false]"#
+        );
+        assert_eq!(
+            to_str(
+                &EthGetBlockByNumber::<BlockTransaction, 16>::new(BlockNumberOrTag::Number(0)),
+                &mut buf
+            ),
+            r#"["0x0",true]"#
+        );
+
+        let params = EthFeeHistory::<4, 2> {
+            block_count: QuantityValue(3),
+            newest_block: BlockNumberOrTag::Latest,
+            reward_percentiles: &[25.0, 75.5],
+        };
+        assert_eq!(to_str(&params, &mut buf), r#"["0x3","latest",[25.0,75.5]]"#);
+
+        let keys = [hash];
+        let params = EthGetProof::<8, 1> {
+            address: address!("0x7f0d15c7faae65896648c8273b6d7e43f58fa842"),
+            storage_keys: Words::new(&keys),
+            block: BlockId::LATEST,
+        };
+        assert_eq!(
+            to_str(&params, &mut buf),
+            r#"["0x7f0d15c7faae65896648c8273b6d7e43f58fa842",["0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"],"latest"]"#
+        );
+
+        let index = QuantityValue(2);
+        assert_eq!(
+            to_str(
+                &Request::new(1, EthGetTransactionByBlockHashAndIndex { hash, index }),
+                &mut buf
+            ),
+            r#"{"jsonrpc":"2.0","method":"eth_getTransactionByBlockHashAndIndex","params":["0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3","0x2"],"id":1}"#
+        );
+        assert_eq!(
+            to_str(
+                &EthGetTransactionByBlockNumberAndIndex {
+                    block: BlockNumberOrTag::Safe,
+                    index
+                },
+                &mut buf
+            ),
+            r#"["safe","0x2"]"#
+        );
+        assert_eq!(
+            to_str(
+                &EthGetUncleByBlockHashAndIndex::<32> { hash, index },
+                &mut buf
+            ),
+            r#"["0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3","0x2"]"#
+        );
+        assert_eq!(
+            to_str(&EthGetUncleCountByBlockHash { hash }, &mut buf),
+            r#"["0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"]"#
+        );
+        assert_eq!(
+            to_str(
+                &EthGetBlockTransactionCountByNumber {
+                    block: BlockNumberOrTag::Number(0x10)
+                },
+                &mut buf
+            ),
+            r#"["0x10"]"#
+        );
+        assert_eq!(
+            to_str(
+                &Request::new(
+                    2,
+                    EthGetBlockReceipts::<16, 4> {
+                        block: BlockId::LATEST
+                    }
+                ),
+                &mut buf
+            ),
+            r#"{"jsonrpc":"2.0","method":"eth_getBlockReceipts","params":["latest"],"id":2}"#
+        );
+    }
+
+    #[test]
+    fn test_client_methods() {
+        let mut buf = [0u8; 512];
+
+        assert_eq!(
+            to_str(&Request::new(1, EthAccounts::<4> {}), &mut buf),
+            r#"{"jsonrpc":"2.0","method":"eth_accounts","params":[],"id":1}"#
+        );
+        assert_eq!(
+            to_str(&Request::new(2, NetPeerCount {}), &mut buf),
+            r#"{"jsonrpc":"2.0","method":"net_peerCount","params":[],"id":2}"#
+        );
+
+        let params = EthSign {
+            address: address!("0x9b2055d370f73ec7d8a03e965129118dc8f5bf83"),
+            message: "0xdeadbeef",
+        };
+        assert_eq!(
+            to_str(&params, &mut buf),
+            r#"["0x9b2055d370f73ec7d8a03e965129118dc8f5bf83","0xdeadbeef"]"#
+        );
+
+        let transaction = CallRequest {
+            from: Some(address!("0x9b2055d370f73ec7d8a03e965129118dc8f5bf83")),
+            value: Some(QuantityValue(uint256::from(1u64))),
+            ..Default::default()
+        };
+        assert_eq!(
+            to_str(
+                &Request::new(3, EthSendTransaction { transaction }),
+                &mut buf
+            ),
+            r#"{"jsonrpc":"2.0","method":"eth_sendTransaction","params":[{"from":"0x9b2055d370f73ec7d8a03e965129118dc8f5bf83","value":"0x1"}],"id":3}"#
+        );
+        assert_eq!(
+            Request::new(4, EthSignTransaction { transaction }).method(),
+            Method::EthSignTransaction
+        );
+        assert_eq!(
+            to_str(
+                &Request::new(
+                    5,
+                    Web3Sha3 {
+                        data: "0x68656c6c6f20776f726c64"
+                    }
+                ),
+                &mut buf
+            ),
+            r#"{"jsonrpc":"2.0","method":"web3_sha3","params":["0x68656c6c6f20776f726c64"],"id":5}"#
+        );
+
+        type Accounts<'de> = MethodResponse<'de, EthAccounts<1>>;
+        let json =
+            r#"{"jsonrpc":"2.0","id":1,"result":["0x9b2055d370f73ec7d8a03e965129118dc8f5bf83"]}"#;
+        let (res, _): (Accounts, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(
+            res.unwrap().as_slice(),
+            &[address!("0x9b2055d370f73ec7d8a03e965129118dc8f5bf83")]
+        );
+
+        type Sign<'de> = MethodResponse<'de, EthSign<'static>>;
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":"0xa3f20717a250c2b0b729b7e5becbff67fdaef7e0699da4de7ca5895b02a170a12d887fd3b17bfdce3481f10bea41f45ba9f709d39ce8325427b57afcfc994cee1b"}"#;
+        let (res, _): (Sign, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.unwrap().v(), 27);
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":"0xa3f2"}"#;
+        assert!(serde_json_core::from_str::<Sign>(json).is_err());
+
+        type Listening<'de> = MethodResponse<'de, NetListening>;
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":true}"#;
+        let (res, _): (Listening, usize) = serde_json_core::from_str(json).unwrap();
+        assert!(res.unwrap());
+
+        type Syncing<'de> = MethodResponse<'de, EthSyncing>;
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":false}"#;
+        let (res, _): (Syncing, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.unwrap(), SyncStatus::Synced);
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":{"startingBlock":"0x0","currentBlock":"0x1","highestBlock":"0x2","syncedAccounts":"0x0"}}"#;
+        let (res, _): (Syncing, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(
+            res.unwrap(),
+            SyncStatus::Syncing(SyncProgress {
+                starting_block: 0,
+                current_block: 1,
+                highest_block: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_list_result() {
+        type Changes<'de> = MethodResponse<'de, EthGetFilterChanges<'static, TxHash, 2>>;
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":["0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"]}"#;
+        let (res, _): (Changes, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.unwrap().len(), 1);
+
+        type Logs<'de> = MethodResponse<'de, EthGetLogs<'static, 1>>;
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":[{
+            "address":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"],
+            "data":"0x01"
+        }]}"#;
+        let (res, _): (Logs, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.unwrap()[0].data, "0x01");
+
+        type Block<'de> = MethodResponse<'de, EthGetBlockByNumber<TxHash, 4>>;
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":null}"#;
+        let (res, _): (Block, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.unwrap(), None);
+
+        type Receipts<'de> = MethodResponse<'de, EthGetBlockReceipts<4, 4>>;
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":[]}"#;
+        let (res, _): (Receipts, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.unwrap().map(|receipts| receipts.len()), Some(0));
+
+        type Uncle<'de> = MethodResponse<'de, EthGetUncleByBlockNumberAndIndex>;
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":null}"#;
+        let (res, _): (Uncle, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.unwrap(), None);
+
+        type Unsubscribe<'de> = MethodResponse<'de, EthUnsubscribe<'static>>;
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":true}"#;
+        let (res, _): (Unsubscribe, usize) = serde_json_core::from_str(json).unwrap();
+        assert!(res.unwrap());
+    }
+}
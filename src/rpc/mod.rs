@@ -18,10 +18,20 @@ mod error;
 #[doc(inline)]
 pub use error::{Code, Error, Message};
 
+mod block_id;
+#[doc(inline)]
+pub use block_id::{BlockId, BlockNumberOrTag};
+
 mod res;
 #[doc(inline)]
-pub use res::Response;
+pub use res::{
+    AccessListResult, AccountProof, FeeHistory, Response, StorageProof, SyncProgress, SyncStatus,
+};
 
 mod req;
 #[doc(inline)]
 pub use req::{Method, Request};
+
+pub mod methods;
+#[doc(inline)]
+pub use methods::{MethodResponse, QuantityValue, RpcMethod};
@@ -2,42 +2,326 @@ use serde::{Deserialize, Serialize};
 
 /// JSON RPC method
 ///
-/// TODO:: Cover all avilable methods from Ethereum specifications.
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// Covers the [execution API](https://ethereum.github.io/execution-apis/)
+/// specification, along with the `net_*`, `web3_*` and the subscription
+/// methods. Each variant documents the shape of its params and result,
+/// where the `BlockId` is a block number, a tag or a block hash (EIP-1898).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Method {
-    #[serde(rename = "eth_chainId")]
-    EthChainId,
-    #[serde(rename = "eth_gasPrice")]
-    EthGasPrice,
+    /// Addresses owned by the client
+    ///
+    /// - params: `[]`;
+    /// - result: `[Address]`;
+    #[serde(rename = "eth_accounts")]
+    EthAccounts,
+    /// Current blob base fee per gas
+    ///
+    /// - params: `[]`;
+    /// - result: `QUANTITY`;
+    #[serde(rename = "eth_blobBaseFee")]
+    EthBlobBaseFee,
+    /// Number of the most recent block
+    ///
+    /// - params: `[]`;
+    /// - result: `QUANTITY`;
     #[serde(rename = "eth_blockNumber")]
     EthBlockNumber,
-    #[serde(rename = "eth_getBalance")]
-    EthGetBalance,
-    #[serde(rename = "eth_sendRawTransaction")]
-    EthSendRawTransaction,
+    /// Execute a message call without creating a transaction
+    ///
+    /// - params: `[transaction, BlockId]`;
+    /// - result: `DATA`, the return value;
     #[serde(rename = "eth_call")]
     EthCall,
+    /// Chain id used for the replay protection
+    ///
+    /// - params: `[]`;
+    /// - result: `QUANTITY`;
+    #[serde(rename = "eth_chainId")]
+    EthChainId,
+    /// Client coinbase address
+    ///
+    /// - params: `[]`;
+    /// - result: `Address`;
+    #[serde(rename = "eth_coinbase")]
+    EthCoinbase,
+    /// Generate an access list for the transaction
+    ///
+    /// - params: `[transaction, BlockId]`;
+    /// - result: `{ accessList, gasUsed, error? }`, see [`AccessListResult`](super::AccessListResult);
+    #[serde(rename = "eth_createAccessList")]
+    EthCreateAccessList,
+    /// Estimate gas required by the transaction
+    ///
+    /// - params: `[transaction, BlockId?]`;
+    /// - result: `QUANTITY`;
     #[serde(rename = "eth_estimateGas")]
     EthEstimateGas,
+    /// Fee market history
+    ///
+    /// - params: `[QUANTITY block count, BlockId newest block, [reward percentiles]]`;
+    /// - result: `{ oldestBlock, baseFeePerGas, gasUsedRatio, baseFeePerBlobGas, blobGasUsedRatio, reward? }`;
+    #[serde(rename = "eth_feeHistory")]
+    EthFeeHistory,
+    /// Current gas price
+    ///
+    /// - params: `[]`;
+    /// - result: `QUANTITY`;
+    #[serde(rename = "eth_gasPrice")]
+    EthGasPrice,
+    /// Balance of the account
+    ///
+    /// - params: `[Address, BlockId]`;
+    /// - result: `QUANTITY`;
+    #[serde(rename = "eth_getBalance")]
+    EthGetBalance,
+    /// Block by hash
+    ///
+    /// - params: `[hash, bool full transactions]`;
+    /// - result: `Block` or `null`;
+    #[serde(rename = "eth_getBlockByHash")]
+    EthGetBlockByHash,
+    /// Block by number or tag
+    ///
+    /// - params: `[BlockId, bool full transactions]`;
+    /// - result: `Block` or `null`;
+    #[serde(rename = "eth_getBlockByNumber")]
+    EthGetBlockByNumber,
+    /// Receipts of all transactions in the block
+    ///
+    /// - params: `[BlockId]`;
+    /// - result: `[TransactionReceipt]` or `null`;
+    #[serde(rename = "eth_getBlockReceipts")]
+    EthGetBlockReceipts,
+    /// Number of transactions in the block
+    ///
+    /// - params: `[hash]`;
+    /// - result: `QUANTITY`;
+    #[serde(rename = "eth_getBlockTransactionCountByHash")]
+    EthGetBlockTransactionCountByHash,
+    /// Number of transactions in the block
+    ///
+    /// - params: `[BlockId]`;
+    /// - result: `QUANTITY`;
+    #[serde(rename = "eth_getBlockTransactionCountByNumber")]
+    EthGetBlockTransactionCountByNumber,
+    /// Code of the account
+    ///
+    /// - params: `[Address, BlockId]`;
+    /// - result: `DATA`;
+    #[serde(rename = "eth_getCode")]
+    EthGetCode,
+    /// Poll the filter for changes since the last poll
+    ///
+    /// - params: `[QUANTITY filter id]`;
+    /// - result: `[Log]` or `[hash]`, depending on the filter;
+    #[serde(rename = "eth_getFilterChanges")]
+    EthGetFilterChanges,
+    /// All logs matching the filter
+    ///
+    /// - params: `[QUANTITY filter id]`;
+    /// - result: `[Log]`;
+    #[serde(rename = "eth_getFilterLogs")]
+    EthGetFilterLogs,
+    /// Logs matching the filter
+    ///
+    /// - params: `[{ fromBlock?, toBlock?, blockHash?, address?, topics? }]`;
+    /// - result: `[Log]`;
+    #[serde(rename = "eth_getLogs")]
+    EthGetLogs,
+    /// Merkle proof of the account and its storage
+    ///
+    /// - params: `[Address, [storage key], BlockId]`;
+    /// - result: `{ address, accountProof, balance, codeHash, nonce, storageHash, storageProof }`;
+    #[serde(rename = "eth_getProof")]
+    EthGetProof,
+    /// Signed transaction encoding by hash
+    ///
+    /// - params: `[hash]`;
+    /// - result: `DATA` or `null`;
+    #[serde(rename = "eth_getRawTransactionByHash")]
+    EthGetRawTransactionByHash,
+    /// Value of the storage slot
+    ///
+    /// - params: `[Address, QUANTITY slot, BlockId]`;
+    /// - result: `DATA`, 32 bytes;
+    #[serde(rename = "eth_getStorageAt")]
+    EthGetStorageAt,
+    /// Transaction by block hash and index
+    ///
+    /// - params: `[hash, QUANTITY index]`;
+    /// - result: `Transaction` or `null`;
+    #[serde(rename = "eth_getTransactionByBlockHashAndIndex")]
+    EthGetTransactionByBlockHashAndIndex,
+    /// Transaction by block number and index
+    ///
+    /// - params: `[BlockId, QUANTITY index]`;
+    /// - result: `Transaction` or `null`;
+    #[serde(rename = "eth_getTransactionByBlockNumberAndIndex")]
+    EthGetTransactionByBlockNumberAndIndex,
+    /// Transaction by hash
+    ///
+    /// - params: `[hash]`;
+    /// - result: `Transaction` or `null`;
     #[serde(rename = "eth_getTransactionByHash")]
     EthGetTransactionByHash,
+    /// Number of transactions sent from the account (nonce)
+    ///
+    /// - params: `[Address, BlockId]`;
+    /// - result: `QUANTITY`;
+    #[serde(rename = "eth_getTransactionCount")]
+    EthGetTransactionCount,
+    /// Receipt of the transaction
+    ///
+    /// - params: `[hash]`;
+    /// - result: `TransactionReceipt` or `null`;
     #[serde(rename = "eth_getTransactionReceipt")]
     EthGetTransactionReceipt,
+    /// Uncle by block hash and index
+    ///
+    /// - params: `[hash, QUANTITY index]`;
+    /// - result: `Block` or `null`;
+    #[serde(rename = "eth_getUncleByBlockHashAndIndex")]
+    EthGetUncleByBlockHashAndIndex,
+    /// Uncle by block number and index
+    ///
+    /// - params: `[BlockId, QUANTITY index]`;
+    /// - result: `Block` or `null`;
+    #[serde(rename = "eth_getUncleByBlockNumberAndIndex")]
+    EthGetUncleByBlockNumberAndIndex,
+    /// Number of uncles in the block
+    ///
+    /// - params: `[hash]`;
+    /// - result: `QUANTITY`;
+    #[serde(rename = "eth_getUncleCountByBlockHash")]
+    EthGetUncleCountByBlockHash,
+    /// Number of uncles in the block
+    ///
+    /// - params: `[BlockId]`;
+    /// - result: `QUANTITY`;
+    #[serde(rename = "eth_getUncleCountByBlockNumber")]
+    EthGetUncleCountByBlockNumber,
+    /// Suggested priority fee per gas
+    ///
+    /// - params: `[]`;
+    /// - result: `QUANTITY`;
+    #[serde(rename = "eth_maxPriorityFeePerGas")]
+    EthMaxPriorityFeePerGas,
+    /// Create a filter notifying on new blocks
+    ///
+    /// - params: `[]`;
+    /// - result: `QUANTITY` filter id;
+    #[serde(rename = "eth_newBlockFilter")]
+    EthNewBlockFilter,
+    /// Create a filter notifying on new logs
+    ///
+    /// - params: `[{ fromBlock?, toBlock?, address?, topics? }]`;
+    /// - result: `QUANTITY` filter id;
+    #[serde(rename = "eth_newFilter")]
+    EthNewFilter,
+    /// Create a filter notifying on new pending transactions
+    ///
+    /// - params: `[]`;
+    /// - result: `QUANTITY` filter id;
+    #[serde(rename = "eth_newPendingTransactionFilter")]
+    EthNewPendingTransactionFilter,
+    /// Ethereum protocol version
+    ///
+    /// - params: `[]`;
+    /// - result: `STRING`;
+    #[serde(rename = "eth_protocolVersion")]
+    EthProtocolVersion,
+    /// Submit the signed transaction
+    ///
+    /// - params: `[DATA]`;
+    /// - result: `hash` of the transaction;
+    #[serde(rename = "eth_sendRawTransaction")]
+    EthSendRawTransaction,
+    /// Sign and submit the transaction by the client
+    ///
+    /// - params: `[transaction]`;
+    /// - result: `hash` of the transaction;
+    #[serde(rename = "eth_sendTransaction")]
+    EthSendTransaction,
+    /// Sign the EIP-191 message by the client
+    ///
+    /// - params: `[Address, DATA]`;
+    /// - result: `DATA`, 65 bytes signature;
+    #[serde(rename = "eth_sign")]
+    EthSign,
+    /// Sign the transaction by the client
+    ///
+    /// - params: `[transaction]`;
+    /// - result: `DATA`, the signed transaction;
+    #[serde(rename = "eth_signTransaction")]
+    EthSignTransaction,
+    /// Subscribe to events (WebSocket only)
+    ///
+    /// - params: `["newHeads" | "logs" | "newPendingTransactions" | "syncing", options?]`;
+    /// - result: `QUANTITY` subscription id;
+    #[serde(rename = "eth_subscribe")]
+    EthSubscribe,
+    /// Synchronization status
+    ///
+    /// - params: `[]`;
+    /// - result: `false` or `{ startingBlock, currentBlock, highestBlock }`;
+    #[serde(rename = "eth_syncing")]
+    EthSyncing,
+    /// Remove the filter
+    ///
+    /// - params: `[QUANTITY filter id]`;
+    /// - result: `bool`;
+    #[serde(rename = "eth_uninstallFilter")]
+    EthUninstallFilter,
+    /// Cancel the subscription (WebSocket only)
+    ///
+    /// - params: `[QUANTITY subscription id]`;
+    /// - result: `bool`;
+    #[serde(rename = "eth_unsubscribe")]
+    EthUnsubscribe,
+    /// Whether the client listens for network connections
+    ///
+    /// - params: `[]`;
+    /// - result: `bool`;
+    #[serde(rename = "net_listening")]
+    NetListening,
+    /// Number of connected peers
+    ///
+    /// - params: `[]`;
+    /// - result: `QUANTITY`;
+    #[serde(rename = "net_peerCount")]
+    NetPeerCount,
+    /// Network id
+    ///
+    /// - params: `[]`;
+    /// - result: `STRING`, decimal;
+    #[serde(rename = "net_version")]
+    NetVersion,
+    /// Client version
+    ///
+    /// - params: `[]`;
+    /// - result: `STRING`;
+    #[serde(rename = "web3_clientVersion")]
+    Web3ClientVersion,
+    /// Keccak-256 hash of the data
+    ///
+    /// - params: `[DATA]`;
+    /// - result: `DATA`, 32 bytes;
+    #[serde(rename = "web3_sha3")]
+    Web3Sha3,
 }
 
 /// JSON RPC request
 ///
-/// ```rust
-/// use ethgen::rpc::{Request, Method, Version};
+/// Built with [`Request::new`] from the params of a method type, which sets
+/// the method name, so a request can not pair a method with the params of
+/// another one.
 ///
+/// ```rust
+/// use ethgen::rpc::{methods::EthGasPrice, Method, Request};
 ///
 ///
-/// let rpc = Request {
-///     jsonrpc: Version::V2,
-///     method: Method::EthGasPrice,
-///     params: [0u8; 0],
-///     id: 1,
-/// };
+/// let rpc = Request::new(1, EthGasPrice {});
+/// assert_eq!(rpc.method(), Method::EthGasPrice);
 ///
 /// let mut buf = [0u8; 128];
 /// let n = serde_json_core::to_slice(&rpc, &mut buf).unwrap();
@@ -50,7 +334,45 @@ pub enum Method {
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Request<T> {
     pub jsonrpc: super::Version,
-    pub method: Method,
+    pub(super) method: Method,
     pub params: T,
     pub id: u64, // TODO: make it U256
 }
+
+impl<T> Request<T> {
+    #[inline(always)]
+    pub const fn method(&self) -> Method {
+        self.method
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_method() {
+        let cases = [
+            (Method::EthGetBlockByNumber, "\"eth_getBlockByNumber\""),
+            (Method::EthFeeHistory, "\"eth_feeHistory\""),
+            (
+                Method::EthMaxPriorityFeePerGas,
+                "\"eth_maxPriorityFeePerGas\"",
+            ),
+            (Method::EthGetProof, "\"eth_getProof\""),
+            (Method::NetVersion, "\"net_version\""),
+            (Method::Web3ClientVersion, "\"web3_clientVersion\""),
+        ];
+
+        let mut buf = [0u8; 64];
+        for (method, expected) in cases {
+            let n = serde_json_core::to_slice(&method, &mut buf).unwrap();
+            assert_eq!(core::str::from_utf8(&buf[..n]).unwrap(), expected);
+
+            let (parsed, _): (Method, usize) = serde_json_core::from_str(expected).unwrap();
+            assert_eq!(parsed, method);
+        }
+
+        assert!(serde_json_core::from_str::<Method>("\"eth_unknown\"").is_err());
+    }
+}
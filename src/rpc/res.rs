@@ -1,5 +1,18 @@
-use super::{Code, Error, Version};
-use serde::{Deserialize, Serialize};
+use super::QuantityValue;
+use super::{Code, Error, Message, Version};
+use crate::{
+    as_quantity, encoding::data, transaction::AccessListBuf, typenum::U32, uint256, Address, Bytes,
+    ListBuf,
+};
+use core::{fmt, marker::PhantomData};
+use serde::{
+    de::{
+        self,
+        value::{MapAccessDeserializer, UnitDeserializer},
+        MapAccess, Unexpected, Visitor,
+    },
+    Deserialize, Deserializer, Serialize,
+};
 
 /// JSON RPC response
 ///
@@ -59,18 +72,56 @@ use serde::{Deserialize, Serialize};
 pub struct Response<T> {
     pub jsonrpc: Version,
     pub id: u64, // TODO: make it U256
+    /// `null` is passed to `T` if it accepts one (an optional result becomes
+    /// `Some(None)`), otherwise it is the same as the missing result
+    #[serde(
+        default = "Option::default",
+        deserialize_with = "result",
+        bound(deserialize = "T: Deserialize<'de>")
+    )]
     pub result: Option<T>,
     pub error: Option<Error>,
 }
 
-impl<'de, T> Response<T> {
+/// Deserialize the result, mapping `null` to `None` when `T` rejects it
+fn result<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct ResultVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for ResultVisitor<T> {
+        type Value = Option<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "JSON RPC result")
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(T::deserialize(UnitDeserializer::<E>::new()).ok())
+        }
+
+        fn visit_some<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            T::deserialize(deserializer).map(Some)
+        }
+    }
+
+    deserializer.deserialize_option(ResultVisitor(PhantomData))
+}
+
+impl<T> Response<T> {
+    /// The error takes precedence over the result, as some nodes send both
+    /// with the `null` result
+    #[allow(clippy::result_large_err)]
     pub fn into_result(self) -> Result<T, Error> {
-        match self.result {
-            Some(result) => Ok(result),
-            None => match self.error {
-                Some(err) => Err(err),
-                None => Err(Error::new(-32700, "unknown")),
-            },
+        match (self.error, self.result) {
+            (Some(err), _) => Err(err),
+            (None, Some(result)) => Ok(result),
+            (None, None) => Err(Error::new(-32700, "unknown")),
         }
     }
 
@@ -88,3 +139,270 @@ impl<'de, T> Response<T> {
         }
     }
 }
+
+/// The `eth_createAccessList` result
+///
+/// The access list is stored in the fixed capacity [`AccessListBuf`], holding
+/// up to `N` addresses and `K` storage keys in total, and is ready to be used
+/// by a transaction via [`AccessListBuf::as_access_list`].
+///
+/// ```rust
+/// use ethgen::{address, rpc::{AccessListResult, Response}};
+///
+///
+/// let json = r#"{
+///     "jsonrpc": "2.0",
+///     "id": 1,
+///     "result": {
+///         "accessList": [
+///             {
+///                 "address": "0xa02457e5dfd32bda5fc7e1f1b008aa5979568150",
+///                 "storageKeys": [
+///                     "0x0000000000000000000000000000000000000000000000000000000000000081"
+///                 ]
+///             }
+///         ],
+///         "gasUsed": "0x125f8"
+///     }
+/// }"#;
+///
+/// let (res, _): (Response<AccessListResult<4, 16>>, usize) =
+///     serde_json_core::from_str(json).unwrap();
+///
+/// let result = res.unwrap();
+/// assert_eq!(result.gas_used, 75_256);
+/// assert_eq!(result.error, None);
+///
+/// let item = result.access_list.as_access_list().iter().next().unwrap();
+/// assert_eq!(item.address, address!("0xa02457e5dfd32bda5fc7e1f1b008aa5979568150"));
+/// assert_eq!(item.storage_keys.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult<const N: usize, const K: usize> {
+    pub access_list: AccessListBuf<N, K>,
+    #[serde(with = "as_quantity")]
+    pub gas_used: u64,
+    /// Execution error message, if the call has reverted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Message>,
+}
+
+/// The `eth_feeHistory` result
+///
+/// The lists hold up to `N` items, note that the base fee lists include the
+/// block following the newest one, so up to `N - 1` blocks may be requested.
+/// The rewards hold up to `P` percentiles per block and are `None` when no
+/// percentiles are requested, the blob fields are `None` before Cancun.
+///
+/// ```rust
+/// use ethgen::rpc::{FeeHistory, QuantityValue, Response};
+///
+///
+/// let json = r#"{"jsonrpc":"2.0","id":1,"result":{
+///     "oldestBlock":"0x13a8c3e",
+///     "baseFeePerGas":["0x3b9aca00","0x3a699d00","0x3b2f2e40"],
+///     "gasUsedRatio":[0.4,0.55],
+///     "reward":[["0x5f5e100"],["0x77359400"]]
+/// }}"#;
+///
+/// let (res, _): (Response<FeeHistory<4, 1>>, usize) = serde_json_core::from_str(json).unwrap();
+///
+/// let history = res.unwrap();
+/// assert_eq!(history.oldest_block, 20614206);
+/// assert_eq!(history.base_fee_per_gas.len(), 3);
+/// assert_eq!(history.gas_used_ratio[1], 0.55);
+/// assert_eq!(history.reward.unwrap()[1][0], QuantityValue(2_000_000_000));
+/// assert_eq!(history.base_fee_per_blob_gas, None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistory<const N: usize, const P: usize> {
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub oldest_block: u64,
+    pub base_fee_per_gas: ListBuf<QuantityValue<u128>, N>,
+    pub gas_used_ratio: ListBuf<f64, N>,
+    /// Blob base fees since Cancun
+    #[serde(default)]
+    pub base_fee_per_blob_gas: Option<ListBuf<QuantityValue<u128>, N>>,
+    /// Blob gas used ratios since Cancun
+    #[serde(default)]
+    pub blob_gas_used_ratio: Option<ListBuf<f64, N>>,
+    /// Priority fees at the requested percentiles of each block
+    #[serde(default)]
+    pub reward: Option<ListBuf<ListBuf<QuantityValue<u128>, P>, N>>,
+}
+
+/// The `eth_getProof` result
+///
+/// Merkle proofs hold up to `N` nodes each, borrowed from the JSON as `0x`
+/// prefixed hex strings, the storage proofs hold up to `K` items.
+///
+/// ```rust
+/// use ethgen::{address, rpc::{AccountProof, Response}, uint256};
+///
+///
+/// let json = r#"{"jsonrpc":"2.0","id":1,"result":{
+///     "address":"0x7f0d15c7faae65896648c8273b6d7e43f58fa842",
+///     "accountProof":["0xf90211a0","0xf90211a1"],
+///     "balance":"0x0",
+///     "codeHash":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+///     "nonce":"0x0",
+///     "storageHash":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+///     "storageProof":[{
+///         "key":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+///         "value":"0x0",
+///         "proof":[]
+///     }]
+/// }}"#;
+///
+/// let (res, _): (Response<AccountProof<8, 2>>, usize) = serde_json_core::from_str(json).unwrap();
+///
+/// let proof = res.unwrap();
+/// assert_eq!(proof.address, address!("0x7f0d15c7faae65896648c8273b6d7e43f58fa842"));
+/// assert_eq!(proof.account_proof[1], "0xf90211a1");
+/// assert_eq!(proof.storage_proof[0].value, uint256::ZERO);
+/// assert!(proof.storage_proof[0].proof.is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof<'a, const N: usize, const K: usize> {
+    pub address: Address,
+    #[serde(borrow)]
+    pub account_proof: ListBuf<&'a str, N>,
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub balance: uint256,
+    #[serde(deserialize_with = "data")]
+    pub code_hash: Bytes<U32>,
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub nonce: u64,
+    #[serde(deserialize_with = "data")]
+    pub storage_hash: Bytes<U32>,
+    #[serde(borrow)]
+    pub storage_proof: ListBuf<StorageProof<'a, N>, K>,
+}
+
+/// Storage slot proof of the `eth_getProof` result
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct StorageProof<'a, const N: usize> {
+    /// Hex encoded storage key as requested
+    pub key: &'a str,
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub value: uint256,
+    #[serde(borrow)]
+    pub proof: ListBuf<&'a str, N>,
+}
+
+/// The `eth_syncing` result, `false` or the progress of the synchronization
+///
+/// Deserialization relies on `deserialize_ignored_any` dispatching on the
+/// type of the value, the way `serde_json_core` does, see
+/// [`BlockId`](super::BlockId). Formats which skip the ignored value instead,
+/// such as `serde_json`, always yield [`SyncStatus::Synced`].
+///
+/// ```rust
+/// use ethgen::rpc::{Response, SyncStatus};
+///
+///
+/// let json = r#"{"jsonrpc":"2.0","id":1,"result":false}"#;
+/// let (res, _): (Response<SyncStatus>, usize) = serde_json_core::from_str(json).unwrap();
+/// assert_eq!(res.unwrap(), SyncStatus::Synced);
+///
+/// let json = r#"{"jsonrpc":"2.0","id":1,"result":{
+///     "startingBlock":"0x384",
+///     "currentBlock":"0x386",
+///     "highestBlock":"0x454"
+/// }}"#;
+/// let (res, _): (Response<SyncStatus>, usize) = serde_json_core::from_str(json).unwrap();
+///
+/// match res.unwrap() {
+///     SyncStatus::Syncing(progress) => assert_eq!(progress.highest_block, 0x454),
+///     SyncStatus::Synced => panic!("expected the progress"),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SyncStatus {
+    /// The node is not syncing
+    #[default]
+    Synced,
+    Syncing(SyncProgress),
+}
+
+/// Synchronization progress of the `eth_syncing` result, the client specific
+/// fields are ignored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncProgress {
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub starting_block: u64,
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub current_block: u64,
+    #[serde(deserialize_with = "as_quantity::deserialize")]
+    pub highest_block: u64,
+}
+
+impl<'de> Deserialize<'de> for SyncStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SyncStatusVisitor;
+
+        impl<'de> Visitor<'de> for SyncStatusVisitor {
+            type Value = SyncStatus;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "false or synchronization progress object")
+            }
+
+            // `serde_json_core` ends up here for `false`
+            fn visit_unit<E: de::Error>(self) -> Result<SyncStatus, E> {
+                Ok(SyncStatus::Synced)
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> Result<SyncStatus, E> {
+                match v {
+                    false => Ok(SyncStatus::Synced),
+                    true => Err(E::invalid_value(Unexpected::Bool(v), &self)),
+                }
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<SyncStatus, A::Error> {
+                SyncProgress::deserialize(MapAccessDeserializer::new(map)).map(SyncStatus::Syncing)
+            }
+        }
+
+        deserializer.deserialize_ignored_any(SyncStatusVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_null_result() {
+        // Optional result
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":null}"#;
+        let (res, _): (Response<Option<u64>>, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.result, Some(None));
+        assert_eq!(res.unwrap(), None);
+
+        // Unit result
+        let (res, _): (Response<()>, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.unwrap(), ());
+
+        // Error response carrying the `null` result
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":null,"error":{"code":-32000,"message":"execution reverted"}}"#;
+        let (res, _): (Response<u64>, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.result, None);
+        assert_eq!(res.error_code(), Some(Code::ServerError(-32000)));
+
+        let (res, _): (Response<Option<u64>>, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.result, Some(None));
+        assert!(res.into_result().is_err());
+
+        // Missing result
+        let json = r#"{"jsonrpc":"2.0","id":1}"#;
+        let (res, _): (Response<Option<u64>>, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(res.result, None);
+        assert!(res.into_result().is_err());
+    }
+}
@@ -0,0 +1,325 @@
+//! secp256k1 field, scalar and point arithmetic
+//!
+//! Curve `y^2 = x^3 + 7` over the prime field, parameters are taken from
+//! <https://www.secg.org/sec2-v2.pdf> (section 2.4.1). Field elements and
+//! scalars are kept in Montgomery form using `crypto-bigint` residues, points
+//! are kept in Jacobian coordinates to avoid inversion on every operation.
+
+use crypto_bigint::{
+    impl_modulus,
+    modular::constant_mod::{Residue, ResidueParams},
+    subtle::{Choice, ConditionallySelectable, ConstantTimeEq},
+    U256,
+};
+
+impl_modulus!(
+    FieldModulus,
+    U256,
+    "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F"
+);
+
+impl_modulus!(
+    OrderModulus,
+    U256,
+    "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"
+);
+
+/// Element of the prime field
+pub(crate) type Fe = Residue<FieldModulus, { U256::LIMBS }>;
+
+/// Element of the scalar field (modulo curve order)
+pub(crate) type Scalar = Residue<OrderModulus, { U256::LIMBS }>;
+
+/// Field prime `p`
+pub(crate) const P: U256 = FieldModulus::MODULUS;
+
+/// Curve order `n`
+pub(crate) const N: U256 = OrderModulus::MODULUS;
+
+/// Half of the curve order, the upper bound of low `s` values (EIP-2)
+pub(crate) const HALF_N: U256 = N.shr_vartime(1);
+
+/// Curve constant `b`
+const B: Fe = Fe::new(&U256::from_u8(7));
+
+/// Reduce an integer below `2^256` modulo curve order
+#[inline(always)]
+pub(crate) fn reduce(value: U256) -> U256 {
+    match value >= N {
+        true => value.wrapping_sub(&N),
+        false => value,
+    }
+}
+
+/// Curve point in affine coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Affine {
+    pub x: U256,
+    pub y: U256,
+}
+
+impl Affine {
+    /// Check if the coordinates satisfy the curve equation
+    pub fn is_on_curve(&self) -> bool {
+        if self.x >= P || self.y >= P {
+            return false;
+        }
+
+        let (x, y) = (Fe::new(&self.x), Fe::new(&self.y));
+        y.square() == x.square().mul(&x).add(&B)
+    }
+
+    /// Find the point with given `x` and `y` parity
+    ///
+    /// Square root is computed as `a^((p + 1) / 4)`, since `p = 3 mod 4`.
+    pub fn decompress(x: &U256, odd: bool) -> Option<Self> {
+        const EXP: U256 = P.wrapping_add(&U256::ONE).shr_vartime(2);
+
+        if x >= &P {
+            return None;
+        }
+
+        let fx = Fe::new(x);
+        let rhs = fx.square().mul(&fx).add(&B);
+        let root = rhs.pow(&EXP);
+
+        if root.square() != rhs {
+            return None;
+        }
+
+        let y = root.retrieve();
+        let y = match (y.as_words()[0] & 1 == 1) == odd {
+            true => y,
+            false => root.neg().retrieve(),
+        };
+
+        Some(Self { x: *x, y })
+    }
+}
+
+/// Curve point in Jacobian coordinates `(X / Z^2, Y / Z^3)`
+///
+/// The point at infinity is represented with `Z = 0`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Point {
+    x: Fe,
+    y: Fe,
+    z: Fe,
+}
+
+impl Point {
+    pub const INFINITY: Self = Self {
+        x: Fe::ONE,
+        y: Fe::ONE,
+        z: Fe::ZERO,
+    };
+
+    /// Base point `G`
+    pub const GENERATOR: Self = Self {
+        x: Fe::new(&U256::from_be_hex(
+            "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        )),
+        y: Fe::new(&U256::from_be_hex(
+            "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+        )),
+        z: Fe::ONE,
+    };
+
+    #[inline(always)]
+    pub fn from_affine(point: &Affine) -> Self {
+        Self {
+            x: Fe::new(&point.x),
+            y: Fe::new(&point.y),
+            z: Fe::ONE,
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_infinity(&self) -> Choice {
+        self.z.ct_eq(&Fe::ZERO)
+    }
+
+    /// Convert to affine coordinates, `None` for the point at infinity
+    pub fn to_affine(self) -> Option<Affine> {
+        let (z_inv, exists) = self.z.invert();
+        if !bool::from(exists) {
+            return None;
+        }
+
+        let z_inv2 = z_inv.square();
+        Some(Affine {
+            x: self.x.mul(&z_inv2).retrieve(),
+            y: self.y.mul(&z_inv2).mul(&z_inv).retrieve(),
+        })
+    }
+
+    /// Point doubling, `dbl-2009-l` formulas for `a = 0`
+    pub fn double(&self) -> Self {
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = b.square();
+
+        let d = self.x.add(&b).square().sub(&a).sub(&c);
+        let d = d.add(&d);
+        let e = a.add(&a).add(&a);
+        let f = e.square();
+
+        let x = f.sub(&d).sub(&d);
+        let c8 = c.add(&c);
+        let c8 = c8.add(&c8);
+        let c8 = c8.add(&c8);
+        let y = e.mul(&d.sub(&x)).sub(&c8);
+        let z = self.y.mul(&self.z);
+
+        Self { x, y, z: z.add(&z) }
+    }
+
+    /// Point addition, `add-2007-bl` formulas
+    ///
+    /// The special cases (infinity operands, doubling and opposite points)
+    /// are handled by constant-time selection rather than branching.
+    pub fn add(&self, other: &Self) -> Self {
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+        let u1 = self.x.mul(&z2z2);
+        let u2 = other.x.mul(&z1z1);
+        let s1 = self.y.mul(&other.z).mul(&z2z2);
+        let s2 = other.y.mul(&self.z).mul(&z1z1);
+
+        let h = u2.sub(&u1);
+        let r = s2.sub(&s1);
+
+        let same_x = h.ct_eq(&Fe::ZERO);
+        let same_y = r.ct_eq(&Fe::ZERO);
+
+        let i = h.add(&h).square();
+        let j = h.mul(&i);
+        let r = r.add(&r);
+        let v = u1.mul(&i);
+
+        let x = r.square().sub(&j).sub(&v).sub(&v);
+        let s1j = s1.mul(&j);
+        let y = r.mul(&v.sub(&x)).sub(&s1j).sub(&s1j);
+        let z = self.z.add(&other.z).square().sub(&z1z1).sub(&z2z2).mul(&h);
+
+        let mut sum = Self { x, y, z };
+        sum.conditional_assign(&Self::INFINITY, same_x & !same_y);
+        sum.conditional_assign(&self.double(), same_x & same_y);
+        sum.conditional_assign(self, other.is_infinity());
+        sum.conditional_assign(other, self.is_infinity());
+        sum
+    }
+
+    /// Scalar multiplication
+    ///
+    /// Montgomery ladder: every bit of the scalar takes one addition and one
+    /// doubling, the operands are swapped by constant-time selection, so
+    /// neither the control flow nor the memory access depend on the scalar.
+    pub fn mul(&self, k: &U256) -> Self {
+        let mut r0 = Self::INFINITY;
+        let mut r1 = *self;
+        let mut i = U256::BITS;
+
+        while i > 0 {
+            i -= 1;
+            let bit = Choice::from(k.bit(i));
+
+            Self::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = r0.add(&r1);
+            r0 = r0.double();
+            Self::conditional_swap(&mut r0, &mut r1, bit);
+        }
+
+        r0
+    }
+}
+
+impl ConditionallySelectable for Point {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            x: Fe::conditional_select(&a.x, &b.x, choice),
+            y: Fe::conditional_select(&a.y, &b.y, choice),
+            z: Fe::conditional_select(&a.z, &b.z, choice),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generator() {
+        let g = Point::GENERATOR.to_affine().unwrap();
+        assert!(g.is_on_curve());
+
+        // n * G = O and (n - 1) * G = -G
+        assert!(bool::from(Point::GENERATOR.mul(&N).is_infinity()));
+
+        let neg = Point::GENERATOR.mul(&N.wrapping_sub(&U256::ONE));
+        let neg = neg.to_affine().unwrap();
+        assert_eq!(neg.x, g.x);
+        assert_eq!(neg.y, P.wrapping_sub(&g.y));
+
+        let doubled = Point::GENERATOR.double().to_affine().unwrap();
+        let added = Point::GENERATOR.add(&Point::GENERATOR).to_affine().unwrap();
+        assert_eq!(doubled, added);
+        assert_eq!(
+            doubled.x,
+            U256::from_be_hex("C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5")
+        );
+
+        assert_eq!(Affine::decompress(&g.x, false), Some(g));
+        assert_eq!(Affine::decompress(&neg.x, true), Some(neg));
+        assert_eq!(Affine::decompress(&U256::from_u8(5), false), None);
+
+        // Special cases of the addition
+        let infinity = Point::INFINITY;
+        let neg = Point::from_affine(&neg);
+        assert_eq!(infinity.add(&Point::GENERATOR).to_affine(), Some(g));
+        assert_eq!(Point::GENERATOR.add(&infinity).to_affine(), Some(g));
+        assert!(bool::from(infinity.add(&infinity).is_infinity()));
+        assert!(bool::from(Point::GENERATOR.add(&neg).is_infinity()));
+        assert!(bool::from(infinity.mul(&U256::from_u8(5)).is_infinity()));
+        assert!(bool::from(Point::GENERATOR.mul(&U256::ZERO).is_infinity()));
+    }
+
+    #[test]
+    fn test_mul() {
+        // Naive double-and-add over the public scalar
+        fn mul_vartime(point: &Point, k: &U256) -> Point {
+            (0..U256::BITS).rev().fold(Point::INFINITY, |acc, i| {
+                let acc = acc.double();
+                match k.bit_vartime(i) {
+                    true => acc.add(point),
+                    false => acc,
+                }
+            })
+        }
+
+        let point = Point::GENERATOR.mul(&U256::from_u8(3));
+        let scalars = [
+            U256::ONE,
+            U256::from_u8(2),
+            U256::from_u64(0xdead_beef),
+            HALF_N,
+            N.wrapping_sub(&U256::from_u8(2)),
+            U256::MAX,
+        ];
+
+        for k in scalars {
+            assert_eq!(
+                point.mul(&k).to_affine(),
+                mul_vartime(&point, &k).to_affine()
+            );
+        }
+
+        // 2 * G, as well as 3 * G computed by the addition
+        let doubled = Point::GENERATOR.mul(&U256::from_u8(2)).to_affine();
+        assert_eq!(doubled, Point::GENERATOR.double().to_affine());
+        assert_eq!(
+            point.to_affine(),
+            Point::GENERATOR.double().add(&Point::GENERATOR).to_affine()
+        );
+    }
+}
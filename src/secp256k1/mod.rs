@@ -0,0 +1,516 @@
+//! secp256k1 ECDSA
+//!
+//! Signing of 32-byte message hashes (prehashes) with the curve used by
+//! Ethereum, implemented on top of `crypto-bigint` and fully `no_std`:
+//!
+//! - nonces are derived deterministically as per RFC 6979, no random number
+//!   generator is required and the same key and hash always produce the same
+//!   signature;
+//! - `s` is normalized to the lower half of the curve order as required by
+//!   [EIP-2](https://eips.ethereum.org/EIPS/eip-2);
+//! - the signature is recoverable, carrying the parity of the `R` point `y`
+//!   coordinate;
+//! - secret key material is zeroized on drop;
+//!
+//! Signer address can be recovered with [`recover`], which follows the
+//! `ecrecover` semantics, with the malleable high `s` signatures rejected.
+//!
+//! ```rust
+//! use ethgen::{address, keccak256, secp256k1::{self, SigningKey}};
+//!
+//!
+//! let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+//! assert_eq!(key.address(), address!("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"));
+//!
+//! let hash = keccak256(b"ethgen rules");
+//! let signature = key.sign_prehash(&hash.into_array());
+//!
+//! assert_eq!(secp256k1::recover(&hash.into_array(), &signature), Ok(key.address()));
+//! ```
+//!
+//! *Important*: the arithmetic is not audited. The scalar multiplication is a
+//! Montgomery ladder with constant-time selection, the field and scalar
+//! arithmetic relies on the `crypto-bigint` constant-time residues.
+
+mod curve;
+mod rfc6979;
+
+use crate::{keccak::keccak256, Address, Bytes, Signature};
+use core::fmt;
+use crypto_bigint::{Encoding, U256};
+use curve::{reduce, Affine, Point, Scalar, HALF_N, N};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// The error type returned by secp256k1 operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Secret key is zero or not less than the curve order
+    InvalidSecretKey,
+    /// Public key is not a valid curve point
+    InvalidPublicKey,
+    /// Signature `r` or `s` are out of range, or `s` is high
+    InvalidSignature,
+    /// Signature does not match the key and the message hash
+    VerificationFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSecretKey => write!(f, "invalid secp256k1 secret key"),
+            Self::InvalidPublicKey => write!(f, "invalid secp256k1 public key"),
+            Self::InvalidSignature => write!(f, "invalid signature"),
+            Self::VerificationFailed => write!(f, "signature verification failed"),
+        }
+    }
+}
+
+/// Uncompressed public key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(Affine);
+
+impl PublicKey {
+    /// Parse SEC1 uncompressed encoding: `0x04 || x || y`
+    pub fn from_uncompressed(bytes: &[u8; 65]) -> Result<Self, Error> {
+        let point = Affine {
+            x: U256::from_be_slice(&bytes[1..33]),
+            y: U256::from_be_slice(&bytes[33..]),
+        };
+
+        match bytes[0] == 0x04 && point.is_on_curve() {
+            true => Ok(Self(point)),
+            false => Err(Error::InvalidPublicKey),
+        }
+    }
+
+    /// SEC1 uncompressed encoding: `0x04 || x || y`
+    pub fn to_uncompressed(&self) -> [u8; 65] {
+        let mut bytes = [0x04u8; 65];
+        bytes[1..33].copy_from_slice(&self.0.x.to_be_bytes());
+        bytes[33..].copy_from_slice(&self.0.y.to_be_bytes());
+        bytes
+    }
+
+    /// Address derived from the key: last 20 bytes of `keccak256(x || y)`
+    pub fn to_address(&self) -> Address {
+        let hash = keccak256(&self.to_uncompressed()[1..]);
+        Address::from(*Bytes::from_slice(&hash[12..]))
+    }
+}
+
+/// Secret key able to sign message hashes
+///
+/// The key is zeroized on drop and is never shown by `Debug`.
+#[derive(Clone)]
+pub struct SigningKey {
+    secret: [u8; 32],
+}
+
+impl SigningKey {
+    /// Create a key from the 32 bytes Big Endian secret
+    ///
+    /// The secret must be in range `[1, n - 1]`, where `n` is the curve order.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, Error> {
+        let d = U256::from_be_bytes(*bytes);
+
+        match d == U256::ZERO || d >= N {
+            true => Err(Error::InvalidSecretKey),
+            false => Ok(Self { secret: *bytes }),
+        }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        let mut d = U256::from_be_bytes(self.secret);
+        let point = Point::GENERATOR.mul(&d).to_affine();
+        wipe(&mut d);
+
+        // The secret is in range, so the product is never the infinity
+        match point {
+            Some(point) => PublicKey(point),
+            None => unreachable!(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn address(&self) -> Address {
+        self.public_key().to_address()
+    }
+
+    /// Sign a 32 bytes message hash
+    ///
+    /// The hash is interpreted as a Big Endian integer reduced modulo curve
+    /// order, the result is deterministic and has low `s` value.
+    pub fn sign_prehash(&self, prehash: &[u8; 32]) -> Signature {
+        let mut d = Scalar::new(&U256::from_be_bytes(self.secret));
+        let z = reduce(U256::from_be_bytes(*prehash));
+
+        let mut nonce = rfc6979::Nonce::new(&self.secret, &z.to_be_bytes());
+
+        loop {
+            let mut k = nonce.next();
+            let signature = sign_with_nonce(&d, &z, &k);
+            wipe(&mut k);
+
+            if let Some(signature) = signature {
+                wipe(&mut d);
+                return signature;
+            }
+        }
+    }
+}
+
+/// Sign with the nonce candidate, `None` if the candidate is to be rejected
+fn sign_with_nonce(d: &Scalar, z: &U256, k: &U256) -> Option<Signature> {
+    // `k` is in range, so the product is never the infinity
+    let point = Point::GENERATOR.mul(k).to_affine()?;
+
+    let r = reduce(point.x);
+    if r == U256::ZERO {
+        return None;
+    }
+
+    let (mut k_inv, _) = Scalar::new(k).invert();
+    let s = k_inv
+        .mul(&Scalar::new(z).add(&Scalar::new(&r).mul(d)))
+        .retrieve();
+    wipe(&mut k_inv);
+
+    if s == U256::ZERO {
+        return None;
+    }
+
+    // Negating `s` corresponds to negating `R`, flipping the parity
+    let odd = point.y.as_words()[0] & 1 == 1;
+    let (s, y_parity) = match s > HALF_N {
+        true => (N.wrapping_sub(&s), !odd),
+        false => (s, odd),
+    };
+
+    Some(Signature {
+        r: Bytes::from_array(r.to_be_bytes()),
+        s: Bytes::from_array(s.to_be_bytes()),
+        y_parity,
+    })
+}
+
+/// Zeroize the secret integer or scalar, which are plain arrays of limbs
+#[inline(always)]
+fn wipe<T: Copy>(value: &mut T) {
+    unsafe { zeroize::zeroize_flat_type(value) }
+}
+
+impl Drop for SigningKey {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SigningKey {}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningKey").finish_non_exhaustive()
+    }
+}
+
+/// Sign a 32 bytes message hash with the 32 bytes secret key
+///
+/// ```rust
+/// use ethgen::{secp256k1, unhex};
+///
+///
+/// let hash = unhex!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53");
+/// let signature = secp256k1::sign(&[0x46u8; 32], &hash).unwrap();
+///
+/// let r: [u8; 32] = unhex!("28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276");
+/// assert_eq!(r, signature.r.into_array());
+/// ```
+pub fn sign(secret: &[u8; 32], prehash: &[u8; 32]) -> Result<Signature, Error> {
+    SigningKey::from_bytes(secret).map(|key| key.sign_prehash(prehash))
+}
+
+/// Parse `r` and `s` checking the `ecrecover` constraints
+///
+/// Both must be in range `[1, n - 1]`, and `s` must not exceed `n / 2`.
+fn scalars(signature: &Signature) -> Result<(U256, U256), Error> {
+    let r = U256::from_be_slice(&signature.r);
+    let s = U256::from_be_slice(&signature.s);
+
+    match r == U256::ZERO || r >= N || s == U256::ZERO || s > HALF_N {
+        true => Err(Error::InvalidSignature),
+        false => Ok((r, s)),
+    }
+}
+
+/// Recover the public key of the message hash signer
+///
+/// Computed as `r^-1 * (s * R - z * G)`, where `R` is the point with `x = r`
+/// and the `y` parity given by the recovery id.
+pub fn recover_public_key(prehash: &[u8; 32], signature: &Signature) -> Result<PublicKey, Error> {
+    let (r, s) = scalars(signature)?;
+    let z = reduce(U256::from_be_bytes(*prehash));
+
+    let point = Affine::decompress(&r, signature.y_parity).ok_or(Error::InvalidSignature)?;
+
+    let (r_inv, _) = Scalar::new(&r).invert();
+    let u1 = Scalar::new(&z).neg().mul(&r_inv).retrieve();
+    let u2 = Scalar::new(&s).mul(&r_inv).retrieve();
+
+    let key = Point::GENERATOR
+        .mul(&u1)
+        .add(&Point::from_affine(&point).mul(&u2));
+
+    key.to_affine()
+        .map(PublicKey)
+        .ok_or(Error::InvalidSignature)
+}
+
+/// Recover the address of the message hash signer
+///
+/// Same as the `ecrecover` precompile, except high `s` values are rejected.
+/// The `v` validation happens when the [`Signature`] is constructed.
+///
+/// ```rust
+/// use ethgen::{address, from_hex, secp256k1, unhex, Signature};
+///
+///
+/// let hash = unhex!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53");
+/// let signature = Signature {
+///     r: from_hex("0x28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276"),
+///     s: from_hex("0x67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"),
+///     y_parity: false,
+/// };
+///
+/// assert_eq!(
+///     secp256k1::recover(&hash, &signature),
+///     Ok(address!("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F")),
+/// );
+/// ```
+#[inline(always)]
+pub fn recover(prehash: &[u8; 32], signature: &Signature) -> Result<Address, Error> {
+    recover_public_key(prehash, signature).map(|key| key.to_address())
+}
+
+/// Verify the message hash signature against the public key
+///
+/// The recovery id is not used, `r` and `s` are subject to the same checks
+/// as in [`recover`].
+pub fn verify(
+    public_key: &PublicKey,
+    prehash: &[u8; 32],
+    signature: &Signature,
+) -> Result<(), Error> {
+    let (r, s) = scalars(signature)?;
+    let z = reduce(U256::from_be_bytes(*prehash));
+
+    let (s_inv, _) = Scalar::new(&s).invert();
+    let u1 = Scalar::new(&z).mul(&s_inv).retrieve();
+    let u2 = Scalar::new(&r).mul(&s_inv).retrieve();
+
+    let point = Point::GENERATOR
+        .mul(&u1)
+        .add(&Point::from_affine(&public_key.0).mul(&u2));
+
+    match point.to_affine() {
+        Some(point) if reduce(point.x) == r => Ok(()),
+        _ => Err(Error::VerificationFailed),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{address, from_hex, unhex};
+
+    #[test]
+    fn test_secret_range() {
+        assert_eq!(
+            SigningKey::from_bytes(&[0u8; 32]).unwrap_err(),
+            Error::InvalidSecretKey
+        );
+        assert_eq!(
+            SigningKey::from_bytes(&N.to_be_bytes()).unwrap_err(),
+            Error::InvalidSecretKey
+        );
+        assert!(SigningKey::from_bytes(&N.wrapping_sub(&U256::ONE).to_be_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_sign_eip155() {
+        // Signing example from EIP-155
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let hash = unhex!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53");
+
+        let signature = key.sign_prehash(&hash);
+        let r: [u8; 32] =
+            unhex!("28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276");
+        let s: [u8; 32] =
+            unhex!("67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83");
+
+        assert_eq!(signature.r.into_array(), r);
+        assert_eq!(signature.s.into_array(), s);
+        assert!(!signature.y_parity);
+        assert_eq!(signature.eip155_v(1), 37);
+
+        // Deterministic
+        assert_eq!(key.sign_prehash(&hash), signature);
+    }
+
+    #[test]
+    fn test_sign_eip2098() {
+        // EIP-2098 example: `eth_sign` of "Hello World"
+        let secret = unhex!("1234567890123456789012345678901234567890123456789012345678901234");
+        let key = SigningKey::from_bytes(&secret).unwrap();
+        let hash = keccak256(b"\x19Ethereum Signed Message:\n11Hello World").into_array();
+        let compact: [u8; 64] = unhex!(
+            "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+            7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"
+        );
+
+        assert_eq!(key.sign_prehash(&hash).to_compact(), compact);
+    }
+
+    #[test]
+    fn test_low_s() {
+        let key = SigningKey::from_bytes(&[0x01u8; 32]).unwrap();
+
+        for i in 0u8..16 {
+            let hash = keccak256(&[i]).into_array();
+            let s = U256::from_be_slice(&key.sign_prehash(&hash).s);
+            assert!(s <= HALF_N && s != U256::ZERO);
+        }
+    }
+
+    fn signature(r: &str, s: &str, v: u64) -> Signature {
+        let (y_parity, _) = Signature::parse_v(v).unwrap();
+        Signature::new(from_hex(r), from_hex(s), y_parity)
+    }
+
+    #[test]
+    fn test_recover() {
+        // go-ethereum crypto test vector
+        let hash = unhex!("ce0677bb30baa8cf067c88db9811f4333d131bf8bcf12fe7065d211dce971008");
+        let sig = signature(
+            "90f27b8b488db00b00606796d2987f6a5f59ae62ea05effe84fef5b8b0e54998",
+            "4a691139ad57a3f0b906637673aa2f63d1f55cb1a69199d4009eea23ceaddc93",
+            1,
+        );
+        let public_key: [u8; 65] = unhex!(
+            "04e32df42865e97135acfb65f3bae71bdc86f4d49150ad6a440b6f15878109880a\
+            0a2b2667f7e725ceea70c673093bf67663e0312623c8e091b13cf2c0f11ef652"
+        );
+
+        let key = recover_public_key(&hash, &sig).unwrap();
+        assert_eq!(key.to_uncompressed(), public_key);
+        assert_eq!(PublicKey::from_uncompressed(&public_key), Ok(key));
+        assert_eq!(verify(&key, &hash, &sig), Ok(()));
+
+        // Wrong parity recovers some other key
+        let flipped = Signature {
+            y_parity: false,
+            ..sig
+        };
+        assert_ne!(recover_public_key(&hash, &flipped), Ok(key));
+    }
+
+    #[test]
+    fn test_address() {
+        // go-ethereum crypto test key
+        let secret = unhex!("289c2857d4598e37fb9647507e47a309d6133539bf21a8b9cb6df88fd5232032");
+        let key = SigningKey::from_bytes(&secret).unwrap();
+        assert_eq!(
+            key.address(),
+            address!("0x970E8128AB834E8EAC17Ab8E3812F010678CF791")
+        );
+
+        let hash = keccak256(b"foo").into_array();
+        assert_eq!(recover(&hash, &key.sign_prehash(&hash)), Ok(key.address()));
+    }
+
+    #[test]
+    fn test_recover_ecrecover_vector() {
+        // ecRecover precompile vector, the signature has high `s` and is
+        // only accepted in the normalized form: `s' = n - s` with flipped `v`
+        let hash = unhex!("18c547e4f7b0f325ad1e56f57e26c745b09a3e503d86e00e5255ff7f715d3d1c");
+        let sig = signature(
+            "73b1693892219d736caba55bdb67216e485557ea6b6af75f37096c9aa6a5a75f",
+            "eeb940b1d03b21e36b0e47e79769f095fe2ab855bd91e3a38756b7d75a9c4549",
+            28,
+        );
+        assert_eq!(recover(&hash, &sig), Err(Error::InvalidSignature));
+
+        let s = N.wrapping_sub(&U256::from_be_slice(&sig.s));
+        let sig = Signature {
+            s: Bytes::from_array(s.to_be_bytes()),
+            y_parity: false,
+            ..sig
+        };
+        assert_eq!(
+            recover(&hash, &sig),
+            Ok(address!("0xa94f5374Fce5edBC8E2a8697C15331677e6EbF0B"))
+        );
+    }
+
+    #[test]
+    fn test_recover_invalid() {
+        let hash = [0x11u8; 32];
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let sig = key.sign_prehash(&hash);
+
+        // `ecrecover` only accepts 27 and 28
+        let mut bytes = sig.to_bytes();
+        for v in [2, 3, 29, 30] {
+            bytes[64] = v;
+            assert!(Signature::from_bytes(&bytes).is_err());
+        }
+
+        let zero = Bytes::default();
+        let n = Bytes::from_array(N.to_be_bytes());
+        for sig in [
+            Signature { r: zero, ..sig },
+            Signature { s: zero, ..sig },
+            Signature { r: n, ..sig },
+            Signature { s: n, ..sig },
+        ] {
+            assert_eq!(recover(&hash, &sig), Err(Error::InvalidSignature));
+            assert_eq!(
+                verify(&key.public_key(), &hash, &sig),
+                Err(Error::InvalidSignature)
+            );
+        }
+
+        // `r` is not a valid `x` coordinate
+        let sig = Signature {
+            r: Bytes::from_array(U256::from_u8(5).to_be_bytes()),
+            ..sig
+        };
+        assert_eq!(recover(&hash, &sig), Err(Error::InvalidSignature));
+    }
+
+    #[test]
+    fn test_verify() {
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let public_key = key.public_key();
+
+        for i in 0u8..8 {
+            let hash = keccak256(&[i]).into_array();
+            let sig = key.sign_prehash(&hash);
+
+            assert_eq!(verify(&public_key, &hash, &sig), Ok(()));
+            assert_eq!(recover(&hash, &sig), Ok(key.address()));
+
+            let other = keccak256(&[i, i]).into_array();
+            assert_eq!(
+                verify(&public_key, &other, &sig),
+                Err(Error::VerificationFailed)
+            );
+        }
+
+        let mut bytes = public_key.to_uncompressed();
+        bytes[64] ^= 1;
+        assert_eq!(
+            PublicKey::from_uncompressed(&bytes),
+            Err(Error::InvalidPublicKey)
+        );
+    }
+}
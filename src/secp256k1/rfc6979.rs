@@ -0,0 +1,71 @@
+//! Deterministic nonce generation
+//!
+//! HMAC-SHA256 based generation of the `k` value as described by
+//! [RFC 6979](https://datatracker.ietf.org/doc/html/rfc6979#section-3.2),
+//! which makes the signature a pure function of the key and the message and
+//! removes the need of a random number generator.
+
+use super::curve::N;
+use crypto_bigint::{Encoding, U256};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn hmac(key: &[u8; 32], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    parts.iter().for_each(|part| mac.update(part));
+
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+/// Stream of nonce candidates for the given key and message
+///
+/// The internal state is zeroized on drop.
+pub(crate) struct Nonce {
+    k: [u8; 32],
+    v: [u8; 32],
+}
+
+impl Nonce {
+    /// Initialize the generator with the secret key and the message hash
+    /// reduced modulo curve order (`bits2octets(h1)`)
+    pub fn new(secret: &[u8; 32], hash: &[u8; 32]) -> Self {
+        let mut v = [0x01u8; 32];
+        let mut k = [0x00u8; 32];
+
+        k = hmac(&k, &[&v, &[0x00], secret, hash]);
+        v = hmac(&k, &[&v]);
+        k = hmac(&k, &[&v, &[0x01], secret, hash]);
+        v = hmac(&k, &[&v]);
+
+        Self { k, v }
+    }
+
+    /// Next nonce in range `[1, n - 1]`
+    pub fn next(&mut self) -> U256 {
+        loop {
+            self.v = hmac(&self.k, &[&self.v]);
+            let candidate = U256::from_be_bytes(self.v);
+
+            // Prepare the state for the next candidate, which is requested
+            // if this one is out of range or produces an invalid signature
+            self.k = hmac(&self.k, &[&self.v, &[0x00]]);
+            self.v = hmac(&self.k, &[&self.v]);
+
+            if candidate != U256::ZERO && candidate < N {
+                return candidate;
+            }
+        }
+    }
+}
+
+impl Drop for Nonce {
+    fn drop(&mut self) {
+        self.k.zeroize();
+        self.v.zeroize();
+    }
+}
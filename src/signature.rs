@@ -0,0 +1,351 @@
+use crate::{
+    as_hex,
+    encoding::Data,
+    typenum::{U32, U64, U65},
+    Bytes,
+};
+use core::fmt;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The error type returned when a signature `v` value is not recognized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureError {
+    /// `v` is neither a parity, a legacy nor an EIP-155 value
+    InvalidV,
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidV => write!(f, "invalid signature v value"),
+        }
+    }
+}
+
+/// Recoverable ECDSA signature
+///
+/// The single representation shared by the signer, transactions and RPC.
+/// Only the `y` parity of the `R` point is stored, while the `v` value is
+/// produced in the form required by the context:
+///
+/// - `0` / `1` for typed transactions (`yParity`);
+/// - `27` / `28` for the legacy form (`eth_sign`, `ecrecover`);
+/// - `chainId * 2 + 35` / `36` for EIP-155 legacy transactions;
+///
+/// Serialized as the `0x` prefixed hex of the 65 bytes `r || s || v` form,
+/// with the legacy `v`.
+///
+/// ```rust
+/// use ethgen::{from_hex, Signature, typenum::U65, Bytes};
+///
+///
+/// let bytes: Bytes<U65> = from_hex("0x\
+///     28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276\
+///     67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83\
+///     1b");
+///
+/// let signature = Signature::try_from(bytes).unwrap();
+/// assert!(!signature.y_parity);
+/// assert_eq!(signature.v(), 27);
+/// assert_eq!(signature.eip155_v(1), 37);
+///
+/// assert_eq!(Signature::parse_v(37), Ok((false, Some(1))));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Signature {
+    pub r: Bytes<U32>,
+    pub s: Bytes<U32>,
+    pub y_parity: bool,
+}
+
+impl Signature {
+    /// Largest chain id allowed by
+    /// [EIP-2294](https://eips.ethereum.org/EIPS/eip-2294), so that the
+    /// EIP-155 `v` value fits into `u64`
+    pub const MAX_CHAIN_ID: u64 = u64::MAX / 2 - 36;
+
+    #[inline(always)]
+    pub const fn new(r: Bytes<U32>, s: Bytes<U32>, y_parity: bool) -> Self {
+        Self { r, s, y_parity }
+    }
+
+    /// Legacy `v` value: `27` or `28`
+    #[inline(always)]
+    pub const fn v(&self) -> u8 {
+        27 + self.y_parity as u8
+    }
+
+    /// EIP-155 `v` value: `chainId * 2 + 35` or `chainId * 2 + 36`
+    ///
+    /// ## Panics
+    ///
+    /// - if the chain id exceeds [`Self::MAX_CHAIN_ID`];
+    #[inline(always)]
+    pub const fn eip155_v(&self, chain_id: u64) -> u64 {
+        assert!(
+            chain_id <= Self::MAX_CHAIN_ID,
+            "chain id exceeds EIP-2294 bound"
+        );
+        chain_id * 2 + 35 + self.y_parity as u64
+    }
+
+    /// Extract the `y` parity and the chain id (if any) from the `v` value
+    ///
+    /// Accepts parity (`0` / `1`), legacy (`27` / `28`) and EIP-155 values,
+    /// the chain id is bounded by [`Self::MAX_CHAIN_ID`].
+    pub const fn parse_v(v: u64) -> Result<(bool, Option<u64>), SignatureError> {
+        match v {
+            0 | 1 => Ok((v == 1, None)),
+            27 | 28 => Ok((v == 28, None)),
+            35.. if (v - 35) >> 1 <= Self::MAX_CHAIN_ID => {
+                Ok(((v - 35) & 1 == 1, Some((v - 35) >> 1)))
+            }
+            _ => Err(SignatureError::InvalidV),
+        }
+    }
+
+    /// The 65 bytes `r || s || v` form, with the legacy `v`
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0u8; 65];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..64].copy_from_slice(&self.s);
+        bytes[64] = self.v();
+        bytes
+    }
+
+    /// Parse the 65 bytes `r || s || v` form
+    ///
+    /// The `v` is expected to be either a parity or a legacy value.
+    pub fn from_bytes(bytes: &[u8; 65]) -> Result<Self, SignatureError> {
+        let y_parity = match bytes[64] {
+            0 | 27 => false,
+            1 | 28 => true,
+            _ => return Err(SignatureError::InvalidV),
+        };
+
+        Ok(Self {
+            r: *Bytes::from_slice(&bytes[..32]),
+            s: *Bytes::from_slice(&bytes[32..64]),
+            y_parity,
+        })
+    }
+
+    /// The [EIP-2098](https://eips.ethereum.org/EIPS/eip-2098) compact form
+    ///
+    /// The parity is packed into the highest bit of `s`, which is always
+    /// clear for low `s` values (EIP-2), as produced by the signer.
+    pub fn to_compact(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..].copy_from_slice(&self.s);
+        bytes[32] |= (self.y_parity as u8) << 7;
+        bytes
+    }
+
+    /// Parse the [EIP-2098](https://eips.ethereum.org/EIPS/eip-2098) compact
+    /// form
+    pub fn from_compact(bytes: &[u8; 64]) -> Self {
+        let mut s = *Bytes::<U32>::from_slice(&bytes[32..]);
+        let y_parity = s[0] >> 7 == 1;
+        s[0] &= 0x7f;
+
+        Self {
+            r: *Bytes::from_slice(&bytes[..32]),
+            s,
+            y_parity,
+        }
+    }
+}
+
+impl From<Signature> for Bytes<U65> {
+    fn from(value: Signature) -> Self {
+        Self::from_array(value.to_bytes())
+    }
+}
+
+impl From<&Signature> for Bytes<U65> {
+    fn from(value: &Signature) -> Self {
+        Self::from_array(value.to_bytes())
+    }
+}
+
+impl TryFrom<Bytes<U65>> for Signature {
+    type Error = SignatureError;
+
+    fn try_from(value: Bytes<U65>) -> Result<Self, Self::Error> {
+        Self::from_bytes(&value.into_array())
+    }
+}
+
+impl From<Signature> for Bytes<U64> {
+    fn from(value: Signature) -> Self {
+        Self::from_array(value.to_compact())
+    }
+}
+
+impl From<Bytes<U64>> for Signature {
+    fn from(value: Bytes<U64>) -> Self {
+        Self::from_compact(&value.into_array())
+    }
+}
+
+impl Serialize for Signature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        as_hex::serialize::<_, _, U65>(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = Data::<U65>::deserialize(deserializer)?;
+        Self::try_from(data.0).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{from_hex, unhex};
+
+    // EIP-2098 example
+    const R: &str = "0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90";
+    const S: &str = "0x7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064";
+
+    #[test]
+    fn test_compact() {
+        let signature = Signature::new(from_hex(R), from_hex(S), false);
+        let compact: [u8; 64] = unhex!(
+            "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+            7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"
+        );
+
+        assert_eq!(signature.to_compact(), compact);
+        assert_eq!(Signature::from_compact(&compact), signature);
+
+        // EIP-2098 second example, odd parity
+        let signature = Signature::new(
+            from_hex("0x9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76"),
+            from_hex("0x139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793"),
+            true,
+        );
+        let compact: [u8; 64] = unhex!(
+            "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76\
+            939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793"
+        );
+
+        assert_eq!(Bytes::<U64>::from(signature).into_array(), compact);
+        assert_eq!(Signature::from(Bytes::from_array(compact)), signature);
+    }
+
+    #[test]
+    fn test_bytes() {
+        let signature = Signature::new(from_hex(R), from_hex(S), true);
+
+        let bytes = signature.to_bytes();
+        assert_eq!(bytes[64], 28);
+        assert_eq!(Signature::from_bytes(&bytes), Ok(signature));
+
+        let mut bytes = Bytes::<U65>::from(&signature);
+        bytes[64] = 1;
+        assert_eq!(Signature::try_from(bytes), Ok(signature));
+
+        for v in [2, 26, 29, 37] {
+            bytes[64] = v;
+            assert_eq!(Signature::try_from(bytes), Err(SignatureError::InvalidV));
+        }
+    }
+
+    #[test]
+    fn test_v() {
+        let even = Signature::default();
+        let odd = Signature::new(Bytes::default(), Bytes::default(), true);
+
+        assert_eq!((even.v(), odd.v()), (27, 28));
+        assert_eq!((even.eip155_v(1), odd.eip155_v(1)), (37, 38));
+        assert_eq!(even.eip155_v(137), 309);
+
+        assert_eq!(Signature::parse_v(0), Ok((false, None)));
+        assert_eq!(Signature::parse_v(1), Ok((true, None)));
+        assert_eq!(Signature::parse_v(27), Ok((false, None)));
+        assert_eq!(Signature::parse_v(28), Ok((true, None)));
+        assert_eq!(Signature::parse_v(35), Ok((false, Some(0))));
+        assert_eq!(Signature::parse_v(38), Ok((true, Some(1))));
+        assert_eq!(Signature::parse_v(310), Ok((true, Some(137))));
+
+        for v in [2, 26, 29, 34] {
+            assert_eq!(Signature::parse_v(v), Err(SignatureError::InvalidV));
+        }
+
+        // EIP-2294 bound
+        let max = Signature::MAX_CHAIN_ID;
+        assert_eq!(odd.eip155_v(max), u64::MAX - 37);
+        assert_eq!(Signature::parse_v(u64::MAX - 37), Ok((true, Some(max))));
+        assert_eq!(
+            Signature::parse_v(u64::MAX - 36),
+            Err(SignatureError::InvalidV)
+        );
+        assert_eq!(Signature::parse_v(u64::MAX), Err(SignatureError::InvalidV));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_eip155_v_overflow() {
+        Signature::default().eip155_v(Signature::MAX_CHAIN_ID + 1);
+    }
+
+    #[test]
+    fn test_serde() {
+        let signature = Signature::new(from_hex(R), from_hex(S), false);
+
+        let mut buf = [0u8; 256];
+        let n = serde_json_core::to_slice(&signature, &mut buf).unwrap();
+        let json = core::str::from_utf8(&buf[..n]).unwrap();
+
+        assert_eq!(
+            json,
+            "\"0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+            7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea520641b\""
+        );
+
+        let (parsed, _): (Signature, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(parsed, signature);
+
+        let invalid = "\"0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+            7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea5206405\"";
+        assert!(serde_json_core::from_str::<Signature>(invalid).is_err());
+
+        let cases = [
+            "\"\"",
+            "\"0x\"",
+            "\"0x1b\"",
+            "\"0x1\"",
+            "\"0xzz\"",
+            // 64 bytes
+            "\"0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+            7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064\"",
+            // 66 bytes
+            "\"0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+            7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea520641b00\"",
+            // Odd length
+            "\"0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+            7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea520641b0\"",
+            // Non-hex digit
+            "\"0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+            7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea520641g\"",
+        ];
+
+        for case in cases {
+            assert!(
+                serde_json_core::from_str::<Signature>(case).is_err(),
+                "{}",
+                case
+            );
+        }
+    }
+}
@@ -0,0 +1,604 @@
+use super::words::{Word, Words, WordsIter};
+use crate::{
+    rlp::{self, Decodable, Encodable, Header},
+    typenum::U32,
+    Address, Bytes, ListBuf,
+};
+use core::{fmt, slice};
+use serde::{
+    de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeSeq, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// The error returned when a fixed capacity access list is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError;
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "access list capacity exceeded")
+    }
+}
+
+/// Storage keys of an access list item
+///
+/// Either borrows a slice of keys or the validated RLP payload of the key
+/// list, iterates over the keys in both cases.
+pub type StorageKeys<'a> = Words<'a>;
+
+/// Iterator over the storage keys
+pub type StorageKeysIter<'a> = WordsIter<'a>;
+
+/// Account and storage slots the transaction plans to access
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccessListItem<'a> {
+    pub address: Address,
+    pub storage_keys: StorageKeys<'a>,
+}
+
+impl<'a> AccessListItem<'a> {
+    #[inline(always)]
+    pub const fn new(address: Address, storage_keys: &'a [Bytes<U32>]) -> Self {
+        Self {
+            address,
+            storage_keys: StorageKeys::new(storage_keys),
+        }
+    }
+
+    #[inline(always)]
+    fn payload_length(&self) -> usize {
+        self.address.length() + self.storage_keys.length()
+    }
+}
+
+impl Encodable for AccessListItem<'_> {
+    fn length(&self) -> usize {
+        Header::list(self.payload_length()).length_with_payload()
+    }
+
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        let mut n = Header::list(self.payload_length()).encode(buf);
+        n += self.address.encode(&mut buf[n..]);
+        n + self.storage_keys.encode(&mut buf[n..])
+    }
+}
+
+impl<'a> Decodable<'a> for AccessListItem<'a> {
+    fn decode(buf: &mut &'a [u8]) -> Result<Self, rlp::Error> {
+        let mut payload = rlp::decode_list(buf)?;
+        let address = Address::decode(&mut payload)?;
+        let storage_keys = StorageKeys::decode(&mut payload)?;
+
+        if !payload.is_empty() {
+            return Err(rlp::Error::LengthMismatch);
+        }
+
+        Ok(Self {
+            address,
+            storage_keys,
+        })
+    }
+}
+
+impl Serialize for AccessListItem<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut item = serializer.serialize_struct("AccessListItem", 2)?;
+        item.serialize_field("address", &self.address)?;
+        item.serialize_field("storageKeys", &self.storage_keys)?;
+        item.end()
+    }
+}
+
+/// Access list introduced by [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930)
+///
+/// A borrowed view over the list items, which are either provided as a
+/// slice, stored in an [`AccessListBuf`] or decoded from RLP (the payload is
+/// validated upfront and is iterated lazily). Serialized to JSON as an array
+/// of `{ address, storageKeys }` objects, use [`AccessListBuf`] to
+/// deserialize.
+///
+/// ```rust
+/// use ethgen::{address, rlp::{self, Encodable}, transaction::{AccessList, AccessListItem}};
+///
+///
+/// let items = [AccessListItem::new(
+///     address!("0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae"),
+///     &[],
+/// )];
+///
+/// let list = AccessList::new(&items);
+///
+/// let mut buf = [0u8; 32];
+/// let n = list.encode(&mut buf);
+///
+/// assert_eq!(b"\xd7\xd6\x94\xde\x0b\x29\x56\x69\xa9\xfd\x93\xd5\xf2\x8d\x9e\xc8\x5e\x40\xf4\xcb\x69\x7b\xae\xc0", &buf[..n]);
+///
+/// let decoded: AccessList = rlp::decode(&buf[..n]).unwrap();
+/// assert_eq!(decoded, list);
+///
+///
+/// let mut json = [0u8; 128];
+/// let n = serde_json_core::to_slice(&list, &mut json).unwrap();
+///
+/// assert_eq!(
+///     r#"[{"address":"0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae","storageKeys":[]}]"#,
+///     core::str::from_utf8(&json[..n]).unwrap(),
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct AccessList<'a>(Items<'a>);
+
+#[derive(Clone, Copy)]
+enum Items<'a> {
+    List(rlp::List<'a, AccessListItem<'a>>),
+    Flat {
+        entries: &'a [(Address, usize)],
+        keys: &'a [Bytes<U32>],
+    },
+}
+
+impl<'a> AccessList<'a> {
+    pub const EMPTY: Self = Self::new(&[]);
+
+    #[inline(always)]
+    pub const fn new(items: &'a [AccessListItem<'a>]) -> Self {
+        Self(Items::List(rlp::List::new(items)))
+    }
+
+    pub fn len(&self) -> usize {
+        match self.0 {
+            Items::List(list) => list.len(),
+            Items::Flat { entries, .. } => entries.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self.0 {
+            Items::List(list) => list.is_empty(),
+            Items::Flat { entries, .. } => entries.is_empty(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn iter(&self) -> AccessListIter<'a> {
+        AccessListIter(match self.0 {
+            Items::List(list) => Iter::List(list.iter()),
+            Items::Flat { entries, keys } => Iter::Flat {
+                entries: entries.iter(),
+                keys,
+            },
+        })
+    }
+
+    fn payload_length(&self) -> usize {
+        self.iter().map(|item| item.length()).sum()
+    }
+}
+
+impl Default for AccessList<'_> {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<'a> From<&'a [AccessListItem<'a>]> for AccessList<'a> {
+    fn from(items: &'a [AccessListItem<'a>]) -> Self {
+        Self::new(items)
+    }
+}
+
+impl<'a> IntoIterator for AccessList<'a> {
+    type Item = AccessListItem<'a>;
+    type IntoIter = AccessListIter<'a>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl PartialEq for AccessList<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for AccessList<'_> {}
+
+impl fmt::Debug for AccessList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Encodable for AccessList<'_> {
+    fn length(&self) -> usize {
+        match self.0 {
+            Items::List(list) => list.length(),
+            Items::Flat { .. } => Header::list(self.payload_length()).length_with_payload(),
+        }
+    }
+
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        match self.0 {
+            Items::List(list) => list.encode(buf),
+            Items::Flat { .. } => {
+                let n = Header::list(self.payload_length()).encode(buf);
+                self.iter()
+                    .fold(n, |n, item| n + item.encode(&mut buf[n..]))
+            }
+        }
+    }
+}
+
+impl<'a> Decodable<'a> for AccessList<'a> {
+    fn decode(buf: &mut &'a [u8]) -> Result<Self, rlp::Error> {
+        rlp::List::decode(buf).map(|list| Self(Items::List(list)))
+    }
+}
+
+impl Serialize for AccessList<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for item in self.iter() {
+            seq.serialize_element(&item)?;
+        }
+        seq.end()
+    }
+}
+
+/// Iterator over the access list items
+#[derive(Clone)]
+pub struct AccessListIter<'a>(Iter<'a>);
+
+#[derive(Clone)]
+enum Iter<'a> {
+    List(rlp::ListIter<'a, AccessListItem<'a>>),
+    Flat {
+        entries: slice::Iter<'a, (Address, usize)>,
+        keys: &'a [Bytes<U32>],
+    },
+}
+
+impl<'a> Iterator for AccessListIter<'a> {
+    type Item = AccessListItem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            Iter::List(items) => items.next(),
+            Iter::Flat { entries, keys } => {
+                let (address, count) = *entries.next()?;
+                let (storage_keys, rest) = keys.split_at(count);
+                *keys = rest;
+                Some(AccessListItem::new(address, storage_keys))
+            }
+        }
+    }
+}
+
+/// Fixed capacity access list storage
+///
+/// Holds up to `N` items with up to `K` storage keys in total, used to
+/// deserialize access lists from JSON (e.g. `eth_createAccessList` result)
+/// without allocation. Exceeding the capacity fails the deserialization.
+///
+/// ```rust
+/// use ethgen::transaction::{AccessList, AccessListBuf};
+///
+///
+/// let json = r#"[{
+///     "address": "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae",
+///     "storageKeys": [
+///         "0x0000000000000000000000000000000000000000000000000000000000000003",
+///         "0x0000000000000000000000000000000000000000000000000000000000000007"
+///     ]
+/// }]"#;
+///
+/// let (buf, _): (AccessListBuf<4, 16>, usize) = serde_json_core::from_str(json).unwrap();
+/// let list = buf.as_access_list();
+///
+/// assert_eq!(list.len(), 1);
+/// assert_eq!(list.iter().next().unwrap().storage_keys.len(), 2);
+///
+/// assert!(serde_json_core::from_str::<AccessListBuf<4, 1>>(json).is_err());
+/// ```
+#[derive(Clone)]
+pub struct AccessListBuf<const N: usize, const K: usize> {
+    entries: ListBuf<(Address, usize), N>,
+    keys: ListBuf<Bytes<U32>, K>,
+}
+
+impl<const N: usize, const K: usize> AccessListBuf<N, K> {
+    pub fn new() -> Self {
+        Self {
+            entries: ListBuf::new(),
+            keys: ListBuf::new(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.keys.clear();
+    }
+
+    /// Append an item, copying its storage keys
+    pub fn push(&mut self, item: AccessListItem<'_>) -> Result<(), CapacityError> {
+        let count = item.storage_keys.len();
+
+        if self.entries.len() == N || K - self.keys.len() < count {
+            return Err(CapacityError);
+        }
+
+        for key in item.storage_keys {
+            self.keys.push(key).map_err(|_| CapacityError)?;
+        }
+
+        self.entries
+            .push((item.address, count))
+            .map_err(|_| CapacityError)
+    }
+
+    #[inline(always)]
+    pub fn as_access_list(&self) -> AccessList<'_> {
+        AccessList(Items::Flat {
+            entries: self.entries.as_slice(),
+            keys: self.keys.as_slice(),
+        })
+    }
+}
+
+impl<const N: usize, const K: usize> Default for AccessListBuf<N, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, const N: usize, const K: usize> From<&'a AccessListBuf<N, K>> for AccessList<'a> {
+    fn from(value: &'a AccessListBuf<N, K>) -> Self {
+        value.as_access_list()
+    }
+}
+
+impl<const N: usize, const K: usize> PartialEq for AccessListBuf<N, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_access_list() == other.as_access_list()
+    }
+}
+
+impl<const N: usize, const K: usize> Eq for AccessListBuf<N, K> {}
+
+impl<const N: usize, const K: usize> fmt::Debug for AccessListBuf<N, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_access_list().fmt(f)
+    }
+}
+
+impl<const N: usize, const K: usize> Serialize for AccessListBuf<N, K> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_access_list().serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize, const K: usize> Deserialize<'de> for AccessListBuf<N, K> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ListVisitor<const N: usize, const K: usize>;
+
+        impl<'de, const N: usize, const K: usize> Visitor<'de> for ListVisitor<N, K> {
+            type Value = AccessListBuf<N, K>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "access list of at most {} items and {} keys", N, K)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut list = AccessListBuf::new();
+                while seq.next_element_seed(ItemSeed(&mut list))?.is_some() {}
+                Ok(list)
+            }
+        }
+
+        deserializer.deserialize_seq(ListVisitor)
+    }
+}
+
+/// Deserialize an access list item directly into the storage
+struct ItemSeed<'b, const N: usize, const K: usize>(&'b mut AccessListBuf<N, K>);
+
+impl<'de, const N: usize, const K: usize> DeserializeSeed<'de> for ItemSeed<'_, N, K> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        const FIELDS: &[&str] = &["address", "storageKeys"];
+        deserializer.deserialize_struct("AccessListItem", FIELDS, self)
+    }
+}
+
+impl<'de, const N: usize, const K: usize> Visitor<'de> for ItemSeed<'_, N, K> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "access list item")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let list = self.0;
+
+        if list.entries.len() == N {
+            return Err(de::Error::custom(CapacityError));
+        }
+
+        let start = list.keys.len();
+        let mut address = None;
+        let mut keys = false;
+
+        while let Some(key) = map.next_key::<&str>()? {
+            match key {
+                "address" => address = Some(map.next_value()?),
+                "storageKeys" if keys => return Err(de::Error::duplicate_field("storageKeys")),
+                "storageKeys" => {
+                    map.next_value_seed(KeysSeed(&mut list.keys))?;
+                    keys = true;
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        let address = address.ok_or_else(|| de::Error::missing_field("address"))?;
+
+        if !keys {
+            return Err(de::Error::missing_field("storageKeys"));
+        }
+
+        list.entries
+            .push((address, list.keys.len() - start))
+            .map_err(|_| de::Error::custom(CapacityError))
+    }
+}
+
+/// Deserialize storage keys into the free space of the storage
+struct KeysSeed<'b, const K: usize>(&'b mut ListBuf<Bytes<U32>, K>);
+
+impl<'de, const K: usize> DeserializeSeed<'de> for KeysSeed<'_, K> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, const K: usize> Visitor<'de> for KeysSeed<'_, K> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "storage keys")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(Word(key)) = seq.next_element()? {
+            self.0
+                .push(key)
+                .map_err(|_| de::Error::custom(CapacityError))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{address, unhex};
+
+    const KEY: [u8; 32] =
+        unhex!("0000000000000000000000000000000000000000000000000000000000000007");
+    const KEYS: [Bytes<U32>; 2] = [Bytes::from_array([0u8; 32]), Bytes::from_array(KEY)];
+
+    const ITEMS: [AccessListItem; 2] = [
+        AccessListItem::new(
+            address!("0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae"),
+            &KEYS,
+        ),
+        AccessListItem::new(address!("0xbb9bc244d798123fde783fcc1c72d3bb8c189413"), &[]),
+    ];
+
+    #[test]
+    fn test_rlp() {
+        let list = AccessList::new(&ITEMS);
+
+        let mut buf = [0u8; 256];
+        let n = list.encode(&mut buf);
+        assert_eq!(n, list.length());
+
+        let decoded: AccessList = rlp::decode(&buf[..n]).unwrap();
+        assert_eq!(decoded, list);
+        assert_eq!(decoded.len(), 2);
+
+        // Decoded form is reencoded as is
+        let mut reencoded = [0u8; 256];
+        assert_eq!(decoded.encode(&mut reencoded), n);
+        assert_eq!(&reencoded[..n], &buf[..n]);
+
+        let item = decoded.iter().next().unwrap();
+        assert_eq!(item.storage_keys.len(), 2);
+        assert_eq!(item.storage_keys.iter().nth(1), Some(KEYS[1]));
+
+        // Storage key of invalid length
+        let invalid: [u8; 27] = unhex!("dad994de0b295669a9fd93d5f28d9ec85e40f4cb697baec3820a0b");
+        assert_eq!(
+            rlp::decode::<AccessList>(&invalid),
+            Err(rlp::Error::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let list = AccessList::new(&ITEMS);
+
+        let mut buf = [0u8; 512];
+        let n = serde_json_core::to_slice(&list, &mut buf).unwrap();
+        let json = core::str::from_utf8(&buf[..n]).unwrap();
+
+        assert_eq!(
+            json,
+            "[{\"address\":\"0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae\",\"storageKeys\":[\
+            \"0x0000000000000000000000000000000000000000000000000000000000000000\",\
+            \"0x0000000000000000000000000000000000000000000000000000000000000007\"]},\
+            {\"address\":\"0xbb9bc244d798123fde783fcc1c72d3bb8c189413\",\"storageKeys\":[]}]"
+        );
+
+        let (parsed, _): (AccessListBuf<2, 2>, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(parsed.as_access_list(), list);
+
+        // Field order doesn't matter, unknown fields are ignored
+        let json = r#"[{"storageKeys":[],"foo":[1],"address":"0xbb9bc244d798123fde783fcc1c72d3bb8c189413"}]"#;
+        let (parsed, _): (AccessListBuf<1, 0>, usize) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(parsed.as_access_list(), AccessList::new(&ITEMS[1..]));
+
+        assert!(serde_json_core::from_str::<AccessListBuf<1, 2>>(
+            core::str::from_utf8(&buf[..n]).unwrap()
+        )
+        .is_err());
+
+        // Malformed keys and addresses are rejected
+        for json in [
+            r#"[{"address":"0xbb9bc244d798123fde783fcc1c72d3bb8c189413","storageKeys":["0x7"]}]"#,
+            r#"[{"address":"0xbb9bc244d798123fde783fcc1c72d3bb8c189413","storageKeys":[""]}]"#,
+            r#"[{"address":"0xbb9bc244d798123fde783fcc1c72d3bb8c189413","storageKeys":["0xzz"]}]"#,
+            r#"[{"address":"0xbb","storageKeys":[]}]"#,
+            r#"[{"address":"0xbb9bc244d798123fde783fcc1c72d3bb8c189413","storageKeys":[],"storageKeys":[]}]"#,
+        ] {
+            assert!(serde_json_core::from_str::<AccessListBuf<2, 2>>(json).is_err());
+        }
+    }
+
+    #[test]
+    fn test_buf() {
+        let mut buf = AccessListBuf::<2, 3>::new();
+
+        assert_eq!(buf.push(ITEMS[0]), Ok(()));
+        assert_eq!(
+            buf.push(AccessListItem::new(Address::ZERO, &[KEYS[0]; 2])),
+            Err(CapacityError)
+        );
+        assert_eq!(buf.push(ITEMS[1]), Ok(()));
+        assert_eq!(buf.push(ITEMS[1]), Err(CapacityError));
+
+        assert_eq!(buf.as_access_list(), AccessList::new(&ITEMS));
+
+        buf.clear();
+        assert!(buf.as_access_list().is_empty());
+    }
+}
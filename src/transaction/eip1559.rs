@@ -0,0 +1,224 @@
+use super::{decode_recipient, recipient, AccessList, Transaction};
+use crate::{
+    rlp::{self, Decodable, Encodable},
+    uint256, Address,
+};
+
+/// Dynamic fee transaction introduced by [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)
+///
+/// Encoded as `0x02 || rlp([chain_id, nonce, max_priority_fee_per_gas,
+/// max_fee_per_gas, gas_limit, to, value, data, access_list])` for signing
+/// and with the `y_parity, r, s` fields appended when signed. All encodings
+/// are written into caller provided buffers.
+///
+/// ```rust
+/// use ethgen::{
+///     address, hex, secp256k1::SigningKey, uint256,
+///     rpc::{methods::EthSendRawTransaction, Request},
+///     transaction::{Eip1559Transaction, Transaction},
+/// };
+///
+///
+/// let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+///
+/// let tx = Eip1559Transaction {
+///     chain_id: 1,
+///     nonce: 0,
+///     max_priority_fee_per_gas: 1_000_000_000,
+///     max_fee_per_gas: 30_000_000_000,
+///     gas_limit: 21_000,
+///     to: Some(address!("0x3535353535353535353535353535353535353535")),
+///     value: uint256::from(1_000_000_000_000_000_000u64),
+///     ..Default::default()
+/// };
+///
+/// let mut raw = [0u8; 256];
+/// let n = tx.sign(&key, &mut raw);
+/// assert_eq!(0x02, raw[0]);
+///
+/// // Hex encode the raw transaction for `eth_sendRawTransaction`
+/// let mut hex = [0u8; 514];
+/// hex[..2].copy_from_slice(b"0x");
+/// hex::encode(&raw[..n], &mut hex[2..2 + 2 * n]);
+///
+/// let rpc = Request::new(1, EthSendRawTransaction {
+///     transaction: core::str::from_utf8(&hex[..2 + 2 * n]).unwrap(),
+/// });
+///
+/// let mut buf = [0u8; 1024];
+/// let len = serde_json_core::to_slice(&rpc, &mut buf).unwrap();
+/// assert!(buf[..len].starts_with(br#"{"jsonrpc":"2.0","method":"eth_sendRawTransaction","params":["0x02f8"#));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Eip1559Transaction<'a> {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub gas_limit: u64,
+    /// Recipient, `None` for the contract creation
+    pub to: Option<Address>,
+    pub value: uint256,
+    pub data: &'a [u8],
+    pub access_list: AccessList<'a>,
+}
+
+impl<'a> Transaction<'a> for Eip1559Transaction<'a> {
+    const TYPE: u8 = 0x02;
+
+    fn fields_length(&self) -> usize {
+        self.chain_id.length()
+            + self.nonce.length()
+            + self.max_priority_fee_per_gas.length()
+            + self.max_fee_per_gas.length()
+            + self.gas_limit.length()
+            + recipient(&self.to).length()
+            + self.value.length()
+            + self.data.length()
+            + self.access_list.length()
+    }
+
+    fn encode_fields(&self, buf: &mut [u8]) -> usize {
+        let mut n = self.chain_id.encode(buf);
+        n += self.nonce.encode(&mut buf[n..]);
+        n += self.max_priority_fee_per_gas.encode(&mut buf[n..]);
+        n += self.max_fee_per_gas.encode(&mut buf[n..]);
+        n += self.gas_limit.encode(&mut buf[n..]);
+        n += recipient(&self.to).encode(&mut buf[n..]);
+        n += self.value.encode(&mut buf[n..]);
+        n += self.data.encode(&mut buf[n..]);
+        n + self.access_list.encode(&mut buf[n..])
+    }
+
+    fn decode_fields(buf: &mut &'a [u8]) -> Result<Self, rlp::Error> {
+        Ok(Self {
+            chain_id: u64::decode(buf)?,
+            nonce: u64::decode(buf)?,
+            max_priority_fee_per_gas: u128::decode(buf)?,
+            max_fee_per_gas: u128::decode(buf)?,
+            gas_limit: u64::decode(buf)?,
+            to: decode_recipient(buf)?,
+            value: uint256::decode(buf)?,
+            data: <&[u8]>::decode(buf)?,
+            access_list: AccessList::decode(buf)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        address, from_hex, keccak256, rlp, secp256k1,
+        transaction::{AccessListItem, Error, SigningKey},
+        unhex, Bytes, Signature,
+    };
+
+    fn vector() -> Eip1559Transaction<'static> {
+        Eip1559Transaction {
+            chain_id: 1,
+            nonce: 2,
+            max_priority_fee_per_gas: 1_000_000_000,
+            max_fee_per_gas: 11_248_607_958,
+            gas_limit: 39_152,
+            to: Some(address!("0xd9e1459a7a482635700cbc20bbaf52d495ab9c96")),
+            value: uint256::ZERO,
+            data: &[0x1b, 0x55, 0xba, 0x3a],
+            access_list: AccessList::EMPTY,
+        }
+    }
+
+    #[test]
+    fn test_signing_payload() {
+        let tx = vector();
+
+        let mut buf = [0u8; 128];
+        let n = tx.encode_for_signing(&mut buf);
+        let expected: [u8; 46] = unhex!(
+            "02ec0102843b9aca0085029e7822d68298f094d9e1459a7a482635700cbc20bbaf\
+            52d495ab9c9680841b55ba3ac0"
+        );
+
+        assert_eq!(n, tx.signing_length());
+        assert_eq!(&buf[..n], &expected);
+        assert_eq!(tx.signature_hash(&mut buf), keccak256(&expected));
+    }
+
+    #[test]
+    fn test_signed() {
+        let tx = vector();
+        let signature = Signature::new(
+            from_hex("0xc199674fcb29f353693dd779c017823b954b3c69dffa3cd6b2a6ff7888798039"),
+            from_hex("0x28ca912de909e7e6cdef9cdcaf24c54dd8c1032946dfa1d85c206b32a9064fe8"),
+            false,
+        );
+
+        let mut buf = [0u8; 128];
+        let n = tx.encode_signed(&signature, &mut buf);
+        let expected: [u8; 114] = unhex!(
+            "02f86f0102843b9aca0085029e7822d68298f094d9e1459a7a482635700cbc20bbaf\
+            52d495ab9c9680841b55ba3ac080a0c199674fcb29f353693dd779c017823b954b3c\
+            69dffa3cd6b2a6ff7888798039a028ca912de909e7e6cdef9cdcaf24c54dd8c10329\
+            46dfa1d85c206b32a9064fe8"
+        );
+
+        assert_eq!(n, tx.signed_length(&signature));
+        assert_eq!(&buf[..n], &expected);
+
+        assert_eq!(
+            Eip1559Transaction::decode_signed(&expected),
+            Ok((tx, signature))
+        );
+        assert_eq!(
+            Eip1559Transaction::decode_signed(&expected[..n - 1]),
+            Err(Error::Rlp(rlp::Error::UnexpectedEnd))
+        );
+
+        // Only the parity is valid as the typed transaction `v`
+        let mut invalid = expected;
+        invalid[47] = 0x1b;
+        assert_eq!(
+            Eip1559Transaction::decode_signed(&invalid),
+            Err(Error::InvalidV)
+        );
+    }
+
+    #[test]
+    fn test_sign() {
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let keys = [Bytes::from_array([0x01u8; 32]), Bytes::default()];
+        let items = [AccessListItem::new(
+            address!("0x3535353535353535353535353535353535353535"),
+            &keys,
+        )];
+
+        let tx = Eip1559Transaction {
+            to: None,
+            data: &[0xfe; 64],
+            access_list: AccessList::new(&items),
+            ..vector()
+        };
+
+        let mut buf = [0u8; 512];
+        let hash = tx.signature_hash(&mut buf);
+        let n = tx.sign(&key, &mut buf);
+
+        let signature = key.sign_prehash(&hash.into_array());
+        assert_eq!(n, tx.signed_length(&signature));
+        assert_eq!(
+            secp256k1::recover(&hash.into_array(), &signature),
+            Ok(key.address())
+        );
+
+        let mut expected = [0u8; 512];
+        let m = tx.encode_signed(&signature, &mut expected);
+        assert_eq!(&buf[..n], &expected[..m]);
+
+        let (decoded, _) = Eip1559Transaction::decode_signed(&buf[..n]).unwrap();
+        assert_eq!(decoded, tx);
+        assert_eq!(
+            Eip1559Transaction::recover_sender(&buf[..n], &mut expected),
+            Ok(key.address())
+        );
+    }
+}
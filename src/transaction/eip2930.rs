@@ -0,0 +1,189 @@
+use super::{decode_recipient, recipient, AccessList, Transaction};
+use crate::{
+    rlp::{self, Decodable, Encodable},
+    uint256, Address,
+};
+
+/// Access list transaction introduced by [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930)
+///
+/// Encoded as `0x01 || rlp([chain_id, nonce, gas_price, gas_limit, to, value,
+/// data, access_list])` for signing and with the `y_parity, r, s` fields
+/// appended when signed. All encodings are written into caller provided
+/// buffers.
+///
+/// ```rust
+/// use ethgen::{
+///     address, from_hex, secp256k1::{self, SigningKey},
+///     transaction::{AccessList, AccessListItem, Eip2930Transaction, Transaction},
+/// };
+///
+///
+/// let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+///
+/// let keys = [from_hex("0x0000000000000000000000000000000000000000000000000000000000000007")];
+/// let items = [AccessListItem::new(
+///     address!("0x3535353535353535353535353535353535353535"),
+///     &keys,
+/// )];
+///
+/// let tx = Eip2930Transaction {
+///     chain_id: 1,
+///     gas_price: 1_000_000_000,
+///     gas_limit: 21_000,
+///     to: Some(address!("0x3535353535353535353535353535353535353535")),
+///     access_list: AccessList::new(&items),
+///     ..Default::default()
+/// };
+///
+/// let mut buf = [0u8; 256];
+/// let hash = tx.signature_hash(&mut buf);
+///
+/// let n = tx.sign(&key, &mut buf);
+/// assert_eq!(0x01, buf[0]);
+///
+/// let signature = key.sign_prehash(&hash.into_array());
+/// assert_eq!(n, tx.signed_length(&signature));
+/// assert_eq!(secp256k1::recover(&hash.into_array(), &signature), Ok(key.address()));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Eip2930Transaction<'a> {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub gas_price: u128,
+    pub gas_limit: u64,
+    /// Recipient, `None` for the contract creation
+    pub to: Option<Address>,
+    pub value: uint256,
+    pub data: &'a [u8],
+    pub access_list: AccessList<'a>,
+}
+
+impl<'a> Transaction<'a> for Eip2930Transaction<'a> {
+    const TYPE: u8 = 0x01;
+
+    fn fields_length(&self) -> usize {
+        self.chain_id.length()
+            + self.nonce.length()
+            + self.gas_price.length()
+            + self.gas_limit.length()
+            + recipient(&self.to).length()
+            + self.value.length()
+            + self.data.length()
+            + self.access_list.length()
+    }
+
+    fn encode_fields(&self, buf: &mut [u8]) -> usize {
+        let mut n = self.chain_id.encode(buf);
+        n += self.nonce.encode(&mut buf[n..]);
+        n += self.gas_price.encode(&mut buf[n..]);
+        n += self.gas_limit.encode(&mut buf[n..]);
+        n += recipient(&self.to).encode(&mut buf[n..]);
+        n += self.value.encode(&mut buf[n..]);
+        n += self.data.encode(&mut buf[n..]);
+        n + self.access_list.encode(&mut buf[n..])
+    }
+
+    fn decode_fields(buf: &mut &'a [u8]) -> Result<Self, rlp::Error> {
+        Ok(Self {
+            chain_id: u64::decode(buf)?,
+            nonce: u64::decode(buf)?,
+            gas_price: u128::decode(buf)?,
+            gas_limit: u64::decode(buf)?,
+            to: decode_recipient(buf)?,
+            value: uint256::decode(buf)?,
+            data: <&[u8]>::decode(buf)?,
+            access_list: AccessList::decode(buf)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        address, keccak256, secp256k1,
+        transaction::{AccessListItem, SigningKey},
+        typenum::U32,
+        unhex, Bytes,
+    };
+
+    const KEY: [u8; 32] =
+        unhex!("0000000000000000000000000000000000000000000000000000000000000007");
+    const KEYS: [Bytes<U32>; 1] = [Bytes::from_array(KEY)];
+
+    #[test]
+    fn test_signing_payload() {
+        let items = [AccessListItem::new(
+            address!("0x3535353535353535353535353535353535353535"),
+            &KEYS,
+        )];
+
+        let tx = Eip2930Transaction {
+            chain_id: 1,
+            nonce: 0,
+            gas_price: 1_000_000_000,
+            gas_limit: 21_000,
+            to: Some(address!("0x3535353535353535353535353535353535353535")),
+            value: uint256::ZERO,
+            data: &[],
+            access_list: AccessList::new(&items),
+        };
+
+        let mut buf = [0u8; 128];
+        let n = tx.encode_for_signing(&mut buf);
+        let expected: [u8; 94] = unhex!(
+            "01f85b0180843b9aca0082520894353535353535353535353535353535353535353580\
+            80f838f7943535353535353535353535353535353535353535e1a000000000000000000\
+            00000000000000000000000000000000000000000000007"
+        );
+
+        assert_eq!(n, tx.signing_length());
+        assert_eq!(&buf[..n], &expected);
+        assert_eq!(tx.signature_hash(&mut buf), keccak256(&expected));
+    }
+
+    #[test]
+    fn test_sign() {
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let items = [AccessListItem::new(
+            address!("0x3535353535353535353535353535353535353535"),
+            &KEYS,
+        )];
+
+        let tx = Eip2930Transaction {
+            chain_id: 5,
+            nonce: 7,
+            gas_price: 20_000_000_000,
+            gas_limit: 100_000,
+            to: None,
+            value: uint256::from(1_000_000_000_000_000_000u64),
+            data: &[0x60, 0x80, 0x60, 0x40],
+            access_list: AccessList::new(&items),
+        };
+
+        let mut buf = [0u8; 256];
+        let hash = tx.signature_hash(&mut buf);
+        let n = tx.sign(&key, &mut buf);
+
+        let signature = key.sign_prehash(&hash.into_array());
+        assert_eq!(n, tx.signed_length(&signature));
+        assert_eq!(buf[0], Eip2930Transaction::TYPE);
+        assert_eq!(
+            secp256k1::recover(&hash.into_array(), &signature),
+            Ok(key.address())
+        );
+
+        let mut expected = [0u8; 256];
+        let m = tx.encode_signed(&signature, &mut expected);
+        assert_eq!(&buf[..n], &expected[..m]);
+
+        assert_eq!(
+            Eip2930Transaction::decode_signed(&buf[..n]),
+            Ok((tx, signature))
+        );
+        assert_eq!(
+            Eip2930Transaction::recover_sender(&buf[..n], &mut expected),
+            Ok(key.address())
+        );
+    }
+}
@@ -0,0 +1,519 @@
+use super::{
+    decode_typed, decode_typed_signature, encode_signature, encode_typed, signature_length,
+    typed_length, AccessList, Error, Transaction, Words,
+};
+use crate::{
+    rlp::{self, Decodable, Encodable, Header},
+    secp256k1::SigningKey,
+    typenum::{U32, U48},
+    uint256, Address, Bytes, Signature,
+};
+use core::fmt;
+use sha2::{Digest, Sha256};
+
+/// Size of a single blob in bytes: 4096 field elements of 32 bytes
+pub const BYTES_PER_BLOB: usize = 131_072;
+
+/// Version byte of the versioned hash produced from a KZG commitment
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// Opaque blob data
+pub type Blob = [u8; BYTES_PER_BLOB];
+
+/// Blob versioned hashes of a transaction, see [`kzg_to_versioned_hash`]
+pub type VersionedHashes<'a> = Words<'a>;
+
+/// Versioned hash of a KZG commitment: `0x01 || sha256(commitment)[1..]`
+///
+/// ```rust
+/// use ethgen::{from_hex, transaction::kzg_to_versioned_hash, typenum::U48, Bytes};
+///
+///
+/// // Commitment to the zero blob
+/// let mut commitment = Bytes::<U48>::default();
+/// commitment[0] = 0xc0;
+///
+/// assert_eq!(
+///     kzg_to_versioned_hash(&commitment),
+///     from_hex("0x010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"),
+/// );
+/// ```
+pub fn kzg_to_versioned_hash(commitment: &Bytes<U48>) -> Bytes<U32> {
+    let mut hash = Bytes::<U32>::default();
+    hash.copy_from_slice(&Sha256::digest(commitment));
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    hash
+}
+
+/// KZG proof verification backend
+///
+/// The crate does not ship a KZG implementation (it requires the trusted
+/// setup), instead the blob sidecar is verified through this trait, which
+/// could be implemented on top of any KZG library.
+pub trait KzgVerifier {
+    type Error;
+
+    /// Verify that each blob matches its commitment according to the proof
+    fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs: &[Blob],
+        commitments: &[Bytes<U48>],
+        proofs: &[Bytes<U48>],
+    ) -> Result<(), Self::Error>;
+}
+
+/// The error type returned by the blob sidecar validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobError<E> {
+    /// The number of blobs, commitments, proofs and versioned hashes differ
+    CountMismatch,
+    /// The commitment at the index does not match the versioned hash
+    VersionedHashMismatch(usize),
+    /// KZG proof verification failure
+    Kzg(E),
+}
+
+impl<E: fmt::Display> fmt::Display for BlobError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CountMismatch => write!(f, "blob sidecar items count mismatch"),
+            Self::VersionedHashMismatch(i) => {
+                write!(f, "commitment {} does not match the versioned hash", i)
+            }
+            Self::Kzg(err) => write!(f, "KZG proof verification failed: {}", err),
+        }
+    }
+}
+
+/// Blobs, KZG commitments and proofs, carried along the blob transaction in
+/// its network form only
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlobSidecar<'a> {
+    pub blobs: &'a [Blob],
+    pub commitments: &'a [Bytes<U48>],
+    pub proofs: &'a [Bytes<U48>],
+}
+
+impl BlobSidecar<'_> {
+    fn fields_length(&self) -> usize {
+        rlp::list_length(self.blobs)
+            + rlp::list_length(self.commitments)
+            + rlp::list_length(self.proofs)
+    }
+
+    fn encode_fields(&self, buf: &mut [u8]) -> usize {
+        let mut n = rlp::encode_list(self.blobs, buf);
+        n += rlp::encode_list(self.commitments, &mut buf[n..]);
+        n + rlp::encode_list(self.proofs, &mut buf[n..])
+    }
+
+    /// Versioned hashes of the commitments, to be used as the transaction
+    /// `blob_versioned_hashes`
+    pub fn versioned_hashes(&self) -> impl Iterator<Item = Bytes<U32>> + '_ {
+        self.commitments.iter().map(kzg_to_versioned_hash)
+    }
+
+    /// Validate the sidecar against the transaction versioned hashes
+    ///
+    /// Checks that every blob has a commitment and a proof, that commitments
+    /// match the versioned hashes and verifies the proofs with the backend.
+    pub fn validate<V: KzgVerifier>(
+        &self,
+        versioned_hashes: VersionedHashes,
+        verifier: &V,
+    ) -> Result<(), BlobError<V::Error>> {
+        let count = versioned_hashes.len();
+        if self.blobs.len() != count
+            || self.commitments.len() != count
+            || self.proofs.len() != count
+        {
+            return Err(BlobError::CountMismatch);
+        }
+
+        if let Some(i) = self
+            .versioned_hashes()
+            .zip(versioned_hashes)
+            .position(|(hash, expected)| hash != expected)
+        {
+            return Err(BlobError::VersionedHashMismatch(i));
+        }
+
+        verifier
+            .verify_blob_kzg_proof_batch(self.blobs, self.commitments, self.proofs)
+            .map_err(BlobError::Kzg)
+    }
+}
+
+/// Blob transaction introduced by [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)
+///
+/// Encoded as `0x03 || rlp([chain_id, nonce, max_priority_fee_per_gas,
+/// max_fee_per_gas, gas_limit, to, value, data, access_list,
+/// max_fee_per_blob_gas, blob_versioned_hashes])` for signing and with the
+/// `y_parity, r, s` fields appended when signed. The signed form is the
+/// canonical one, included into blocks and hashed as the transaction hash.
+///
+/// The blobs themselves are only carried by the network form, accepted by
+/// `eth_sendRawTransaction`: `0x03 || rlp([tx_payload_body, blobs,
+/// commitments, proofs])`, where `tx_payload_body` is the signed list.
+///
+/// ```rust
+/// use ethgen::{
+///     address, secp256k1::SigningKey, typenum::U48, Bytes,
+///     transaction::{
+///         Blob, BlobSidecar, Eip4844Transaction, Transaction, VersionedHashes, BYTES_PER_BLOB,
+///     },
+/// };
+///
+///
+/// let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+///
+/// // Zero blob and its commitment, the proof is the same point at infinity
+/// static BLOBS: [Blob; 1] = [[0u8; BYTES_PER_BLOB]];
+/// let mut commitment = Bytes::<U48>::default();
+/// commitment[0] = 0xc0;
+///
+/// let commitments = [commitment];
+/// let sidecar = BlobSidecar { blobs: &BLOBS, commitments: &commitments, proofs: &commitments };
+/// let hashes = [sidecar.versioned_hashes().next().unwrap()];
+///
+/// let tx = Eip4844Transaction {
+///     chain_id: 1,
+///     max_priority_fee_per_gas: 1_000_000_000,
+///     max_fee_per_gas: 30_000_000_000,
+///     gas_limit: 21_000,
+///     to: address!("0x3535353535353535353535353535353535353535"),
+///     max_fee_per_blob_gas: 1_000_000_000,
+///     blob_versioned_hashes: VersionedHashes::new(&hashes),
+///     ..Default::default()
+/// };
+///
+/// let mut buf = [0u8; 256];
+/// let hash = tx.signature_hash(&mut buf);
+/// let signature = key.sign_prehash(&hash.into_array());
+///
+/// // Canonical form, which hash is the transaction hash
+/// let n = tx.encode_signed(&signature, &mut buf);
+/// assert_eq!(0x03, buf[0]);
+///
+/// // Network form, submitted to the node
+/// let mut raw = [0u8; BYTES_PER_BLOB + 512];
+/// let m = tx.encode_network(&signature, &sidecar, &mut raw);
+/// assert_eq!(m, tx.network_length(&signature, &sidecar));
+/// assert!(m > BYTES_PER_BLOB + n);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Eip4844Transaction<'a> {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub gas_limit: u64,
+    /// Recipient, blob transactions can not create contracts
+    pub to: Address,
+    pub value: uint256,
+    pub data: &'a [u8],
+    pub access_list: AccessList<'a>,
+    pub max_fee_per_blob_gas: u128,
+    pub blob_versioned_hashes: VersionedHashes<'a>,
+}
+
+impl<'a> Transaction<'a> for Eip4844Transaction<'a> {
+    const TYPE: u8 = 0x03;
+
+    fn fields_length(&self) -> usize {
+        self.chain_id.length()
+            + self.nonce.length()
+            + self.max_priority_fee_per_gas.length()
+            + self.max_fee_per_gas.length()
+            + self.gas_limit.length()
+            + self.to.length()
+            + self.value.length()
+            + self.data.length()
+            + self.access_list.length()
+            + self.max_fee_per_blob_gas.length()
+            + self.blob_versioned_hashes.length()
+    }
+
+    fn encode_fields(&self, buf: &mut [u8]) -> usize {
+        let mut n = self.chain_id.encode(buf);
+        n += self.nonce.encode(&mut buf[n..]);
+        n += self.max_priority_fee_per_gas.encode(&mut buf[n..]);
+        n += self.max_fee_per_gas.encode(&mut buf[n..]);
+        n += self.gas_limit.encode(&mut buf[n..]);
+        n += self.to.encode(&mut buf[n..]);
+        n += self.value.encode(&mut buf[n..]);
+        n += self.data.encode(&mut buf[n..]);
+        n += self.access_list.encode(&mut buf[n..]);
+        n += self.max_fee_per_blob_gas.encode(&mut buf[n..]);
+        n + self.blob_versioned_hashes.encode(&mut buf[n..])
+    }
+
+    fn decode_fields(buf: &mut &'a [u8]) -> Result<Self, rlp::Error> {
+        Ok(Self {
+            chain_id: u64::decode(buf)?,
+            nonce: u64::decode(buf)?,
+            max_priority_fee_per_gas: u128::decode(buf)?,
+            max_fee_per_gas: u128::decode(buf)?,
+            gas_limit: u64::decode(buf)?,
+            to: Address::decode(buf)?,
+            value: uint256::decode(buf)?,
+            data: <&[u8]>::decode(buf)?,
+            access_list: AccessList::decode(buf)?,
+            max_fee_per_blob_gas: u128::decode(buf)?,
+            blob_versioned_hashes: VersionedHashes::decode(buf)?,
+        })
+    }
+}
+
+impl<'a> Eip4844Transaction<'a> {
+    /// Payload length of the signed list
+    #[inline(always)]
+    fn signed_payload_length(&self, signature: &Signature) -> usize {
+        self.fields_length() + signature_length(signature.y_parity as u64, signature)
+    }
+
+    /// Exact length of the signed transaction network encoding
+    pub fn network_length(&self, signature: &Signature, sidecar: &BlobSidecar) -> usize {
+        let body_length = Header::list(self.signed_payload_length(signature)).length_with_payload();
+        typed_length(body_length + sidecar.fields_length())
+    }
+
+    /// Encode the signed transaction with the blob sidecar (network form)
+    /// into the beginning of the buffer
+    ///
+    /// The product is the raw transaction accepted by `eth_sendRawTransaction`.
+    /// The sidecar is not validated, consider [`BlobSidecar::validate`].
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the encoding product;
+    pub fn encode_network(
+        &self,
+        signature: &Signature,
+        sidecar: &BlobSidecar,
+        buf: &mut [u8],
+    ) -> usize {
+        let payload_length = self.signed_payload_length(signature);
+        let body_length = Header::list(payload_length).length_with_payload();
+
+        let mut n = encode_typed(Self::TYPE, body_length + sidecar.fields_length(), buf);
+        n += Header::list(payload_length).encode(&mut buf[n..]);
+        n += self.encode_fields(&mut buf[n..]);
+        n += encode_signature(signature.y_parity as u64, signature, &mut buf[n..]);
+        n + sidecar.encode_fields(&mut buf[n..])
+    }
+
+    /// Sign the transaction and encode the network form into the buffer
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the encoding product;
+    pub fn sign_network(&self, key: &SigningKey, sidecar: &BlobSidecar, buf: &mut [u8]) -> usize {
+        let hash = self.signature_hash(buf);
+        let signature = key.sign_prehash(&hash.into_array());
+        self.encode_network(&signature, sidecar, buf)
+    }
+
+    /// Decode the signed transaction network form, which takes the entire
+    /// input
+    ///
+    /// The sidecar is checked to consist of the blobs, commitments and proofs
+    /// of the expected sizes, but is not validated, nor returned.
+    pub fn decode_network(src: &'a [u8]) -> Result<(Self, Signature), Error> {
+        let mut payload = decode_typed(Self::TYPE, src)?;
+        let mut body = rlp::decode_list(&mut payload)?;
+
+        for blob in rlp::List::<&[u8]>::decode(&mut payload)? {
+            if blob.len() != BYTES_PER_BLOB {
+                return Err(rlp::Error::InvalidLength.into());
+            }
+        }
+
+        // Commitments and proofs, validated on decoding
+        for _ in 0..2 {
+            rlp::List::<Bytes<U48>>::decode(&mut payload)?;
+        }
+
+        if !payload.is_empty() {
+            return Err(rlp::Error::LengthMismatch.into());
+        }
+
+        let tx = Self::decode_fields(&mut body)?;
+        Ok((tx, decode_typed_signature(body)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{address, from_hex, keccak256, secp256k1, typenum::U32, unhex, Bytes};
+
+    static BLOBS: [Blob; 1] = [[0u8; BYTES_PER_BLOB]];
+
+    const HASH: &str = "0x010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014";
+
+    fn commitment() -> Bytes<U48> {
+        let mut commitment = Bytes::<U48>::default();
+        commitment[0] = 0xc0;
+        commitment
+    }
+
+    struct Verifier(bool);
+
+    impl KzgVerifier for Verifier {
+        type Error = ();
+
+        fn verify_blob_kzg_proof_batch(
+            &self,
+            blobs: &[Blob],
+            commitments: &[Bytes<U48>],
+            proofs: &[Bytes<U48>],
+        ) -> Result<(), Self::Error> {
+            assert_eq!(blobs.len(), commitments.len());
+            assert_eq!(blobs.len(), proofs.len());
+            self.0.then_some(()).ok_or(())
+        }
+    }
+
+    #[test]
+    fn test_versioned_hash() {
+        let hash: Bytes<U32> = from_hex(HASH);
+        assert_eq!(kzg_to_versioned_hash(&commitment()), hash);
+
+        let commitments = [commitment()];
+        let sidecar = BlobSidecar {
+            blobs: &BLOBS,
+            commitments: &commitments,
+            proofs: &commitments,
+        };
+
+        assert_eq!(
+            sidecar.validate(VersionedHashes::new(&[hash]), &Verifier(true)),
+            Ok(())
+        );
+        assert_eq!(
+            sidecar.validate(VersionedHashes::new(&[hash]), &Verifier(false)),
+            Err(BlobError::Kzg(()))
+        );
+        assert_eq!(
+            sidecar.validate(VersionedHashes::new(&[hash, hash]), &Verifier(true)),
+            Err(BlobError::CountMismatch)
+        );
+        assert_eq!(
+            sidecar.validate(VersionedHashes::new(&[Bytes::default()]), &Verifier(true)),
+            Err(BlobError::VersionedHashMismatch(0))
+        );
+    }
+
+    #[test]
+    fn test_signing_payload() {
+        let hashes = [from_hex(HASH)];
+        let tx = Eip4844Transaction {
+            chain_id: 1,
+            nonce: 0,
+            max_priority_fee_per_gas: 1_000_000_000,
+            max_fee_per_gas: 30_000_000_000,
+            gas_limit: 21_000,
+            to: address!("0x3535353535353535353535353535353535353535"),
+            value: uint256::ZERO,
+            data: &[],
+            access_list: AccessList::EMPTY,
+            max_fee_per_blob_gas: 1_000_000_000,
+            blob_versioned_hashes: VersionedHashes::new(&hashes),
+        };
+
+        let mut buf = [0u8; 128];
+        let n = tx.encode_for_signing(&mut buf);
+        let expected: [u8; 82] = unhex!(
+            "03f84f0180843b9aca008506fc23ac00825208943535353535353535353535353535\
+            3535353535358080c0843b9aca00e1a0010657f37554c781402a22917dee2f75def7ab\
+            966d7b770905398eba3c444014"
+        );
+
+        assert_eq!(n, tx.signing_length());
+        assert_eq!(&buf[..n], &expected);
+        assert_eq!(tx.signature_hash(&mut buf), keccak256(&expected));
+    }
+
+    #[test]
+    fn test_network() {
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let commitments = [commitment()];
+        let sidecar = BlobSidecar {
+            blobs: &BLOBS,
+            commitments: &commitments,
+            proofs: &commitments,
+        };
+
+        let hashes = [from_hex(HASH)];
+        let tx = Eip4844Transaction {
+            chain_id: 1,
+            gas_limit: 21_000,
+            to: address!("0x3535353535353535353535353535353535353535"),
+            max_fee_per_blob_gas: 1,
+            blob_versioned_hashes: VersionedHashes::new(&hashes),
+            ..Default::default()
+        };
+
+        let mut canonical = [0u8; 256];
+        let hash = tx.signature_hash(&mut canonical);
+        let n = tx.sign(&key, &mut canonical);
+
+        let signature = key.sign_prehash(&hash.into_array());
+        assert_eq!(n, tx.signed_length(&signature));
+        assert_eq!(
+            secp256k1::recover(&hash.into_array(), &signature),
+            Ok(key.address())
+        );
+
+        assert_eq!(
+            Eip4844Transaction::decode_signed(&canonical[..n]),
+            Ok((tx, signature))
+        );
+
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            Eip4844Transaction::recover_sender(&canonical[..n], &mut buf),
+            Ok(key.address())
+        );
+
+        let mut raw = [0u8; BYTES_PER_BLOB + 512];
+        let m = tx.sign_network(&key, &sidecar, &mut raw);
+        assert_eq!(m, tx.network_length(&signature, &sidecar));
+        assert_eq!(raw[0], Eip4844Transaction::TYPE);
+
+        assert_eq!(
+            Eip4844Transaction::decode_network(&raw[..m]),
+            Ok((tx, signature))
+        );
+        assert_eq!(
+            Eip4844Transaction::decode_network(&canonical[..n]),
+            Err(Error::Rlp(rlp::Error::UnexpectedString))
+        );
+        assert_eq!(
+            Eip4844Transaction::decode_signed(&raw[..m]),
+            Err(Error::Rlp(rlp::Error::UnexpectedList))
+        );
+
+        // The network form wraps the canonical signed list
+        let mut wrapper = &raw[1..m];
+        let mut payload = rlp::decode_list(&mut wrapper).unwrap();
+        assert!(wrapper.is_empty());
+
+        let body = rlp::decode_list(&mut payload).unwrap();
+        assert_eq!(body, &canonical[n - body.len()..n]);
+
+        let blobs = rlp::decode_list(&mut payload).unwrap();
+        let blob = rlp::decode_string(&mut &blobs[..]).unwrap();
+        assert_eq!(blob, &BLOBS[0][..]);
+
+        let commitments = rlp::decode_list(&mut payload).unwrap();
+        let proofs = rlp::decode_list(&mut payload).unwrap();
+        assert!(payload.is_empty());
+        assert_eq!(commitments, proofs);
+        assert_eq!(
+            rlp::decode_string(&mut &commitments[..]).unwrap(),
+            &commitment()[..]
+        );
+    }
+}
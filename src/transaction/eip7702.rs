@@ -0,0 +1,461 @@
+use super::{encode_signature, encode_typed, signature_length, AccessList, Transaction};
+use crate::{
+    keccak256,
+    rlp::{self, Decodable, Encodable, Header},
+    secp256k1::{self, SigningKey},
+    typenum::U32,
+    uint256, Address, Bytes, Signature,
+};
+
+/// Authorization to delegate the code of an EOA to the contract at `address`
+///
+/// Signed by the EOA over `keccak256(0x05 || rlp([chain_id, address,
+/// nonce]))`, the chain id of `0` makes the authorization valid on any chain.
+///
+/// ```rust
+/// use ethgen::{address, secp256k1::SigningKey, transaction::Authorization, uint256};
+///
+///
+/// let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+///
+/// let authorization = Authorization {
+///     chain_id: uint256::ONE,
+///     address: address!("0x3535353535353535353535353535353535353535"),
+///     nonce: 0,
+/// };
+///
+/// let signed = authorization.sign(&key);
+/// assert_eq!(signed.recover_authority(), Ok(key.address()));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Authorization {
+    /// Chain id, which is not bounded by the transaction chain id range
+    pub chain_id: uint256,
+    /// Delegation target
+    pub address: Address,
+    /// Nonce of the authority account
+    pub nonce: u64,
+}
+
+impl Authorization {
+    /// Signing payload prefix
+    pub const MAGIC: u8 = 0x05;
+
+    fn fields_length(&self) -> usize {
+        self.chain_id.length() + self.address.length() + self.nonce.length()
+    }
+
+    fn encode_fields(&self, buf: &mut [u8]) -> usize {
+        let mut n = self.chain_id.encode(buf);
+        n += self.address.encode(&mut buf[n..]);
+        n + self.nonce.encode(&mut buf[n..])
+    }
+
+    /// Keccak hash of the signing payload
+    pub fn signature_hash(&self) -> Bytes<U32> {
+        // The payload never exceeds 1 + 2 + 33 + 21 + 9 bytes
+        let mut buf = [0u8; 66];
+        let n = encode_typed(Self::MAGIC, self.fields_length(), &mut buf);
+        let n = n + self.encode_fields(&mut buf[n..]);
+        keccak256(&buf[..n])
+    }
+
+    /// Sign the authorization by the authority key
+    pub fn sign(&self, key: &SigningKey) -> SignedAuthorization {
+        let signature = key.sign_prehash(&self.signature_hash().into_array());
+        SignedAuthorization::new(*self, signature)
+    }
+}
+
+/// Signed authorization tuple, an item of the authorization list
+///
+/// Encoded as `rlp([chain_id, address, nonce, y_parity, r, s])`.
+///
+/// The signature fields are kept as decoded: an invalid authorization does
+/// not invalidate the transaction and is only skipped on execution, so the
+/// parity is checked by [`Self::recover_authority`] rather than on decoding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SignedAuthorization {
+    pub authorization: Authorization,
+    /// Signature parity, valid values are `0` and `1`
+    pub y_parity: u8,
+    pub r: Bytes<U32>,
+    pub s: Bytes<U32>,
+}
+
+impl SignedAuthorization {
+    #[inline(always)]
+    pub const fn new(authorization: Authorization, signature: Signature) -> Self {
+        Self {
+            authorization,
+            y_parity: signature.y_parity as u8,
+            r: signature.r,
+            s: signature.s,
+        }
+    }
+
+    /// The signature, if the parity is valid
+    pub const fn signature(&self) -> Option<Signature> {
+        match self.y_parity {
+            0 | 1 => Some(Signature::new(self.r, self.s, self.y_parity == 1)),
+            _ => None,
+        }
+    }
+
+    /// Recover the address of the account which signed the authorization
+    pub fn recover_authority(&self) -> Result<Address, secp256k1::Error> {
+        let signature = self.signature().ok_or(secp256k1::Error::InvalidSignature)?;
+        let hash = self.authorization.signature_hash();
+        secp256k1::recover(&hash.into_array(), &signature)
+    }
+
+    fn signature_fields(&self) -> (u64, Signature) {
+        (self.y_parity as u64, Signature::new(self.r, self.s, false))
+    }
+
+    fn payload_length(&self) -> usize {
+        let (v, signature) = self.signature_fields();
+        self.authorization.fields_length() + signature_length(v, &signature)
+    }
+}
+
+impl Encodable for SignedAuthorization {
+    fn length(&self) -> usize {
+        Header::list(self.payload_length()).length_with_payload()
+    }
+
+    fn encode(&self, buf: &mut [u8]) -> usize {
+        let (v, signature) = self.signature_fields();
+
+        let mut n = Header::list(self.payload_length()).encode(buf);
+        n += self.authorization.encode_fields(&mut buf[n..]);
+        n + encode_signature(v, &signature, &mut buf[n..])
+    }
+}
+
+impl Decodable<'_> for SignedAuthorization {
+    fn decode(buf: &mut &[u8]) -> Result<Self, rlp::Error> {
+        let mut payload = rlp::decode_list(buf)?;
+
+        let authorization = Authorization {
+            chain_id: uint256::decode(&mut payload)?,
+            address: Address::decode(&mut payload)?,
+            nonce: u64::decode(&mut payload)?,
+        };
+
+        let y_parity = u8::decode(&mut payload)?;
+        let r = uint256::decode(&mut payload)?.into();
+        let s = uint256::decode(&mut payload)?.into();
+
+        if !payload.is_empty() {
+            return Err(rlp::Error::LengthMismatch);
+        }
+
+        Ok(Self {
+            authorization,
+            y_parity,
+            r,
+            s,
+        })
+    }
+}
+
+/// Authorization list introduced by [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702)
+///
+/// A borrowed view over the signed authorizations, which are either
+/// provided as a slice or decoded from RLP (the payload is validated upfront
+/// and is iterated lazily).
+///
+/// ```rust
+/// use ethgen::{
+///     address, rlp::{self, Encodable}, secp256k1::SigningKey,
+///     transaction::{Authorization, AuthorizationList}, uint256,
+/// };
+///
+///
+/// let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+/// let items = [Authorization {
+///     chain_id: uint256::ZERO,
+///     address: address!("0x3535353535353535353535353535353535353535"),
+///     nonce: 7,
+/// }
+/// .sign(&key)];
+///
+/// let list = AuthorizationList::new(&items);
+///
+/// let mut buf = [0u8; 128];
+/// let n = list.encode(&mut buf);
+///
+/// let decoded: AuthorizationList = rlp::decode(&buf[..n]).unwrap();
+/// assert_eq!(decoded, list);
+/// assert_eq!(decoded.iter().next().unwrap().recover_authority(), Ok(key.address()));
+/// ```
+pub type AuthorizationList<'a> = rlp::List<'a, SignedAuthorization>;
+
+/// Iterator over the authorization list items
+pub type AuthorizationListIter<'a> = rlp::ListIter<'a, SignedAuthorization>;
+
+/// Set code transaction introduced by [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702)
+///
+/// Encoded as `0x04 || rlp([chain_id, nonce, max_priority_fee_per_gas,
+/// max_fee_per_gas, gas_limit, to, value, data, access_list,
+/// authorization_list])` for signing and with the `y_parity, r, s` fields
+/// appended when signed. All encodings are written into caller provided
+/// buffers.
+///
+/// ```rust
+/// use ethgen::{
+///     address, secp256k1::SigningKey,
+///     transaction::{Authorization, AuthorizationList, Eip7702Transaction, Transaction},
+///     uint256,
+/// };
+///
+///
+/// let eoa = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+/// let sponsor = SigningKey::from_bytes(&[0x47u8; 32]).unwrap();
+///
+/// // Delegate the EOA to the smart account implementation
+/// let authorizations = [Authorization {
+///     chain_id: uint256::ONE,
+///     address: address!("0x3535353535353535353535353535353535353535"),
+///     nonce: 0,
+/// }
+/// .sign(&eoa)];
+///
+/// let tx = Eip7702Transaction {
+///     chain_id: 1,
+///     max_priority_fee_per_gas: 1_000_000_000,
+///     max_fee_per_gas: 30_000_000_000,
+///     gas_limit: 100_000,
+///     to: eoa.address(),
+///     authorization_list: AuthorizationList::new(&authorizations),
+///     ..Default::default()
+/// };
+///
+/// let mut raw = [0u8; 256];
+/// let n = tx.sign(&sponsor, &mut raw);
+/// assert_eq!(0x04, raw[0]);
+///
+/// let mut buf = [0u8; 256];
+/// let (decoded, _) = Eip7702Transaction::decode_signed(&raw[..n]).unwrap();
+///
+/// assert_eq!(decoded, tx);
+/// assert_eq!(Eip7702Transaction::recover_sender(&raw[..n], &mut buf), Ok(sponsor.address()));
+///
+/// let authorization = decoded.authorization_list.iter().next().unwrap();
+/// assert_eq!(authorization.recover_authority(), Ok(eoa.address()));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Eip7702Transaction<'a> {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub gas_limit: u64,
+    /// Recipient, set code transactions can not create contracts
+    pub to: Address,
+    pub value: uint256,
+    pub data: &'a [u8],
+    pub access_list: AccessList<'a>,
+    pub authorization_list: AuthorizationList<'a>,
+}
+
+impl<'a> Transaction<'a> for Eip7702Transaction<'a> {
+    const TYPE: u8 = 0x04;
+
+    fn fields_length(&self) -> usize {
+        self.chain_id.length()
+            + self.nonce.length()
+            + self.max_priority_fee_per_gas.length()
+            + self.max_fee_per_gas.length()
+            + self.gas_limit.length()
+            + self.to.length()
+            + self.value.length()
+            + self.data.length()
+            + self.access_list.length()
+            + self.authorization_list.length()
+    }
+
+    fn encode_fields(&self, buf: &mut [u8]) -> usize {
+        let mut n = self.chain_id.encode(buf);
+        n += self.nonce.encode(&mut buf[n..]);
+        n += self.max_priority_fee_per_gas.encode(&mut buf[n..]);
+        n += self.max_fee_per_gas.encode(&mut buf[n..]);
+        n += self.gas_limit.encode(&mut buf[n..]);
+        n += self.to.encode(&mut buf[n..]);
+        n += self.value.encode(&mut buf[n..]);
+        n += self.data.encode(&mut buf[n..]);
+        n += self.access_list.encode(&mut buf[n..]);
+        n + self.authorization_list.encode(&mut buf[n..])
+    }
+
+    fn decode_fields(buf: &mut &'a [u8]) -> Result<Self, rlp::Error> {
+        Ok(Self {
+            chain_id: u64::decode(buf)?,
+            nonce: u64::decode(buf)?,
+            max_priority_fee_per_gas: u128::decode(buf)?,
+            max_fee_per_gas: u128::decode(buf)?,
+            gas_limit: u64::decode(buf)?,
+            to: Address::decode(buf)?,
+            value: uint256::decode(buf)?,
+            data: <&[u8]>::decode(buf)?,
+            access_list: AccessList::decode(buf)?,
+            authorization_list: AuthorizationList::decode(buf)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        address, keccak256,
+        transaction::{AccessListItem, Error, SigningKey},
+        unhex, Bytes,
+    };
+
+    fn authorization() -> Authorization {
+        Authorization {
+            chain_id: uint256::ONE,
+            address: address!("0x3535353535353535353535353535353535353535"),
+            nonce: 0,
+        }
+    }
+
+    #[test]
+    fn test_authorization() {
+        let expected: [u8; 25] = unhex!("05d70194353535353535353535353535353535353535353580");
+        assert_eq!(authorization().signature_hash(), keccak256(&expected));
+
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let signed = authorization().sign(&key);
+        assert_eq!(signed.recover_authority(), Ok(key.address()));
+
+        let mut buf = [0u8; 128];
+        let n = signed.encode(&mut buf);
+        assert_eq!(n, signed.length());
+        assert_eq!(rlp::decode::<SignedAuthorization>(&buf[..n]), Ok(signed));
+
+        // Tampered authorization recovers another authority
+        let tampered = SignedAuthorization {
+            authorization: Authorization {
+                nonce: 1,
+                ..signed.authorization
+            },
+            ..signed
+        };
+        assert_ne!(tampered.recover_authority(), Ok(key.address()));
+
+        // Parity other than 0 / 1 is decoded and fails on recovery, it
+        // follows the list header and `chain_id, address, nonce` fields
+        let v = 2 + 1 + 21 + 1;
+        assert!(matches!(buf[v], 0x80 | 0x01));
+        buf[v] = 0x1b;
+        let invalid = rlp::decode::<SignedAuthorization>(&buf[..n]).unwrap();
+        assert_eq!(invalid.y_parity, 0x1b);
+        assert_eq!(invalid.signature(), None);
+        assert_eq!(
+            invalid.recover_authority(),
+            Err(secp256k1::Error::InvalidSignature)
+        );
+
+        let mut tmp = [0u8; 128];
+        let m = invalid.encode(&mut tmp);
+        assert_eq!(&tmp[..m], &buf[..n]);
+
+        // Parity is bounded by a byte
+        let a = signed.authorization;
+        let fields = (a.chain_id, a.address, a.nonce, 0x100u16, signed.r, signed.s);
+        let n = fields.encode(&mut buf);
+        assert_eq!(
+            rlp::decode::<SignedAuthorization>(&buf[..n]),
+            Err(rlp::Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_chain_id_range() {
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+
+        for chain_id in [uint256::from_u128(u64::MAX as u128 + 1), uint256::MAX] {
+            let signed = Authorization {
+                chain_id,
+                ..authorization()
+            }
+            .sign(&key);
+            assert_eq!(signed.recover_authority(), Ok(key.address()));
+
+            let mut buf = [0u8; 128];
+            let n = signed.encode(&mut buf);
+            assert_eq!(n, signed.length());
+            assert_eq!(rlp::decode::<SignedAuthorization>(&buf[..n]), Ok(signed));
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let eoa = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let sponsor = SigningKey::from_bytes(&[0x47u8; 32]).unwrap();
+
+        let authorizations = [
+            authorization().sign(&eoa),
+            Authorization {
+                chain_id: uint256::ZERO,
+                nonce: 1,
+                ..authorization()
+            }
+            .sign(&eoa),
+        ];
+
+        let keys = [Bytes::from_array([0x01u8; 32])];
+        let items = [AccessListItem::new(
+            address!("0x3535353535353535353535353535353535353535"),
+            &keys,
+        )];
+
+        let tx = Eip7702Transaction {
+            chain_id: 1,
+            nonce: 3,
+            max_priority_fee_per_gas: 1_000_000_000,
+            max_fee_per_gas: 30_000_000_000,
+            gas_limit: 100_000,
+            to: eoa.address(),
+            value: uint256::ZERO,
+            data: &[0xde, 0xad, 0xbe, 0xef],
+            access_list: AccessList::new(&items),
+            authorization_list: AuthorizationList::new(&authorizations),
+        };
+
+        let mut raw = [0u8; 512];
+        let n = tx.sign(&sponsor, &mut raw);
+
+        let (decoded, signature) = Eip7702Transaction::decode_signed(&raw[..n]).unwrap();
+        assert_eq!(decoded, tx);
+        assert_eq!(decoded.authorization_list.len(), 2);
+        assert_eq!(n, decoded.signed_length(&signature));
+
+        let mut buf = [0u8; 512];
+        let m = decoded.encode_signed(&signature, &mut buf);
+        assert_eq!(&buf[..m], &raw[..n]);
+
+        assert_eq!(
+            Eip7702Transaction::recover_sender(&raw[..n], &mut buf),
+            Ok(sponsor.address())
+        );
+
+        for authorization in decoded.authorization_list {
+            assert_eq!(authorization.recover_authority(), Ok(eoa.address()));
+        }
+
+        assert_eq!(
+            Eip7702Transaction::decode_signed(&raw[..n - 1]),
+            Err(Error::Rlp(rlp::Error::UnexpectedEnd))
+        );
+
+        raw[0] = 0x02;
+        assert_eq!(
+            Eip7702Transaction::decode_signed(&raw[..n]),
+            Err(Error::UnexpectedType(0x02))
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use super::{
+    decode_recipient, decode_signature, encode_signature, recipient, signature_length, Error,
+    Transaction,
+};
+use crate::{
+    rlp::{self, Decodable, Encodable, Header},
+    uint256, Address, Signature,
+};
+
+/// Legacy (untyped) transaction
+///
+/// Signed either with the original scheme, hashing `rlp([nonce, gas_price,
+/// gas_limit, to, value, data])` with `v = 27 / 28`, or with the replay
+/// protection introduced by [EIP-155](https://eips.ethereum.org/EIPS/eip-155),
+/// which appends `chain_id, 0, 0` to the signing payload and encodes the chain
+/// id into `v`. The scheme is selected by the `chain_id` field.
+///
+/// ```rust
+/// use ethgen::{
+///     address, secp256k1::SigningKey,
+///     transaction::{LegacyTransaction, Transaction},
+///     uint256,
+/// };
+///
+///
+/// let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+///
+/// let tx = LegacyTransaction {
+///     chain_id: Some(1),
+///     nonce: 9,
+///     gas_price: 20_000_000_000,
+///     gas_limit: 21_000,
+///     to: Some(address!("0x3535353535353535353535353535353535353535")),
+///     value: uint256::from(1_000_000_000_000_000_000u64),
+///     data: &[],
+/// };
+///
+/// let mut raw = [0u8; 128];
+/// let n = tx.sign(&key, &mut raw);
+///
+/// let mut buf = [0u8; 128];
+/// let (decoded, signature) = LegacyTransaction::decode_signed(&raw[..n]).unwrap();
+///
+/// assert_eq!(decoded, tx);
+/// assert_eq!(signature.eip155_v(1), 37);
+/// assert_eq!(LegacyTransaction::recover_sender(&raw[..n], &mut buf), Ok(key.address()));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LegacyTransaction<'a> {
+    /// Chain id for the EIP-155 replay protection, `None` for the original
+    /// signing scheme
+    pub chain_id: Option<u64>,
+    pub nonce: u64,
+    pub gas_price: u128,
+    pub gas_limit: u64,
+    /// Recipient, `None` for the contract creation
+    pub to: Option<Address>,
+    pub value: uint256,
+    pub data: &'a [u8],
+}
+
+impl LegacyTransaction<'_> {
+    /// The EIP-155 `chain_id, 0, 0` fields length
+    fn replay_protection_length(&self) -> usize {
+        self.chain_id.map_or(0, |chain_id| chain_id.length() + 2)
+    }
+
+    /// The `v` value for the signature according to the signing scheme
+    ///
+    /// ## Panics
+    ///
+    /// - if the chain id exceeds [`Signature::MAX_CHAIN_ID`];
+    pub const fn v(&self, signature: &Signature) -> u64 {
+        match self.chain_id {
+            Some(chain_id) => signature.eip155_v(chain_id),
+            None => signature.v() as u64,
+        }
+    }
+}
+
+impl<'a> Transaction<'a> for LegacyTransaction<'a> {
+    const TYPE: u8 = 0x00;
+
+    fn fields_length(&self) -> usize {
+        self.nonce.length()
+            + self.gas_price.length()
+            + self.gas_limit.length()
+            + recipient(&self.to).length()
+            + self.value.length()
+            + self.data.length()
+    }
+
+    fn encode_fields(&self, buf: &mut [u8]) -> usize {
+        let mut n = self.nonce.encode(buf);
+        n += self.gas_price.encode(&mut buf[n..]);
+        n += self.gas_limit.encode(&mut buf[n..]);
+        n += recipient(&self.to).encode(&mut buf[n..]);
+        n += self.value.encode(&mut buf[n..]);
+        n + self.data.encode(&mut buf[n..])
+    }
+
+    /// Decode the list fields, leaving the chain id unset, since it is
+    /// encoded into the signature `v`
+    fn decode_fields(buf: &mut &'a [u8]) -> Result<Self, rlp::Error> {
+        Ok(Self {
+            chain_id: None,
+            nonce: u64::decode(buf)?,
+            gas_price: u128::decode(buf)?,
+            gas_limit: u64::decode(buf)?,
+            to: decode_recipient(buf)?,
+            value: uint256::decode(buf)?,
+            data: <&[u8]>::decode(buf)?,
+        })
+    }
+
+    fn signing_length(&self) -> usize {
+        let payload_length = self.fields_length() + self.replay_protection_length();
+        Header::list(payload_length).length_with_payload()
+    }
+
+    fn encode_for_signing(&self, buf: &mut [u8]) -> usize {
+        let payload_length = self.fields_length() + self.replay_protection_length();
+
+        let mut n = Header::list(payload_length).encode(buf);
+        n += self.encode_fields(&mut buf[n..]);
+
+        if let Some(chain_id) = self.chain_id {
+            n += chain_id.encode(&mut buf[n..]);
+            n += 0u8.encode(&mut buf[n..]);
+            n += 0u8.encode(&mut buf[n..]);
+        }
+
+        n
+    }
+
+    #[inline(always)]
+    fn signed_length(&self, signature: &Signature) -> usize {
+        let payload_length = self.fields_length() + signature_length(self.v(signature), signature);
+        Header::list(payload_length).length_with_payload()
+    }
+
+    fn encode_signed(&self, signature: &Signature, buf: &mut [u8]) -> usize {
+        let v = self.v(signature);
+        let payload_length = self.fields_length() + signature_length(v, signature);
+
+        let mut n = Header::list(payload_length).encode(buf);
+        n += self.encode_fields(&mut buf[n..]);
+        n + encode_signature(v, signature, &mut buf[n..])
+    }
+
+    /// Decode the signed transaction, which takes the entire input
+    ///
+    /// The signing scheme is derived from `v`: `27 / 28` for the original
+    /// one, otherwise the chain id is extracted as per EIP-155.
+    fn decode_signed(src: &'a [u8]) -> Result<(Self, Signature), Error> {
+        let mut buf = src;
+        let mut payload = rlp::decode_list(&mut buf)?;
+
+        if !buf.is_empty() {
+            return Err(rlp::Error::LengthMismatch.into());
+        }
+
+        let tx = Self::decode_fields(&mut payload)?;
+        let (v, r, s) = decode_signature(&mut payload)?;
+
+        if !payload.is_empty() {
+            return Err(rlp::Error::LengthMismatch.into());
+        }
+
+        // Typed transaction parity values are not valid for legacy ones
+        let (y_parity, chain_id) = match v {
+            27.. => Signature::parse_v(v)?,
+            _ => return Err(Error::InvalidV),
+        };
+
+        Ok((Self { chain_id, ..tx }, Signature::new(r, s, y_parity)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{address, from_hex, transaction::SigningKey, unhex};
+
+    // EIP-155 example
+    fn vector() -> LegacyTransaction<'static> {
+        LegacyTransaction {
+            chain_id: Some(1),
+            nonce: 9,
+            gas_price: 20_000_000_000,
+            gas_limit: 21_000,
+            to: Some(address!("0x3535353535353535353535353535353535353535")),
+            value: uint256::from(1_000_000_000_000_000_000u64),
+            data: &[],
+        }
+    }
+
+    const SIGNED: [u8; 110] = unhex!(
+        "f86c098504a817c800825208943535353535353535353535353535353535353535880d\
+        e0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1\
+        590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1\
+        966a3b6d83"
+    );
+
+    #[test]
+    fn test_eip155() {
+        let tx = vector();
+
+        let mut buf = [0u8; 128];
+        let n = tx.encode_for_signing(&mut buf);
+        let expected: [u8; 45] = unhex!(
+            "ec098504a817c800825208943535353535353535353535353535353535353535880d\
+            e0b6b3a764000080018080"
+        );
+
+        assert_eq!(n, tx.signing_length());
+        assert_eq!(&buf[..n], &expected);
+
+        let hash: [u8; 32] =
+            unhex!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53");
+        assert_eq!(tx.signature_hash(&mut buf).into_array(), hash);
+
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let n = tx.sign(&key, &mut buf);
+        assert_eq!(&buf[..n], &SIGNED);
+    }
+
+    #[test]
+    fn test_decode() {
+        let (tx, signature) = LegacyTransaction::decode_signed(&SIGNED).unwrap();
+        assert_eq!(tx, vector());
+        assert_eq!(
+            signature,
+            Signature::new(
+                from_hex("0x28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276"),
+                from_hex("0x67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"),
+                false,
+            )
+        );
+        assert_eq!(tx.signed_length(&signature), SIGNED.len());
+
+        let mut buf = [0u8; 128];
+        assert_eq!(
+            LegacyTransaction::recover_sender(&SIGNED, &mut buf),
+            Ok(address!("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"))
+        );
+
+        assert_eq!(
+            LegacyTransaction::decode_signed(&SIGNED[..SIGNED.len() - 1]),
+            Err(Error::Rlp(rlp::Error::UnexpectedEnd))
+        );
+    }
+
+    #[test]
+    fn test_pre_eip155() {
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let tx = LegacyTransaction {
+            chain_id: None,
+            to: None,
+            data: &[0x60, 0x80, 0x60, 0x40],
+            ..vector()
+        };
+
+        let mut raw = [0u8; 128];
+        let n = tx.sign(&key, &mut raw);
+
+        let (decoded, signature) = LegacyTransaction::decode_signed(&raw[..n]).unwrap();
+        assert_eq!(decoded, tx);
+        assert!(matches!(tx.v(&signature), 27 | 28));
+
+        let mut buf = [0u8; 128];
+        assert_eq!(
+            LegacyTransaction::recover_sender(&raw[..n], &mut buf),
+            Ok(key.address())
+        );
+
+        // Typed transaction parity is rejected
+        let v = raw.iter().position(|b| *b == 0xa0).unwrap() - 1;
+        assert!(matches!(raw[v], 27 | 28));
+
+        raw[v] = 0x01;
+        assert_eq!(
+            LegacyTransaction::decode_signed(&raw[..n]),
+            Err(Error::InvalidV)
+        );
+    }
+}
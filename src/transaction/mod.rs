@@ -0,0 +1,284 @@
+//! Transactions
+//!
+//! Typed ([EIP-2718](https://eips.ethereum.org/EIPS/eip-2718)) transaction
+//! envelopes, their signing payloads and signed encodings. All encodings are
+//! written into caller provided buffers, the required capacity is reported
+//! by the corresponding `*_length` methods.
+
+use crate::{
+    keccak256,
+    rlp::{self, Decodable, Encodable, Header},
+    secp256k1::{self, SigningKey},
+    typenum::U32,
+    uint256, Address, Bytes, Signature, SignatureError,
+};
+use core::fmt;
+
+/// The error type returned when decoding or recovering a transaction fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Malformed RLP encoding
+    Rlp(rlp::Error),
+    /// Signature `v` value is invalid for the transaction type
+    InvalidV,
+    /// Signer can not be recovered from the signature
+    Recovery(secp256k1::Error),
+    /// The envelope type byte does not match the transaction type
+    UnexpectedType(u8),
+}
+
+impl From<rlp::Error> for Error {
+    fn from(value: rlp::Error) -> Self {
+        Self::Rlp(value)
+    }
+}
+
+impl From<SignatureError> for Error {
+    fn from(value: SignatureError) -> Self {
+        match value {
+            SignatureError::InvalidV => Self::InvalidV,
+        }
+    }
+}
+
+impl From<secp256k1::Error> for Error {
+    fn from(value: secp256k1::Error) -> Self {
+        Self::Recovery(value)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rlp(err) => write!(f, "invalid transaction encoding: {}", err),
+            Self::InvalidV => write!(f, "invalid transaction signature v value"),
+            Self::Recovery(err) => write!(f, "failed to recover transaction signer: {}", err),
+            Self::UnexpectedType(ty) => write!(f, "unexpected transaction type: {:#04x}", ty),
+        }
+    }
+}
+
+/// Signing, encoding and decoding shared by the transaction types
+///
+/// A transaction type provides its list fields, while the signing payload
+/// `type || rlp([fields])` and the signed form `type || rlp([fields,
+/// y_parity, r, s])` are derived from them. The legacy transaction overrides
+/// the derived methods with its own signing schemes.
+pub trait Transaction<'a>: Sized {
+    /// EIP-2718 transaction type, `0x00` for the legacy transaction
+    const TYPE: u8;
+
+    /// Length of the list fields, excluding the signature
+    fn fields_length(&self) -> usize;
+
+    /// Encode the list fields, excluding the signature, into the beginning
+    /// of the buffer
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the encoding product;
+    fn encode_fields(&self, buf: &mut [u8]) -> usize;
+
+    /// Decode the list fields, excluding the signature, advancing the input
+    fn decode_fields(buf: &mut &'a [u8]) -> Result<Self, rlp::Error>;
+
+    /// Exact length of the signing payload
+    #[inline(always)]
+    fn signing_length(&self) -> usize {
+        typed_length(self.fields_length())
+    }
+
+    /// Encode the signing payload into the beginning of the buffer
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the encoding product;
+    fn encode_for_signing(&self, buf: &mut [u8]) -> usize {
+        let n = encode_typed(Self::TYPE, self.fields_length(), buf);
+        n + self.encode_fields(&mut buf[n..])
+    }
+
+    /// Keccak hash of the signing payload, using the buffer as scratch space
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the signing payload;
+    fn signature_hash(&self, buf: &mut [u8]) -> Bytes<U32> {
+        let n = self.encode_for_signing(buf);
+        keccak256(&buf[..n])
+    }
+
+    /// Exact length of the signed transaction encoding
+    ///
+    /// ## Panics
+    ///
+    /// - if the legacy transaction chain id exceeds [`Signature::MAX_CHAIN_ID`];
+    #[inline(always)]
+    fn signed_length(&self, signature: &Signature) -> usize {
+        let v = signature.y_parity as u64;
+        typed_length(self.fields_length() + signature_length(v, signature))
+    }
+
+    /// Encode the signed transaction into the beginning of the buffer
+    ///
+    /// The product is the form included into blocks, its Keccak hash is the
+    /// transaction hash. It is also the raw transaction accepted by
+    /// `eth_sendRawTransaction`, except for the blob transaction, which is
+    /// submitted in the [network form](Eip4844Transaction::encode_network).
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the encoding product;
+    /// - if the legacy transaction chain id exceeds [`Signature::MAX_CHAIN_ID`];
+    fn encode_signed(&self, signature: &Signature, buf: &mut [u8]) -> usize {
+        let v = signature.y_parity as u64;
+        let payload_length = self.fields_length() + signature_length(v, signature);
+
+        let mut n = encode_typed(Self::TYPE, payload_length, buf);
+        n += self.encode_fields(&mut buf[n..]);
+        n + encode_signature(v, signature, &mut buf[n..])
+    }
+
+    /// Sign the transaction and encode the signed form into the buffer
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the encoding product;
+    /// - if the legacy transaction chain id exceeds [`Signature::MAX_CHAIN_ID`];
+    fn sign(&self, key: &SigningKey, buf: &mut [u8]) -> usize {
+        let hash = self.signature_hash(buf);
+        let signature = key.sign_prehash(&hash.into_array());
+        self.encode_signed(&signature, buf)
+    }
+
+    /// Decode the signed transaction, which takes the entire input
+    fn decode_signed(src: &'a [u8]) -> Result<(Self, Signature), Error> {
+        let mut payload = decode_typed(Self::TYPE, src)?;
+        let tx = Self::decode_fields(&mut payload)?;
+
+        Ok((tx, decode_typed_signature(payload)?))
+    }
+
+    /// Recover the sender of the signed transaction
+    ///
+    /// The buffer is used as scratch space for the signing payload, which is
+    /// never larger than the signed transaction itself.
+    fn recover_sender(src: &'a [u8], buf: &mut [u8]) -> Result<Address, Error> {
+        let (tx, signature) = Self::decode_signed(src)?;
+        let hash = tx.signature_hash(buf);
+
+        secp256k1::recover(&hash.into_array(), &signature).map_err(Error::from)
+    }
+}
+
+mod access_list;
+pub use access_list::{
+    AccessList, AccessListBuf, AccessListItem, AccessListIter, CapacityError, StorageKeys,
+    StorageKeysIter,
+};
+
+mod eip2930;
+pub use eip2930::Eip2930Transaction;
+
+mod eip1559;
+pub use eip1559::Eip1559Transaction;
+
+mod eip4844;
+pub use eip4844::{
+    kzg_to_versioned_hash, Blob, BlobError, BlobSidecar, Eip4844Transaction, KzgVerifier,
+    VersionedHashes, BYTES_PER_BLOB, VERSIONED_HASH_VERSION_KZG,
+};
+
+mod eip7702;
+pub use eip7702::{
+    Authorization, AuthorizationList, AuthorizationListIter, Eip7702Transaction,
+    SignedAuthorization,
+};
+
+mod legacy;
+pub use legacy::LegacyTransaction;
+
+mod typed;
+pub use typed::{DecodeError, TypedTransaction};
+
+mod words;
+pub use words::{Words, WordsIter};
+
+/// Recipient encoded as a string, empty for the contract creation
+#[inline(always)]
+fn recipient(to: &Option<Address>) -> &[u8] {
+    to.as_ref().map_or(&[], Address::as_slice)
+}
+
+fn decode_recipient(buf: &mut &[u8]) -> Result<Option<Address>, rlp::Error> {
+    match rlp::decode_string(buf)? {
+        [] => Ok(None),
+        to if to.len() == 20 => Ok(Some(Address::from(*Bytes::from_slice(to)))),
+        _ => Err(rlp::Error::InvalidLength),
+    }
+}
+
+/// Length of the typed envelope: `type || rlp(list)`
+#[inline(always)]
+fn typed_length(payload_length: usize) -> usize {
+    1 + Header::list(payload_length).length_with_payload()
+}
+
+/// Encode the typed envelope prefix: type byte and list header
+fn encode_typed(ty: u8, payload_length: usize, buf: &mut [u8]) -> usize {
+    buf[0] = ty;
+    1 + Header::list(payload_length).encode(&mut buf[1..])
+}
+
+/// Length of the `v, r, s` signature fields, `r` and `s` are integers
+fn signature_length(v: u64, signature: &Signature) -> usize {
+    v.length()
+        + rlp::string_length(rlp::trim(&signature.r))
+        + rlp::string_length(rlp::trim(&signature.s))
+}
+
+fn encode_signature(v: u64, signature: &Signature, buf: &mut [u8]) -> usize {
+    let mut n = v.encode(buf);
+    n += rlp::encode_string(rlp::trim(&signature.r), &mut buf[n..]);
+    n + rlp::encode_string(rlp::trim(&signature.s), &mut buf[n..])
+}
+
+/// Decode the `v, r, s` signature fields
+///
+/// Returns `v` as is, since its meaning depends on the transaction type.
+fn decode_signature(buf: &mut &[u8]) -> Result<(u64, Bytes<U32>, Bytes<U32>), rlp::Error> {
+    let v = u64::decode(buf)?;
+    let r = uint256::decode(buf)?;
+    let s = uint256::decode(buf)?;
+
+    Ok((v, r.into(), s.into()))
+}
+
+/// Decode the typed envelope, which takes the entire input, returning the
+/// list payload
+fn decode_typed(ty: u8, src: &[u8]) -> Result<&[u8], Error> {
+    let (&first, mut buf) = src.split_first().ok_or(rlp::Error::UnexpectedEnd)?;
+    if first != ty {
+        return Err(Error::UnexpectedType(first));
+    }
+
+    let payload = rlp::decode_list(&mut buf)?;
+    match buf.is_empty() {
+        true => Ok(payload),
+        false => Err(rlp::Error::LengthMismatch.into()),
+    }
+}
+
+/// Decode the trailing `y_parity, r, s` fields of the typed transaction
+fn decode_typed_signature(mut buf: &[u8]) -> Result<Signature, Error> {
+    let (v, r, s) = decode_signature(&mut buf)?;
+
+    if !buf.is_empty() {
+        return Err(rlp::Error::LengthMismatch.into());
+    }
+
+    match v {
+        0 | 1 => Ok(Signature::new(r, s, v == 1)),
+        _ => Err(Error::InvalidV),
+    }
+}
@@ -0,0 +1,383 @@
+use super::{
+    Eip1559Transaction, Eip2930Transaction, Eip4844Transaction, Eip7702Transaction, Error,
+    LegacyTransaction, Transaction,
+};
+use crate::{keccak256, rlp, secp256k1, typenum::U32, Address, Bytes, Signature};
+use core::fmt;
+
+/// The error type returned when decoding a transaction envelope fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError<'a> {
+    /// Transaction type is not supported, the payload following the type
+    /// byte is preserved as is
+    UnknownType { ty: u8, payload: &'a [u8] },
+    /// Transaction of a known type is malformed
+    Invalid(Error),
+}
+
+impl From<Error> for DecodeError<'_> {
+    fn from(value: Error) -> Self {
+        Self::Invalid(value)
+    }
+}
+
+impl From<rlp::Error> for DecodeError<'_> {
+    fn from(value: rlp::Error) -> Self {
+        Self::Invalid(value.into())
+    }
+}
+
+impl fmt::Display for DecodeError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownType { ty, .. } => write!(f, "unknown transaction type: {:#04x}", ty),
+            Self::Invalid(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Signed transaction of any supported type
+///
+/// Dispatches on the [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718)
+/// envelope type byte, while the legacy transactions are recognized by the
+/// leading RLP list header. Blob transactions are accepted both in the
+/// canonical form (as returned by `eth_getRawTransactionByHash`) and in the
+/// network form (as submitted to `eth_sendRawTransaction`), the sidecar of the
+/// latter is skipped.
+///
+/// ```rust
+/// use ethgen::{
+///     address, keccak256, secp256k1::SigningKey,
+///     transaction::{DecodeError, Eip1559Transaction, Transaction, TypedTransaction},
+/// };
+///
+///
+/// let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+///
+/// let tx = Eip1559Transaction {
+///     chain_id: 1,
+///     max_fee_per_gas: 30_000_000_000,
+///     gas_limit: 21_000,
+///     to: Some(address!("0x3535353535353535353535353535353535353535")),
+///     ..Default::default()
+/// };
+///
+/// let mut raw = [0u8; 256];
+/// let n = tx.sign(&key, &mut raw);
+///
+/// let mut buf = [0u8; 256];
+/// let decoded = TypedTransaction::decode(&raw[..n]).unwrap();
+///
+/// assert!(matches!(decoded, TypedTransaction::Eip1559(decoded, _) if decoded == tx));
+/// assert_eq!(decoded.ty(), Eip1559Transaction::TYPE);
+/// assert_eq!(decoded.hash(&mut buf), keccak256(&raw[..n]));
+/// assert_eq!(decoded.recover_sender(&mut buf), Ok(key.address()));
+///
+/// // Unsupported types are reported with the raw payload
+/// assert_eq!(
+///     TypedTransaction::decode(b"\x7e\xc0"),
+///     Err(DecodeError::UnknownType { ty: 0x7e, payload: b"\xc0" }),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypedTransaction<'a> {
+    Legacy(LegacyTransaction<'a>, Signature),
+    Eip2930(Eip2930Transaction<'a>, Signature),
+    Eip1559(Eip1559Transaction<'a>, Signature),
+    Eip4844(Eip4844Transaction<'a>, Signature),
+    Eip7702(Eip7702Transaction<'a>, Signature),
+}
+
+/// Apply the expression to the transaction and the signature of any variant
+macro_rules! dispatch {
+    ($self:expr, $tx:ident, $signature:ident => $expr:expr) => {
+        match $self {
+            TypedTransaction::Legacy($tx, $signature) => $expr,
+            TypedTransaction::Eip2930($tx, $signature) => $expr,
+            TypedTransaction::Eip1559($tx, $signature) => $expr,
+            TypedTransaction::Eip4844($tx, $signature) => $expr,
+            TypedTransaction::Eip7702($tx, $signature) => $expr,
+        }
+    };
+}
+
+impl<'a> TypedTransaction<'a> {
+    /// Decode the signed transaction, which takes the entire input
+    pub fn decode(src: &'a [u8]) -> Result<Self, DecodeError<'a>> {
+        let (&ty, payload) = src.split_first().ok_or(rlp::Error::UnexpectedEnd)?;
+
+        let tx = match ty {
+            Eip2930Transaction::TYPE => Eip2930Transaction::decode_signed(src)
+                .map(|(tx, signature)| Self::Eip2930(tx, signature)),
+            Eip1559Transaction::TYPE => Eip1559Transaction::decode_signed(src)
+                .map(|(tx, signature)| Self::Eip1559(tx, signature)),
+            Eip4844Transaction::TYPE => match is_network(payload) {
+                true => Eip4844Transaction::decode_network(src),
+                false => Eip4844Transaction::decode_signed(src),
+            }
+            .map(|(tx, signature)| Self::Eip4844(tx, signature)),
+            Eip7702Transaction::TYPE => Eip7702Transaction::decode_signed(src)
+                .map(|(tx, signature)| Self::Eip7702(tx, signature)),
+            // EIP-2718 reserves the type range of `[0, 0x7f]`
+            0x00..=0x7f => return Err(DecodeError::UnknownType { ty, payload }),
+            // RLP string is neither a type byte nor a legacy transaction
+            0x80..=0xbf => return Err(rlp::Error::UnexpectedString.into()),
+            0xc0..=0xff => LegacyTransaction::decode_signed(src)
+                .map(|(tx, signature)| Self::Legacy(tx, signature)),
+        };
+
+        tx.map_err(DecodeError::from)
+    }
+
+    /// EIP-2718 transaction type, `0x00` for the legacy transaction
+    pub const fn ty(&self) -> u8 {
+        match self {
+            Self::Legacy(..) => 0x00,
+            Self::Eip2930(..) => Eip2930Transaction::TYPE,
+            Self::Eip1559(..) => Eip1559Transaction::TYPE,
+            Self::Eip4844(..) => Eip4844Transaction::TYPE,
+            Self::Eip7702(..) => Eip7702Transaction::TYPE,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn signature(&self) -> &Signature {
+        dispatch!(self, _tx, signature => signature)
+    }
+
+    /// Exact length of the signed transaction encoding
+    pub fn length(&self) -> usize {
+        dispatch!(self, tx, signature => tx.signed_length(signature))
+    }
+
+    /// Encode the signed transaction into the beginning of the buffer
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the encoding product;
+    pub fn encode(&self, buf: &mut [u8]) -> usize {
+        dispatch!(self, tx, signature => tx.encode_signed(signature, buf))
+    }
+
+    /// Transaction hash, using the buffer as scratch space
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the signed encoding;
+    pub fn hash(&self, buf: &mut [u8]) -> Bytes<U32> {
+        let n = self.encode(buf);
+        keccak256(&buf[..n])
+    }
+
+    /// Keccak hash of the signing payload, using the buffer as scratch space
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the signing payload;
+    pub fn sighash(&self, buf: &mut [u8]) -> Bytes<U32> {
+        dispatch!(self, tx, _signature => tx.signature_hash(buf))
+    }
+
+    /// Recover the sender, using the buffer as scratch space
+    ///
+    /// ## Panics
+    ///
+    /// - if buffer capacity is incufficient to contain the signing payload;
+    pub fn recover_sender(&self, buf: &mut [u8]) -> Result<Address, Error> {
+        let hash = self.sighash(buf);
+        secp256k1::recover(&hash.into_array(), self.signature()).map_err(Error::from)
+    }
+}
+
+/// Check if the blob transaction payload is in the network form, which
+/// starts with the signed list rather than the chain id
+fn is_network(mut payload: &[u8]) -> bool {
+    rlp::decode_list(&mut payload)
+        .and_then(|mut items| rlp::Header::decode(&mut items))
+        .is_ok_and(|header| header.list)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        address,
+        secp256k1::SigningKey,
+        transaction::{
+            kzg_to_versioned_hash, AccessList, Authorization, AuthorizationList, Blob, BlobSidecar,
+            VersionedHashes, BYTES_PER_BLOB,
+        },
+        uint256, unhex,
+    };
+
+    // EIP-155 example
+    const LEGACY: [u8; 110] = unhex!(
+        "f86c098504a817c800825208943535353535353535353535353535353535353535880d\
+        e0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1\
+        590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1\
+        966a3b6d83"
+    );
+
+    #[test]
+    fn test_legacy() {
+        let tx = TypedTransaction::decode(&LEGACY).unwrap();
+        assert!(matches!(tx, TypedTransaction::Legacy(tx, _) if tx.chain_id == Some(1)));
+        assert_eq!(tx.ty(), 0x00);
+        assert_eq!(tx.length(), LEGACY.len());
+
+        let mut buf = [0u8; 128];
+        let n = tx.encode(&mut buf);
+        assert_eq!(&buf[..n], &LEGACY);
+        assert_eq!(tx.hash(&mut buf), keccak256(&LEGACY));
+
+        let sighash: [u8; 32] =
+            unhex!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53");
+        assert_eq!(tx.sighash(&mut buf).into_array(), sighash);
+        assert_eq!(
+            tx.recover_sender(&mut buf),
+            Ok(address!("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"))
+        );
+    }
+
+    #[test]
+    fn test_typed() {
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let to = address!("0x3535353535353535353535353535353535353535");
+        let hashes = [Bytes::from_array([0x01u8; 32])];
+        let authorizations = [Authorization {
+            chain_id: uint256::ONE,
+            address: to,
+            nonce: 0,
+        }
+        .sign(&key)];
+
+        let eip2930 = Eip2930Transaction {
+            chain_id: 1,
+            gas_price: 1_000_000_000,
+            to: Some(to),
+            ..Default::default()
+        };
+        let eip1559 = Eip1559Transaction {
+            chain_id: 1,
+            max_fee_per_gas: 1_000_000_000,
+            to: None,
+            data: &[0x60, 0x80],
+            ..Default::default()
+        };
+        let eip4844 = Eip4844Transaction {
+            chain_id: 1,
+            to,
+            value: uint256::from(1u64),
+            blob_versioned_hashes: VersionedHashes::new(&hashes),
+            ..Default::default()
+        };
+        let eip7702 = Eip7702Transaction {
+            chain_id: 1,
+            to,
+            access_list: AccessList::EMPTY,
+            authorization_list: AuthorizationList::new(&authorizations),
+            ..Default::default()
+        };
+
+        let mut raw = [0u8; 512];
+        let mut buf = [0u8; 512];
+
+        let n = eip2930.sign(&key, &mut raw);
+        let tx = TypedTransaction::decode(&raw[..n]).unwrap();
+        assert!(matches!(tx, TypedTransaction::Eip2930(tx, _) if tx == eip2930));
+        assert_eq!(tx.sighash(&mut buf), eip2930.signature_hash(&mut buf));
+        assert_eq!(tx.recover_sender(&mut buf), Ok(key.address()));
+
+        let n = eip1559.sign(&key, &mut raw);
+        let tx = TypedTransaction::decode(&raw[..n]).unwrap();
+        assert!(matches!(tx, TypedTransaction::Eip1559(tx, _) if tx == eip1559));
+        assert_eq!(tx.hash(&mut buf), keccak256(&raw[..n]));
+        assert_eq!(tx.recover_sender(&mut buf), Ok(key.address()));
+
+        let n = eip4844.sign(&key, &mut raw);
+        let tx = TypedTransaction::decode(&raw[..n]).unwrap();
+        assert!(matches!(tx, TypedTransaction::Eip4844(tx, _) if tx == eip4844));
+        assert_eq!(tx.ty(), 0x03);
+        assert_eq!(tx.recover_sender(&mut buf), Ok(key.address()));
+
+        let n = eip7702.sign(&key, &mut raw);
+        let tx = TypedTransaction::decode(&raw[..n]).unwrap();
+        assert!(matches!(tx, TypedTransaction::Eip7702(tx, _) if tx == eip7702));
+        assert_eq!(tx.length(), n);
+        assert_eq!(tx.recover_sender(&mut buf), Ok(key.address()));
+    }
+
+    #[test]
+    fn test_blob_network() {
+        static BLOBS: [Blob; 1] = [[0u8; BYTES_PER_BLOB]];
+
+        let key = SigningKey::from_bytes(&[0x46u8; 32]).unwrap();
+        let commitments = [Bytes::default()];
+        let sidecar = BlobSidecar {
+            blobs: &BLOBS,
+            commitments: &commitments,
+            proofs: &commitments,
+        };
+
+        let hashes = [kzg_to_versioned_hash(&commitments[0])];
+        let eip4844 = Eip4844Transaction {
+            chain_id: 1,
+            to: address!("0x3535353535353535353535353535353535353535"),
+            blob_versioned_hashes: VersionedHashes::new(&hashes),
+            ..Default::default()
+        };
+
+        let mut raw = [0u8; BYTES_PER_BLOB + 512];
+        let n = eip4844.sign_network(&key, &sidecar, &mut raw);
+
+        // The network form is decoded into the canonical transaction
+        let tx = TypedTransaction::decode(&raw[..n]).unwrap();
+        assert!(matches!(tx, TypedTransaction::Eip4844(tx, _) if tx == eip4844));
+        assert_eq!(tx.recover_sender(&mut [0u8; 256]), Ok(key.address()));
+
+        let mut canonical = [0u8; 256];
+        let m = eip4844.encode_signed(tx.signature(), &mut canonical);
+        assert_eq!(tx.hash(&mut [0u8; 256]), keccak256(&canonical[..m]));
+
+        assert_eq!(
+            TypedTransaction::decode(&raw[..n - 1]),
+            Err(DecodeError::Invalid(Error::Rlp(rlp::Error::UnexpectedEnd)))
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            TypedTransaction::decode(&[]),
+            Err(DecodeError::Invalid(Error::Rlp(rlp::Error::UnexpectedEnd)))
+        );
+        assert_eq!(
+            TypedTransaction::decode(&[0x00, 0xc0]),
+            Err(DecodeError::UnknownType {
+                ty: 0x00,
+                payload: &[0xc0]
+            })
+        );
+        assert_eq!(
+            TypedTransaction::decode(&[0x05, 0x01, 0x02]),
+            Err(DecodeError::UnknownType {
+                ty: 0x05,
+                payload: &[0x01, 0x02]
+            })
+        );
+        assert_eq!(
+            TypedTransaction::decode(&[0x83, 0x01, 0x02, 0x03]),
+            Err(DecodeError::Invalid(Error::Rlp(
+                rlp::Error::UnexpectedString
+            )))
+        );
+        assert_eq!(
+            TypedTransaction::decode(&[0x02, 0xc0]),
+            Err(DecodeError::Invalid(Error::Rlp(rlp::Error::UnexpectedEnd)))
+        );
+        assert_eq!(
+            TypedTransaction::decode(&LEGACY[..LEGACY.len() - 1]),
+            Err(DecodeError::Invalid(Error::Rlp(rlp::Error::UnexpectedEnd)))
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{
+    as_hex,
+    encoding::Data,
+    rlp::{List, ListIter},
+    typenum::U32,
+    Bytes,
+};
+use serde::{ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Word serialized with `as_hex`, deserialized strictly
+pub(super) struct Word(pub(super) Bytes<U32>);
+
+impl Serialize for Word {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        as_hex::serialize(self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Word {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Data::deserialize(deserializer).map(|data| Self(data.0))
+    }
+}
+
+/// List of 32 bytes words, such as the storage keys or the blob versioned
+/// hashes
+pub type Words<'a> = List<'a, Bytes<U32>>;
+
+/// Iterator over the words
+pub type WordsIter<'a> = ListIter<'a, Bytes<U32>>;
+
+impl Serialize for Words<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for word in self.iter() {
+            seq.serialize_element(&Word(word))?;
+        }
+        seq.end()
+    }
+}
@@ -0,0 +1,514 @@
+//! Ether units
+//!
+//! Contains fixed-point conversions between human readable amounts like
+//! `1.5 ether` or `30 gwei` and `uint256` base unit (wei) values, as well as
+//! formatting of arbitrary ERC-20 token amounts given their decimals.
+//!
+//! All conversions are exact: whenever the amount can not be represented
+//! without losing precision either an error is returned or the rounding mode
+//! is required to be selected explicitly.
+//!
+//! ```rust
+//! use ethgen::{uint256, units};
+//!
+//!
+//! let value = units::parse("1.5 ether").unwrap();
+//! assert_eq!(value, uint256::from(1_500_000_000_000_000_000u64));
+//!
+//! let usdc = units::parse_units("12.34", 6).unwrap();
+//! assert_eq!("12.34", format!("{}", units::format_units(usdc, 6)));
+//! ```
+
+use crate::biguint::{uint256, ParseIntError, UINT256_DIGITS};
+use core::{fmt, str::FromStr};
+
+/// Maximum amount of decimals that can be represented by `uint256`
+pub const MAX_DECIMALS: u8 = 77;
+
+/// Units conversion error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Empty string or missing digits
+    Empty,
+    /// Unexpected character in the amount
+    InvalidDigit,
+    /// The amount doesn't fit into `uint256`
+    Overflow,
+    /// The amount has more fractional digits than can be represented
+    PrecisionLoss,
+    /// Unknown unit name
+    UnknownUnit,
+    /// Decimals exceed `MAX_DECIMALS`
+    InvalidDecimals,
+}
+
+impl From<ParseIntError> for Error {
+    fn from(value: ParseIntError) -> Self {
+        match value {
+            ParseIntError::Empty => Self::Empty,
+            ParseIntError::InvalidDigit => Self::InvalidDigit,
+            ParseIntError::Overflow => Self::Overflow,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "cannot parse amount from empty string"),
+            Self::InvalidDigit => write!(f, "invalid digit found in amount"),
+            Self::Overflow => write!(f, "amount too large to fit in uint256"),
+            Self::PrecisionLoss => write!(f, "amount cannot be represented without precision loss"),
+            Self::UnknownUnit => write!(f, "unknown unit"),
+            Self::InvalidDecimals => write!(f, "decimals exceed {}", MAX_DECIMALS),
+        }
+    }
+}
+
+/// Ether denomination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Wei,
+    Kwei,
+    Mwei,
+    Gwei,
+    Szabo,
+    Finney,
+    Ether,
+}
+
+impl Unit {
+    /// Amount of decimals of the unit relative to wei
+    pub const fn decimals(&self) -> u8 {
+        match self {
+            Self::Wei => 0,
+            Self::Kwei => 3,
+            Self::Mwei => 6,
+            Self::Gwei => 9,
+            Self::Szabo => 12,
+            Self::Finney => 15,
+            Self::Ether => 18,
+        }
+    }
+}
+
+impl FromStr for Unit {
+    type Err = Error;
+
+    /// Parse a unit name, case insensitive, including the alternative names
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const NAMES: [(&str, Unit); 12] = [
+            ("wei", Unit::Wei),
+            ("kwei", Unit::Kwei),
+            ("babbage", Unit::Kwei),
+            ("mwei", Unit::Mwei),
+            ("lovelace", Unit::Mwei),
+            ("gwei", Unit::Gwei),
+            ("shannon", Unit::Gwei),
+            ("szabo", Unit::Szabo),
+            ("microether", Unit::Szabo),
+            ("finney", Unit::Finney),
+            ("milliether", Unit::Finney),
+            ("ether", Unit::Ether),
+        ];
+
+        NAMES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+            .map(|(_, unit)| *unit)
+            .ok_or(Error::UnknownUnit)
+    }
+}
+
+/// Rounding mode applied to the digits which can not be represented
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+    /// Fail with `Error::PrecisionLoss` if any non-zero digit is dropped
+    #[default]
+    Exact,
+    /// Truncate the dropped digits
+    Down,
+    /// Round up if any non-zero digit is dropped
+    Up,
+    /// Round to the nearest, ties away from zero
+    HalfUp,
+    /// Round to the nearest, ties to even (banker's rounding)
+    HalfEven,
+}
+
+impl Rounding {
+    /// Decide whether the kept value should be incremented
+    fn round_up(&self, odd: bool, first: u8, rest: bool) -> Result<bool, Error> {
+        let dropped = first != 0 || rest;
+
+        match self {
+            Self::Exact if dropped => Err(Error::PrecisionLoss),
+            Self::Exact | Self::Down => Ok(false),
+            Self::Up => Ok(dropped),
+            Self::HalfUp => Ok(first >= 5),
+            Self::HalfEven => Ok(first > 5 || (first == 5 && (rest || odd))),
+        }
+    }
+}
+
+#[inline(always)]
+fn pow10(exp: u8) -> uint256 {
+    uint256::from_u64(10).pow(exp as u32)
+}
+
+/// Parse a decimal amount into base units given the amount of decimals
+///
+/// Accepts an integer part, a fractional part or both separated by a dot.
+/// Fails with `Error::PrecisionLoss` if the amount has more non-zero
+/// fractional digits than `decimals`.
+///
+/// ```rust
+/// use ethgen::{uint256, units};
+///
+///
+/// assert_eq!(units::parse_units("30", 9), Ok(uint256::from(30_000_000_000u64)));
+/// assert_eq!(units::parse_units(".5", 1), Ok(uint256::from(5u64)));
+/// assert_eq!(units::parse_units("1.250", 2), Ok(uint256::from(125u64)));
+/// assert_eq!(units::parse_units("1.255", 2), Err(units::Error::PrecisionLoss));
+/// ```
+pub fn parse_units(src: &str, decimals: u8) -> Result<uint256, Error> {
+    parse_units_rounded(src, decimals, Rounding::Exact)
+}
+
+/// Parse a decimal amount into base units with an explicit rounding mode
+///
+/// ```rust
+/// use ethgen::{uint256, units::{self, Rounding}};
+///
+///
+/// let value = units::parse_units_rounded("1.255", 2, Rounding::HalfEven);
+/// assert_eq!(value, Ok(uint256::from(126u64)));
+///
+/// let value = units::parse_units_rounded("1.255", 2, Rounding::Down);
+/// assert_eq!(value, Ok(uint256::from(125u64)));
+/// ```
+pub fn parse_units_rounded(src: &str, decimals: u8, rounding: Rounding) -> Result<uint256, Error> {
+    if decimals > MAX_DECIMALS {
+        return Err(Error::InvalidDecimals);
+    }
+
+    let (int, frac) = match src.split_once('.') {
+        Some((int, frac)) => (int, frac),
+        None => (src, ""),
+    };
+
+    if int.is_empty() && frac.is_empty() {
+        return Err(Error::Empty);
+    }
+
+    // Signs and radix prefixes are accepted by the integer parser, which is
+    // not desired for the amounts.
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if !digits(int) || !digits(frac) {
+        return Err(Error::InvalidDigit);
+    }
+
+    let (kept, dropped) = frac.split_at(frac.len().min(decimals as usize));
+
+    let int = match int.is_empty() {
+        true => uint256::ZERO,
+        false => int.parse::<uint256>()?,
+    };
+    let kept_value = match kept.is_empty() {
+        true => uint256::ZERO,
+        false => kept.parse::<uint256>()?,
+    };
+
+    // The fractional part is right-padded with zeroes up to `decimals`
+    let scale = pow10(decimals - kept.len() as u8);
+    let value = int
+        .checked_mul(&pow10(decimals))
+        .and_then(|int| int.checked_add(&kept_value.checked_mul(&scale)?))
+        .ok_or(Error::Overflow)?;
+
+    let (first, rest) = match dropped.as_bytes() {
+        [] => (0u8, false),
+        [first, rest @ ..] => (first - b'0', rest.iter().any(|b| *b != b'0')),
+    };
+
+    match rounding.round_up(value.bit(0), first, rest)? {
+        true => value.checked_add(&uint256::ONE).ok_or(Error::Overflow),
+        false => Ok(value),
+    }
+}
+
+/// Parse an amount with an ether unit into wei
+///
+/// The unit is separated from the amount by optional whitespace, the amount
+/// without a unit is treated as wei.
+///
+/// ```rust
+/// use ethgen::{uint256, units};
+///
+///
+/// assert_eq!(units::parse("30 gwei"), Ok(uint256::from(30_000_000_000u64)));
+/// assert_eq!(units::parse("0.01ether"), Ok(uint256::from(10_000_000_000_000_000u64)));
+/// assert_eq!(units::parse("21000"), Ok(uint256::from(21000u64)));
+/// assert_eq!(units::parse("1.5 wei"), Err(units::Error::PrecisionLoss));
+/// assert_eq!(units::parse("1 dogecoin"), Err(units::Error::UnknownUnit));
+/// ```
+pub fn parse(src: &str) -> Result<uint256, Error> {
+    let src = src.trim();
+    let split = src
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(src.len());
+
+    let (amount, unit) = src.split_at(split);
+    let unit = match unit.is_empty() {
+        true => Unit::Wei,
+        false => unit.parse()?,
+    };
+
+    parse_units(amount.trim_end(), unit.decimals())
+}
+
+/// Format base units as a decimal amount given the amount of decimals
+///
+/// The result implements `Display` without any allocation. By default the
+/// trailing zeroes of the fractional part are omitted, use `with_precision`
+/// to output a fixed amount of fractional digits.
+///
+/// ## Panics
+///
+/// - if `decimals` exceed `MAX_DECIMALS`;
+///
+/// <br>
+///
+/// ## Example
+///
+/// ```rust
+/// use ethgen::{uint256, units::{self, Unit}};
+///
+///
+/// let value = uint256::from(1_500_000u64);
+///
+/// assert_eq!("1.5", format!("{}", units::format_units(value, 6)));
+/// assert_eq!("0.0015", format!("{}", units::format_units(value, Unit::Gwei.decimals())));
+/// assert_eq!("1500000", format!("{}", units::format_units(value, 0)));
+/// ```
+pub fn format_units(value: uint256, decimals: u8) -> FixedPoint {
+    assert!(decimals <= MAX_DECIMALS, "Invalid amount of decimals");
+
+    FixedPoint {
+        value,
+        decimals,
+        fixed: false,
+    }
+}
+
+/// Format wei as a decimal amount of ether
+pub fn format_ether(value: uint256) -> FixedPoint {
+    format_units(value, Unit::Ether.decimals())
+}
+
+/// Format wei as a decimal amount of gwei
+pub fn format_gwei(value: uint256) -> FixedPoint {
+    format_units(value, Unit::Gwei.decimals())
+}
+
+/// Fixed-point decimal representation of base units
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedPoint {
+    value: uint256,
+    decimals: u8,
+    fixed: bool,
+}
+
+impl FixedPoint {
+    /// Round to the given amount of fractional digits
+    ///
+    /// The resulting representation always contains exactly `digits`
+    /// fractional digits, including trailing zeroes.
+    ///
+    /// ```rust
+    /// use ethgen::{uint256, units::{self, Rounding}};
+    ///
+    ///
+    /// let value = uint256::from(1_234_567_890_000_000_000u64);
+    /// let eth = units::format_ether(value);
+    ///
+    /// let rounded = eth.with_precision(4, Rounding::HalfUp).unwrap();
+    /// assert_eq!("1.2346", format!("{}", rounded));
+    ///
+    /// let padded = eth.with_precision(20, Rounding::Exact).unwrap();
+    /// assert_eq!("1.23456789000000000000", format!("{}", padded));
+    ///
+    /// let err = eth.with_precision(4, Rounding::Exact);
+    /// assert_eq!(err, Err(units::Error::PrecisionLoss));
+    /// ```
+    pub fn with_precision(self, digits: u8, rounding: Rounding) -> Result<Self, Error> {
+        if digits > MAX_DECIMALS {
+            return Err(Error::InvalidDecimals);
+        }
+
+        if digits >= self.decimals {
+            return Ok(Self {
+                fixed: true,
+                decimals: digits,
+                value: self
+                    .value
+                    .checked_mul(&pow10(digits - self.decimals))
+                    .ok_or(Error::Overflow)?,
+            });
+        }
+
+        let drop = self.decimals - digits;
+        let (value, rem) = self
+            .value
+            .checked_div_rem(&pow10(drop))
+            .ok_or(Error::Overflow)?;
+        let (first, rest) = rem
+            .checked_div_rem(&pow10(drop - 1))
+            .ok_or(Error::Overflow)?;
+
+        let value = match rounding.round_up(value.bit(0), first.as_u64() as u8, !rest.is_zero())? {
+            true => value.checked_add(&uint256::ONE).ok_or(Error::Overflow)?,
+            false => value,
+        };
+
+        Ok(Self {
+            value,
+            decimals: digits,
+            fixed: true,
+        })
+    }
+
+    /// Value in base units of the representation
+    pub fn value(&self) -> uint256 {
+        self.value
+    }
+
+    /// Amount of fractional digits of the representation
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+}
+
+impl fmt::Display for FixedPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = [0u8; UINT256_DIGITS];
+        let pos = self.value.write_decimal(&mut digits);
+        let digits = &digits[pos..];
+
+        // Integer part, a dot and the fractional part padded up to decimals
+        let mut buf = [b'0'; UINT256_DIGITS + 2 + MAX_DECIMALS as usize];
+        let decimals = self.decimals as usize;
+
+        let (int, frac) = digits.split_at(digits.len().saturating_sub(decimals));
+        let int_len = int.len().max(1);
+        buf[int_len - int.len()..int_len].copy_from_slice(int);
+
+        let frac_start = int_len + 1;
+        let frac_end = frac_start + decimals;
+        buf[frac_end - frac.len()..frac_end].copy_from_slice(frac);
+
+        let frac_end = match self.fixed {
+            true => frac_end,
+            false => {
+                let zeroes = buf[frac_start..frac_end]
+                    .iter()
+                    .rev()
+                    .take_while(|b| **b == b'0')
+                    .count();
+                frac_end - zeroes
+            }
+        };
+
+        let end = match frac_end > frac_start {
+            true => {
+                buf[int_len] = b'.';
+                frac_end
+            }
+            false => int_len,
+        };
+
+        // Safe since only ascii digits and a dot are written into the buffer
+        let s = unsafe { core::str::from_utf8_unchecked(&buf[..end]) };
+        f.pad(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_units() {
+        assert_eq!(parse_units("0", 18), Ok(uint256::ZERO));
+        assert_eq!(parse_units("0.000000000000000001", 18), Ok(uint256::ONE));
+        assert_eq!(parse_units("1.", 2), Ok(uint256::from(100u64)));
+        assert_eq!(parse_units(".", 2), Err(Error::Empty));
+        assert_eq!(parse_units("", 2), Err(Error::Empty));
+        assert_eq!(parse_units("+1", 2), Err(Error::InvalidDigit));
+        assert_eq!(parse_units("0x1", 2), Err(Error::InvalidDigit));
+        assert_eq!(parse_units("1.2.3", 2), Err(Error::InvalidDigit));
+        assert_eq!(parse_units("1", 78), Err(Error::InvalidDecimals));
+        assert_eq!(
+            parse_units(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                1
+            ),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_rounding() {
+        let cases = [
+            ("1.25", Rounding::Down, 12u64),
+            ("1.25", Rounding::Up, 13),
+            ("1.21", Rounding::Up, 13),
+            ("1.20", Rounding::Up, 12),
+            ("1.25", Rounding::HalfUp, 13),
+            ("1.24", Rounding::HalfUp, 12),
+            ("1.25", Rounding::HalfEven, 12),
+            ("1.35", Rounding::HalfEven, 14),
+            ("1.2501", Rounding::HalfEven, 13),
+        ];
+
+        for (src, rounding, expected) in cases {
+            let value = parse_units_rounded(src, 1, rounding);
+            assert_eq!(value, Ok(uint256::from(expected)), "{} {:?}", src, rounding);
+        }
+    }
+
+    #[test]
+    fn test_format_units() {
+        let mut buf = [0u8; 256];
+
+        let cases: [(FixedPoint, &str); 5] = [
+            (format_units(uint256::ZERO, 18), "0"),
+            (format_units(uint256::ONE, 18), "0.000000000000000001"),
+            (format_units(uint256::from(100u64), 2), "1"),
+            (format_units(uint256::from(105u64), 0), "105"),
+            (
+                format_units(uint256::ZERO, 2)
+                    .with_precision(2, Rounding::Exact)
+                    .unwrap(),
+                "0.00",
+            ),
+        ];
+
+        for (value, expected) in cases {
+            let mut w = Writer(&mut buf, 0);
+            fmt::write(&mut w, format_args!("{}", value)).unwrap();
+            let n = w.1;
+            assert_eq!(&buf[..n], expected.as_bytes());
+        }
+    }
+
+    struct Writer<'a>(&'a mut [u8], usize);
+
+    impl fmt::Write for Writer<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0[self.1..self.1 + s.len()].copy_from_slice(s.as_bytes());
+            self.1 += s.len();
+            Ok(())
+        }
+    }
+}